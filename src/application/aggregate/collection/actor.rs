@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        aggregate::collection::{
+            cmd::{CollectionCommand, CollectionCommandError, CollectionCommandKind},
+            event::CollectionComponentsChangedEvent,
+        },
+        service::collection_membership::{CollectionMembershipValidator, ComponentEventStream},
+        shared::event::bus::EventBus,
+    },
+    domain::{collection::Collection, shared::aggregate::EventSourcedAggregate},
+};
+use actix::{Actor, Context, Handler};
+
+pub struct CollectionActor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    pub state: Collection,
+    event_bus: Arc<EB>,
+    membership: Arc<CollectionMembershipValidator<S>>,
+}
+
+impl<EB, S> CollectionActor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    #[must_use]
+    pub const fn new(
+        state: Collection,
+        event_bus: Arc<EB>,
+        membership: Arc<CollectionMembershipValidator<S>>,
+    ) -> Self {
+        Self {
+            state,
+            event_bus,
+            membership,
+        }
+    }
+}
+
+impl<EB, S> Actor for CollectionActor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    type Context = Context<Self>;
+}
+
+impl<EB, S> Handler<CollectionCommand> for CollectionActor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    type Result = Result<(), CollectionCommandError>;
+
+    fn handle(&mut self, cmd: CollectionCommand, _ctx: &mut Context<Self>) -> Self::Result {
+        let CollectionCommand { id, kind } = cmd;
+
+        if !self.state.id().eq(&id) {
+            return Err(CollectionCommandError::NotFound(id));
+        }
+
+        let events = match kind {
+            CollectionCommandKind::ReplaceComponents(components) => {
+                self.membership.validate(&components)?;
+                self.state.replace_components(&components)?
+            }
+            CollectionCommandKind::Create(_) => {
+                return Err(CollectionCommandError::AlreadyExists(id));
+            }
+        };
+
+        tracing::info!("Persist events");
+        for event in events {
+            self.state.apply(&event)?;
+            let _ = self
+                .event_bus
+                .publish(CollectionComponentsChangedEvent::new(event));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::service::collection_membership::{
+        CollectionMembershipError, InvalidMembers,
+    };
+    use crate::application::shared::event::Event;
+    use crate::application::shared::event::error::EventBusError;
+    use crate::domain::collection::event::CollectionEvent;
+    use crate::domain::collection::id::CollectionId;
+    use crate::domain::component::Component;
+    use crate::domain::component::event::ComponentEvent;
+    use crate::domain::component::id::ComponentId;
+    use crate::infrastructure::journal::in_memory_component::InMemoryComponentJournal;
+    use actix::Addr;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingBus(Mutex<Vec<CollectionEvent>>);
+
+    impl EventBus for RecordingBus {
+        fn publish<E: Event + Clone + 'static>(&self, event: E) -> Result<(), EventBusError> {
+            if let Some(payload) = event.as_payload::<CollectionEvent>() {
+                self.0.lock().unwrap().push(payload.clone());
+            }
+            Ok(())
+        }
+    }
+
+    fn dummy_id(name: &str) -> ComponentId {
+        ComponentId::from_str(&format!("registry.test/namespace/{name}:v0")).unwrap()
+    }
+
+    fn collection_id() -> CollectionId {
+        CollectionId::new("collection").unwrap()
+    }
+
+    /// Actor of a collection holding `a`, over a journal where `a` and `b` are registered
+    /// and `c` is deprecated.
+    fn actor() -> (
+        Addr<CollectionActor<RecordingBus, InMemoryComponentJournal>>,
+        Arc<RecordingBus>,
+    ) {
+        let journal = InMemoryComponentJournal::default();
+        for name in ["a", "b", "c"] {
+            journal.append(Component::register(dummy_id(name)));
+        }
+        journal.append(ComponentEvent::ComponentDeprecated {
+            component_id: dummy_id("c"),
+        });
+
+        let event = Collection::create(collection_id(), HashSet::from([dummy_id("a")])).unwrap();
+        let bus = Arc::new(RecordingBus::default());
+        let actor = CollectionActor::new(
+            Collection::from_initial_event(&event).unwrap(),
+            bus.clone(),
+            Arc::new(CollectionMembershipValidator::new(Arc::new(journal))),
+        )
+        .start();
+        (actor, bus)
+    }
+
+    fn replace(names: &[&str]) -> CollectionCommand {
+        CollectionCommand {
+            id: collection_id(),
+            kind: CollectionCommandKind::ReplaceComponents(
+                names.iter().map(|name| dummy_id(name)).collect(),
+            ),
+        }
+    }
+
+    #[actix::test]
+    async fn replacing_components_should_publish_the_changes() {
+        let (actor, bus) = actor();
+
+        actor.send(replace(&["b"])).await.unwrap().unwrap();
+
+        assert_eq!(
+            *bus.0.lock().unwrap(),
+            vec![
+                CollectionEvent::ComponentDropped {
+                    collection_id: collection_id(),
+                    component_id: dummy_id("a"),
+                },
+                CollectionEvent::ComponentAdded {
+                    collection_id: collection_id(),
+                    component_id: dummy_id("b"),
+                },
+            ]
+        );
+    }
+
+    #[actix::test]
+    async fn deprecated_members_should_be_rejected() {
+        let (actor, bus) = actor();
+
+        let err = actor.send(replace(&["a", "c"])).await.unwrap().unwrap_err();
+
+        assert_eq!(
+            err,
+            CollectionCommandError::Membership(CollectionMembershipError::InvalidMembers(
+                InvalidMembers {
+                    unknown: vec![],
+                    deprecated: vec![dummy_id("c")],
+                }
+            ))
+        );
+        assert!(bus.0.lock().unwrap().is_empty());
+    }
+
+    #[actix::test]
+    async fn commands_for_other_collections_should_be_rejected() {
+        let (actor, _) = actor();
+        let other = CollectionId::new("other").unwrap();
+
+        let err = actor
+            .send(CollectionCommand {
+                id: other.clone(),
+                kind: CollectionCommandKind::ReplaceComponents(HashSet::from([dummy_id("b")])),
+            })
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, CollectionCommandError::NotFound(other));
+
+        let err = actor
+            .send(CollectionCommand {
+                id: collection_id(),
+                kind: CollectionCommandKind::Create(HashSet::from([dummy_id("b")])),
+            })
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, CollectionCommandError::AlreadyExists(collection_id()));
+    }
+}
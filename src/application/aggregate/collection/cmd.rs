@@ -0,0 +1,37 @@
+use crate::application::service::collection_membership::CollectionMembershipError;
+use crate::domain::collection::CollectionError;
+use crate::domain::collection::id::CollectionId;
+use crate::domain::component::id::ComponentId;
+use actix::Message;
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "Result<(), CollectionCommandError>")]
+pub struct CollectionCommand {
+    pub id: CollectionId,
+    pub kind: CollectionCommandKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum CollectionCommandKind {
+    Create(HashSet<ComponentId>),
+    ReplaceComponents(HashSet<ComponentId>),
+}
+
+/// Failure of a [`CollectionCommand`]: either the aggregate rejected it, or one of the
+/// cross-aggregate checks performed by the application layer did.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CollectionCommandError {
+    #[error("Collection `{0}` already exists")]
+    AlreadyExists(CollectionId),
+
+    #[error("Collection `{0}` does not exist")]
+    NotFound(CollectionId),
+
+    #[error(transparent)]
+    Membership(#[from] CollectionMembershipError),
+
+    #[error(transparent)]
+    Collection(#[from] CollectionError),
+}
@@ -0,0 +1,78 @@
+use crate::application::shared::event::Event;
+use crate::domain::collection::event::CollectionEvent;
+
+use actix::Message;
+use std::any::Any;
+use std::fmt::Debug;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Published once a collection has been created.
+#[derive(Debug, Clone)]
+pub struct CollectionCreatedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: CollectionEvent,
+}
+
+impl CollectionCreatedEvent {
+    #[must_use]
+    pub fn new(payload: CollectionEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for CollectionCreatedEvent {
+    type Result = ();
+}
+
+impl Event for CollectionCreatedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published for each component added to or dropped from an existing collection.
+#[derive(Debug, Clone)]
+pub struct CollectionComponentsChangedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: CollectionEvent,
+}
+
+impl CollectionComponentsChangedEvent {
+    #[must_use]
+    pub fn new(payload: CollectionEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for CollectionComponentsChangedEvent {
+    type Result = ();
+}
+
+impl Event for CollectionComponentsChangedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
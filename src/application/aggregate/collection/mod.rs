@@ -1,2 +1,4 @@
+pub mod actor;
 pub mod cmd;
 pub mod event;
+pub mod supervisor;
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix::{Actor, Addr, Context, Handler};
+
+use crate::application::aggregate::collection::actor::CollectionActor;
+use crate::application::aggregate::collection::cmd::{
+    CollectionCommand, CollectionCommandError, CollectionCommandKind,
+};
+use crate::application::aggregate::collection::event::CollectionCreatedEvent;
+use crate::application::service::collection_membership::{
+    CollectionMembershipValidator, ComponentEventStream,
+};
+use crate::application::shared::command::RegistersCommands;
+use crate::application::shared::command::bus::CommandBus;
+use crate::application::shared::command::handler::HandlesCommand;
+use crate::application::shared::event::bus::EventBus;
+use crate::domain::collection::Collection;
+use crate::domain::collection::id::CollectionId;
+use crate::domain::shared::aggregate::EventSourcedAggregate;
+
+pub struct CollectionSupervisor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    children: HashMap<CollectionId, Addr<CollectionActor<EB, S>>>,
+    event_bus: Arc<EB>,
+    membership: Arc<CollectionMembershipValidator<S>>,
+}
+
+impl<EB, S> Actor for CollectionSupervisor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    type Context = Context<Self>;
+}
+
+impl<EB, S> CollectionSupervisor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    pub fn new(event_bus: Arc<EB>, components: Arc<S>) -> Self {
+        Self {
+            children: HashMap::new(),
+            event_bus,
+            membership: Arc::new(CollectionMembershipValidator::new(components)),
+        }
+    }
+}
+
+impl<EB, S> Handler<CollectionCommand> for CollectionSupervisor<EB, S>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    type Result = Result<(), CollectionCommandError>;
+
+    fn handle(&mut self, cmd: CollectionCommand, _ctx: &mut Context<Self>) -> Self::Result {
+        let CollectionCommand { ref id, ref kind } = cmd;
+
+        if let CollectionCommandKind::Create(components) = kind {
+            if self.children.contains_key(id) {
+                return Err(CollectionCommandError::AlreadyExists(id.clone()));
+            }
+            self.membership.validate(components)?;
+            let event = Collection::create(id.clone(), components.clone())?;
+            let collection = Collection::from_initial_event(&event)?;
+            tracing::info!("Persist {collection:?} in journal");
+            let actor =
+                CollectionActor::new(collection, self.event_bus.clone(), self.membership.clone())
+                    .start();
+            self.children.insert(id.clone(), actor);
+            let _ = self.event_bus.publish(CollectionCreatedEvent::new(event));
+            Ok(())
+        } else {
+            let actor = self
+                .children
+                .get(id)
+                .ok_or_else(|| CollectionCommandError::NotFound(id.clone()))?;
+            actor.do_send(cmd);
+            Ok(())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<EB, S> HandlesCommand<CollectionCommand> for Addr<CollectionSupervisor<EB, S>>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    async fn handle(&self, cmd: CollectionCommand) -> Result<(), String> {
+        self.do_send(cmd);
+        Ok(())
+    }
+}
+
+impl<EB, S> RegistersCommands for Addr<CollectionSupervisor<EB, S>>
+where
+    EB: EventBus + Send + Sync + 'static,
+    S: ComponentEventStream + ?Sized + 'static,
+{
+    fn register_with(self, bus: &mut CommandBus) {
+        bus.register_handler::<CollectionCommand, Self>(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::aggregate::component::event::ComponentDeprecatedEvent;
+    use crate::application::service::collection_membership::{
+        CollectionMembershipError, InvalidMembers,
+    };
+    use crate::application::shared::event::Event;
+    use crate::application::shared::event::error::EventBusError;
+    use crate::application::shared::event::listener::EventListener;
+    use crate::domain::collection::event::CollectionEvent;
+    use crate::domain::component::Component;
+    use crate::domain::component::event::ComponentEvent;
+    use crate::domain::component::id::ComponentId;
+    use crate::infrastructure::journal::in_memory_component::InMemoryComponentJournal;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct RecordingBus(Mutex<Vec<CollectionEvent>>);
+
+    impl EventBus for RecordingBus {
+        fn publish<E: Event + Clone + 'static>(&self, event: E) -> Result<(), EventBusError> {
+            if let Some(payload) = event.as_payload::<CollectionEvent>() {
+                self.0.lock().unwrap().push(payload.clone());
+            }
+            Ok(())
+        }
+    }
+
+    fn dummy_id(name: &str) -> ComponentId {
+        ComponentId::from_str(&format!("registry.test/namespace/{name}:v0")).unwrap()
+    }
+
+    fn collection_id() -> CollectionId {
+        CollectionId::new("collection").unwrap()
+    }
+
+    fn create(names: &[&str]) -> CollectionCommand {
+        CollectionCommand {
+            id: collection_id(),
+            kind: CollectionCommandKind::Create(names.iter().map(|name| dummy_id(name)).collect()),
+        }
+    }
+
+    /// Supervisor over a journal where `a` and `b` are registered.
+    fn supervisor() -> (
+        Addr<CollectionSupervisor<RecordingBus, InMemoryComponentJournal>>,
+        Arc<RecordingBus>,
+        Arc<InMemoryComponentJournal>,
+    ) {
+        let journal = Arc::new(InMemoryComponentJournal::default());
+        journal.append(Component::register(dummy_id("a")));
+        journal.append(Component::register(dummy_id("b")));
+        let bus = Arc::new(RecordingBus::default());
+        let supervisor = CollectionSupervisor::new(bus.clone(), journal.clone()).start();
+        (supervisor, bus, journal)
+    }
+
+    #[actix::test]
+    async fn collections_should_be_created_once() {
+        let (supervisor, bus, _) = supervisor();
+
+        supervisor.send(create(&["a"])).await.unwrap().unwrap();
+        let err = supervisor.send(create(&["b"])).await.unwrap().unwrap_err();
+
+        assert_eq!(err, CollectionCommandError::AlreadyExists(collection_id()));
+        assert_eq!(
+            *bus.0.lock().unwrap(),
+            vec![CollectionEvent::CollectionCreated {
+                collection_id: collection_id(),
+                initial_components: vec![dummy_id("a")],
+            }]
+        );
+    }
+
+    #[actix::test]
+    async fn components_deprecated_through_the_journal_should_not_become_members() {
+        let (supervisor, bus, journal) = supervisor();
+        journal
+            .on_event(&ComponentDeprecatedEvent::new(
+                ComponentEvent::ComponentDeprecated {
+                    component_id: dummy_id("b"),
+                },
+            ))
+            .await;
+
+        let err = supervisor
+            .send(create(&["a", "b", "z"]))
+            .await
+            .unwrap()
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            CollectionCommandError::Membership(CollectionMembershipError::InvalidMembers(
+                InvalidMembers {
+                    unknown: vec![dummy_id("z")],
+                    deprecated: vec![dummy_id("b")],
+                }
+            ))
+        );
+        assert!(bus.0.lock().unwrap().is_empty());
+    }
+
+    #[actix::test]
+    async fn replacements_should_be_forwarded_to_existing_collections() {
+        let (supervisor, bus, _) = supervisor();
+        let replace = CollectionCommand {
+            id: collection_id(),
+            kind: CollectionCommandKind::ReplaceComponents(HashSet::from([dummy_id("b")])),
+        };
+
+        let err = supervisor.send(replace.clone()).await.unwrap().unwrap_err();
+        assert_eq!(err, CollectionCommandError::NotFound(collection_id()));
+
+        supervisor.send(create(&["a"])).await.unwrap().unwrap();
+        supervisor.send(replace).await.unwrap().unwrap();
+        for _ in 0..50 {
+            if bus.0.lock().unwrap().len() == 3 {
+                break;
+            }
+            actix::clock::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(
+            bus.0.lock().unwrap()[1..],
+            [
+                CollectionEvent::ComponentDropped {
+                    collection_id: collection_id(),
+                    component_id: dummy_id("a"),
+                },
+                CollectionEvent::ComponentAdded {
+                    collection_id: collection_id(),
+                    component_id: dummy_id("b"),
+                },
+            ]
+        );
+    }
+}
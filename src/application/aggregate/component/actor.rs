@@ -1,28 +1,52 @@
+use std::sync::Arc;
+
 use crate::{
-    application::aggregate::component::cmd::{ComponentCommand, ComponentCommandKind},
+    application::{
+        aggregate::component::{
+            cmd::{ComponentCommand, ComponentCommandKind},
+            event::{
+                ComponentDeprecatedEvent, ExecutionContextReplacedEvent, SbomAssignedEvent,
+                SbomGenerationFailedEvent, SbomGenerationRequestedEvent,
+            },
+        },
+        shared::event::bus::EventBus,
+    },
     domain::{
-        component::{Component, ComponentError},
+        component::{Component, ComponentError, event::ComponentEvent},
         shared::aggregate::EventSourcedAggregate,
     },
 };
 use actix::{Actor, Context, Handler};
 
-pub struct ComponentActor {
+pub struct ComponentActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
     pub state: Component,
+    event_bus: Arc<EB>,
 }
 
-impl ComponentActor {
+impl<EB> ComponentActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
     #[must_use]
-    pub const fn new(state: Component) -> Self {
-        Self { state }
+    pub const fn new(state: Component, event_bus: Arc<EB>) -> Self {
+        Self { state, event_bus }
     }
 }
 
-impl Actor for ComponentActor {
+impl<EB> Actor for ComponentActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
     type Context = Context<Self>;
 }
 
-impl Handler<ComponentCommand> for ComponentActor {
+impl<EB> Handler<ComponentCommand> for ComponentActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
     type Result = Result<(), ComponentError>;
 
     fn handle(&mut self, cmd: ComponentCommand, _ctx: &mut Context<Self>) -> Self::Result {
@@ -37,12 +61,47 @@ impl Handler<ComponentCommand> for ComponentActor {
 
         let event = match kind {
             ComponentCommandKind::AssignSbom(sbom) => self.state.assign_sbom(sbom),
-            ComponentCommandKind::Register => Err(ComponentError::AlreadyRegistered(id)),
+            ComponentCommandKind::AssignExecutionContext(context) => {
+                self.state.assign_execution_context(context)
+            }
+            ComponentCommandKind::ReplaceExecutionContext(context) => {
+                self.state.replace_execution_context(context)
+            }
+            ComponentCommandKind::Deprecate => self.state.deprecate(),
+            ComponentCommandKind::FailSbomGeneration { reason, attempts } => {
+                self.state.fail_sbom_generation(reason, attempts)
+            }
+            ComponentCommandKind::RequestSbomGeneration => self.state.request_sbom_generation(),
+            ComponentCommandKind::Register => Err(ComponentError::AlreadyRegistered(Box::new(id))),
         }?;
 
         tracing::info!("Persist event");
-        let result = self.state.apply(&event);
+        self.state.apply(&event)?;
         tracing::info!("Emit event");
-        result
+        match event {
+            ComponentEvent::ExecutionContextReplaced { .. } => {
+                let _ = self
+                    .event_bus
+                    .publish(ExecutionContextReplacedEvent::new(event));
+            }
+            ComponentEvent::ComponentDeprecated { .. } => {
+                let _ = self.event_bus.publish(ComponentDeprecatedEvent::new(event));
+            }
+            ComponentEvent::SbomAssigned { .. } => {
+                let _ = self.event_bus.publish(SbomAssignedEvent::new(event));
+            }
+            ComponentEvent::SbomGenerationFailed { .. } => {
+                let _ = self
+                    .event_bus
+                    .publish(SbomGenerationFailedEvent::new(event));
+            }
+            ComponentEvent::SbomGenerationRequested { .. } => {
+                let _ = self
+                    .event_bus
+                    .publish(SbomGenerationRequestedEvent::new(event));
+            }
+            _ => {}
+        }
+        Ok(())
     }
 }
@@ -1,4 +1,6 @@
-use crate::domain::component::{id::ComponentId, sbom::Sbom, ComponentError};
+use crate::domain::component::{
+    ComponentError, context::ExecutionContext, id::ComponentId, sbom::Sbom,
+};
 use actix::Message;
 
 #[derive(Message, Debug, Clone)]
@@ -12,4 +14,9 @@ pub struct ComponentCommand {
 pub enum ComponentCommandKind {
     Register,
     AssignSbom(Sbom),
+    AssignExecutionContext(ExecutionContext),
+    ReplaceExecutionContext(ExecutionContext),
+    Deprecate,
+    FailSbomGeneration { reason: String, attempts: u32 },
+    RequestSbomGeneration,
 }
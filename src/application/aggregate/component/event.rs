@@ -40,3 +40,178 @@ impl Event for ComponentRegisteredEvent {
         &self.payload
     }
 }
+
+/// Published once the execution context of a component has been replaced.
+#[derive(Debug, Clone)]
+pub struct ExecutionContextReplacedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ComponentEvent,
+}
+
+impl ExecutionContextReplacedEvent {
+    #[must_use]
+    pub fn new(payload: ComponentEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for ExecutionContextReplacedEvent {
+    type Result = ();
+}
+
+impl Event for ExecutionContextReplacedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published once a component has been deprecated.
+#[derive(Debug, Clone)]
+pub struct ComponentDeprecatedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ComponentEvent,
+}
+
+impl ComponentDeprecatedEvent {
+    #[must_use]
+    pub fn new(payload: ComponentEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for ComponentDeprecatedEvent {
+    type Result = ();
+}
+
+impl Event for ComponentDeprecatedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published once an SBOM has been assigned to a component.
+#[derive(Debug, Clone)]
+pub struct SbomAssignedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ComponentEvent,
+}
+
+impl SbomAssignedEvent {
+    #[must_use]
+    pub fn new(payload: ComponentEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for SbomAssignedEvent {
+    type Result = ();
+}
+
+impl Event for SbomAssignedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published once every attempt to generate the SBOM of a component failed.
+#[derive(Debug, Clone)]
+pub struct SbomGenerationFailedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ComponentEvent,
+}
+
+impl SbomGenerationFailedEvent {
+    #[must_use]
+    pub fn new(payload: ComponentEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for SbomGenerationFailedEvent {
+    type Result = ();
+}
+
+impl Event for SbomGenerationFailedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published when the SBOM of a component must be generated again.
+#[derive(Debug, Clone)]
+pub struct SbomGenerationRequestedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ComponentEvent,
+}
+
+impl SbomGenerationRequestedEvent {
+    #[must_use]
+    pub fn new(payload: ComponentEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for SbomGenerationRequestedEvent {
+    type Result = ();
+}
+
+impl Event for SbomGenerationRequestedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
@@ -6,9 +6,9 @@ use actix::{Actor, Addr, Context, Handler};
 use crate::application::aggregate::component::actor::ComponentActor;
 use crate::application::aggregate::component::cmd::{ComponentCommand, ComponentCommandKind};
 use crate::application::aggregate::component::event::ComponentRegisteredEvent;
+use crate::application::shared::command::RegistersCommands;
 use crate::application::shared::command::bus::CommandBus;
 use crate::application::shared::command::handler::HandlesCommand;
-use crate::application::shared::command::RegistersCommands;
 use crate::application::shared::event::bus::EventBus;
 use crate::domain::component::id::ComponentId;
 use crate::domain::component::{Component, ComponentError};
@@ -19,7 +19,7 @@ pub struct ComponentSupervisor<EB>
 where
     EB: EventBus + Send + Sync + 'static,
 {
-    children: HashMap<ComponentId, Addr<ComponentActor>>,
+    children: HashMap<ComponentId, Addr<ComponentActor<EB>>>,
     event_bus: Arc<EB>,
 }
 
@@ -52,25 +52,22 @@ where
         let ComponentCommand { ref id, ref kind } = cmd;
 
         if let ComponentCommandKind::Register = kind {
-            tracing::info!("Check if new component id is in the journal");
-            tracing::info!("Throw an exception if is");
+            if self.children.contains_key(id) {
+                return Err(ComponentError::AlreadyRegistered(Box::new(id.clone())));
+            }
             let event = Component::register(id.clone());
             let component = Component::from_initial_event(&event)?;
             tracing::info!("Persist {component:?} in journal");
+            let actor = ComponentActor::new(component, self.event_bus.clone()).start();
+            self.children.insert(id.clone(), actor);
             let _ = self.event_bus.publish(ComponentRegisteredEvent::new(event));
             Ok(())
         } else {
-            if !self.children.contains_key(id) {
-                tracing::info!("Check if id exists in journal");
-                let component = Component::new(id.clone());
-                let actor = ComponentActor::new(component).start();
-                tracing::info!("Register actor addr {actor:?}")
-                // self.children.insert(id.clone(), actor);
-            }
-
-            if let Some(actor) = self.children.get(id) {
-                actor.do_send(cmd.clone());
-            }
+            let actor = self
+                .children
+                .get(id)
+                .ok_or_else(|| ComponentError::NotRegistered(Box::new(id.clone())))?;
+            actor.do_send(cmd.clone());
             Ok(())
         }
     }
@@ -95,3 +92,101 @@ where
         bus.register_handler::<ComponentCommand, Self>(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::shared::event::Event;
+    use crate::application::shared::event::error::EventBusError;
+    use crate::domain::component::event::ComponentEvent;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct RecordingBus(Mutex<Vec<ComponentEvent>>);
+
+    impl EventBus for RecordingBus {
+        fn publish<E: Event + Clone + 'static>(&self, event: E) -> Result<(), EventBusError> {
+            if let Some(payload) = event.as_payload::<ComponentEvent>() {
+                self.0.lock().unwrap().push(payload.clone());
+            }
+            Ok(())
+        }
+    }
+
+    fn dummy_id(name: &str) -> ComponentId {
+        ComponentId::from_str(&format!("registry.test/namespace/{name}:v0")).unwrap()
+    }
+
+    fn command(id: &ComponentId, kind: ComponentCommandKind) -> ComponentCommand {
+        ComponentCommand {
+            id: id.clone(),
+            kind,
+        }
+    }
+
+    #[actix::test]
+    async fn components_should_be_registered_once() {
+        let bus = Arc::new(RecordingBus::default());
+        let supervisor = ComponentSupervisor::new(bus.clone()).start();
+        let id = dummy_id("a");
+
+        supervisor
+            .send(command(&id, ComponentCommandKind::Register))
+            .await
+            .unwrap()
+            .unwrap();
+        let err = supervisor
+            .send(command(&id, ComponentCommandKind::Register))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, ComponentError::AlreadyRegistered(Box::new(id.clone())));
+
+        // The live aggregate kept its state: it can still be deprecated, once.
+        supervisor
+            .send(command(&id, ComponentCommandKind::Deprecate))
+            .await
+            .unwrap()
+            .unwrap();
+        for _ in 0..50 {
+            if bus.0.lock().unwrap().len() == 2 {
+                break;
+            }
+            actix::clock::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            *bus.0.lock().unwrap(),
+            vec![
+                ComponentEvent::ComponentRegistered {
+                    component_id: id.clone()
+                },
+                ComponentEvent::ComponentDeprecated { component_id: id },
+            ]
+        );
+    }
+
+    #[actix::test]
+    async fn commands_for_unregistered_components_should_be_rejected() {
+        let bus = Arc::new(RecordingBus::default());
+        let supervisor = ComponentSupervisor::new(bus.clone()).start();
+        let id = dummy_id("a");
+
+        let err = supervisor
+            .send(command(&id, ComponentCommandKind::RequestSbomGeneration))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, ComponentError::NotRegistered(Box::new(id.clone())));
+
+        // No aggregate was created on the way.
+        let err = supervisor
+            .send(command(&id, ComponentCommandKind::Deprecate))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err, ComponentError::NotRegistered(Box::new(id)));
+        assert!(bus.0.lock().unwrap().is_empty());
+    }
+}
@@ -1 +1,3 @@
+pub mod collection;
 pub mod component;
+pub mod vulnerability;
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use crate::{
+    application::{
+        aggregate::vulnerability::{
+            cmd::{ManagedVulnerabilityCommand, ManagedVulnerabilityCommandKind},
+            event::{
+                ClassificationInheritedEvent, ClassificationOutdatedEvent,
+                ManagedVulnerabilityClassifiedEvent,
+            },
+        },
+        shared::event::bus::EventBus,
+    },
+    domain::{
+        shared::aggregate::EventSourcedAggregate,
+        vulnerability::{
+            ManagedVulnerability, ManagedVulnerabilityError, event::ManagedVulnerabilityEvent,
+        },
+    },
+};
+use actix::{Actor, Context, Handler};
+
+pub struct ManagedVulnerabilityActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    pub state: ManagedVulnerability,
+    event_bus: Arc<EB>,
+}
+
+impl<EB> ManagedVulnerabilityActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    #[must_use]
+    pub const fn new(state: ManagedVulnerability, event_bus: Arc<EB>) -> Self {
+        Self { state, event_bus }
+    }
+}
+
+impl<EB> Actor for ManagedVulnerabilityActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    type Context = Context<Self>;
+}
+
+impl<EB> Handler<ManagedVulnerabilityCommand> for ManagedVulnerabilityActor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    type Result = Result<(), ManagedVulnerabilityError>;
+
+    fn handle(
+        &mut self,
+        cmd: ManagedVulnerabilityCommand,
+        _ctx: &mut Context<Self>,
+    ) -> Self::Result {
+        let ManagedVulnerabilityCommand { id, kind } = cmd;
+
+        if !self.state.id().eq(&id) {
+            return Err(ManagedVulnerabilityError::InconsistentIds(
+                id.to_string(),
+                self.state.id().to_string(),
+            ));
+        }
+
+        let event = match kind {
+            ManagedVulnerabilityCommandKind::Classify(severity, context) => {
+                self.state.classify(severity, Some(&context))
+            }
+            ManagedVulnerabilityCommandKind::MarkOutdated => self.state.mark_outdated(),
+            ManagedVulnerabilityCommandKind::Reclassify(severity, context) => {
+                self.state.reclassify(severity, Some(&context))
+            }
+            ManagedVulnerabilityCommandKind::Register => {
+                Err(ManagedVulnerabilityError::AlreadyRegistered(Box::new(id)))
+            }
+        }?;
+
+        tracing::info!("Persist event");
+        self.state.apply(&event)?;
+        tracing::info!("Emit event");
+        match event {
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassified { .. } => {
+                let _ = self
+                    .event_bus
+                    .publish(ManagedVulnerabilityClassifiedEvent::new(event));
+            }
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationInherited { .. } => {
+                let _ = self
+                    .event_bus
+                    .publish(ClassificationInheritedEvent::new(event));
+            }
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated { .. } => {
+                let _ = self
+                    .event_bus
+                    .publish(ClassificationOutdatedEvent::new(event));
+            }
+            // Registrations are published by the supervisor creating the aggregate.
+            ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered { .. } => {}
+        }
+        Ok(())
+    }
+}
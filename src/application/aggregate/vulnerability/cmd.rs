@@ -0,0 +1,20 @@
+use crate::domain::component::context::ExecutionContext;
+use crate::domain::vulnerability::ManagedVulnerabilityError;
+use crate::domain::vulnerability::classification::Severity;
+use crate::domain::vulnerability::id::ManagedVulnerabilityId;
+use actix::Message;
+
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "Result<(), ManagedVulnerabilityError>")]
+pub struct ManagedVulnerabilityCommand {
+    pub id: ManagedVulnerabilityId,
+    pub kind: ManagedVulnerabilityCommandKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ManagedVulnerabilityCommandKind {
+    Register,
+    Classify(Severity, ExecutionContext),
+    MarkOutdated,
+    Reclassify(Severity, ExecutionContext),
+}
@@ -0,0 +1,218 @@
+use crate::application::shared::event::Event;
+use crate::domain::vulnerability::event::{ManagedVulnerabilityEvent, VulnerabilityEvent};
+
+use actix::Message;
+use std::any::Any;
+use std::fmt::Debug;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Published once a vulnerability starts being tracked for a component.
+#[derive(Debug, Clone)]
+pub struct ManagedVulnerabilityRegisteredEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ManagedVulnerabilityEvent,
+}
+
+impl ManagedVulnerabilityRegisteredEvent {
+    #[must_use]
+    pub fn new(payload: ManagedVulnerabilityEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for ManagedVulnerabilityRegisteredEvent {
+    type Result = ();
+}
+
+impl Event for ManagedVulnerabilityRegisteredEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published once a managed vulnerability has been classified or reclassified.
+#[derive(Debug, Clone)]
+pub struct ManagedVulnerabilityClassifiedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ManagedVulnerabilityEvent,
+}
+
+impl ManagedVulnerabilityClassifiedEvent {
+    #[must_use]
+    pub fn new(payload: ManagedVulnerabilityEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for ManagedVulnerabilityClassifiedEvent {
+    type Result = ();
+}
+
+impl Event for ManagedVulnerabilityClassifiedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published once a managed vulnerability has adopted the classification of a similar component.
+#[derive(Debug, Clone)]
+pub struct ClassificationInheritedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ManagedVulnerabilityEvent,
+}
+
+impl ClassificationInheritedEvent {
+    #[must_use]
+    pub fn new(payload: ManagedVulnerabilityEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for ClassificationInheritedEvent {
+    type Result = ();
+}
+
+impl Event for ClassificationInheritedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published once the classification of a managed vulnerability has been flagged as outdated.
+#[derive(Debug, Clone)]
+pub struct ClassificationOutdatedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: ManagedVulnerabilityEvent,
+}
+
+impl ClassificationOutdatedEvent {
+    #[must_use]
+    pub fn new(payload: ManagedVulnerabilityEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for ClassificationOutdatedEvent {
+    type Result = ();
+}
+
+impl Event for ClassificationOutdatedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published when a vulnerability feed brings a vulnerability for the first time.
+#[derive(Debug, Clone)]
+pub struct VulnerabilityRegisteredEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: VulnerabilityEvent,
+}
+
+impl VulnerabilityRegisteredEvent {
+    #[must_use]
+    pub fn new(payload: VulnerabilityEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for VulnerabilityRegisteredEvent {
+    type Result = ();
+}
+
+impl Event for VulnerabilityRegisteredEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
+
+/// Published when a vulnerability feed brings new data for a known vulnerability.
+#[derive(Debug, Clone)]
+pub struct VulnerabilityUpdatedEvent {
+    id: Uuid,
+    date: SystemTime,
+    payload: VulnerabilityEvent,
+}
+
+impl VulnerabilityUpdatedEvent {
+    #[must_use]
+    pub fn new(payload: VulnerabilityEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            date: SystemTime::now(),
+            payload,
+        }
+    }
+}
+
+impl Message for VulnerabilityUpdatedEvent {
+    type Result = ();
+}
+
+impl Event for VulnerabilityUpdatedEvent {
+    fn event_id(&self) -> &Uuid {
+        &self.id
+    }
+    fn date(&self) -> &SystemTime {
+        &self.date
+    }
+    fn payload(&self) -> &dyn Any {
+        &self.payload
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix::{Actor, Addr, Context, Handler};
+
+use crate::application::aggregate::vulnerability::actor::ManagedVulnerabilityActor;
+use crate::application::aggregate::vulnerability::cmd::{
+    ManagedVulnerabilityCommand, ManagedVulnerabilityCommandKind,
+};
+use crate::application::aggregate::vulnerability::event::ManagedVulnerabilityRegisteredEvent;
+use crate::application::shared::command::RegistersCommands;
+use crate::application::shared::command::bus::CommandBus;
+use crate::application::shared::command::handler::HandlesCommand;
+use crate::application::shared::event::bus::EventBus;
+use crate::domain::shared::aggregate::EventSourcedAggregate;
+use crate::domain::vulnerability::id::ManagedVulnerabilityId;
+use crate::domain::vulnerability::{ManagedVulnerability, ManagedVulnerabilityError};
+
+pub struct ManagedVulnerabilitySupervisor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    children: HashMap<ManagedVulnerabilityId, Addr<ManagedVulnerabilityActor<EB>>>,
+    event_bus: Arc<EB>,
+}
+
+impl<EB> Actor for ManagedVulnerabilitySupervisor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    type Context = Context<Self>;
+}
+
+impl<EB> ManagedVulnerabilitySupervisor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    pub fn new(event_bus: Arc<EB>) -> Self {
+        Self {
+            children: HashMap::new(),
+            event_bus,
+        }
+    }
+
+    /// Id under which the vulnerability is already tracked on the component, if any.
+    ///
+    /// Ids only compare their primary identifier: a vulnerability first reported under a
+    /// GHSA, then under a CVE aliasing it, is found through the identifiers they share.
+    fn resolve(&self, id: &ManagedVulnerabilityId) -> Option<ManagedVulnerabilityId> {
+        self.children
+            .keys()
+            .find(|known| {
+                known.component_id() == id.component_id()
+                    && known
+                        .vulnerability_id()
+                        .same_vulnerability(id.vulnerability_id())
+            })
+            .cloned()
+    }
+}
+
+impl<EB> Handler<ManagedVulnerabilityCommand> for ManagedVulnerabilitySupervisor<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    type Result = Result<(), ManagedVulnerabilityError>;
+
+    fn handle(
+        &mut self,
+        cmd: ManagedVulnerabilityCommand,
+        _ctx: &mut Context<Self>,
+    ) -> Self::Result {
+        let ManagedVulnerabilityCommand { ref id, ref kind } = cmd;
+        let known = self.resolve(id);
+
+        if let ManagedVulnerabilityCommandKind::Register = kind {
+            if let Some(known) = known {
+                return Err(ManagedVulnerabilityError::AlreadyRegistered(Box::new(
+                    known,
+                )));
+            }
+            let event = ManagedVulnerability::register(id.clone());
+            let vulnerability = ManagedVulnerability::from_initial_event(&event)?;
+            tracing::info!("Persist {vulnerability:?} in journal");
+            let actor =
+                ManagedVulnerabilityActor::new(vulnerability, self.event_bus.clone()).start();
+            self.children.insert(id.clone(), actor);
+            let _ = self
+                .event_bus
+                .publish(ManagedVulnerabilityRegisteredEvent::new(event));
+            Ok(())
+        } else {
+            let known = known
+                .ok_or_else(|| ManagedVulnerabilityError::NotRegistered(Box::new(id.clone())))?;
+            let actor = &self.children[&known];
+            actor.do_send(ManagedVulnerabilityCommand {
+                id: known,
+                kind: kind.clone(),
+            });
+            Ok(())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<EB> HandlesCommand<ManagedVulnerabilityCommand> for Addr<ManagedVulnerabilitySupervisor<EB>>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    async fn handle(&self, cmd: ManagedVulnerabilityCommand) -> Result<(), String> {
+        self.do_send(cmd);
+        Ok(())
+    }
+}
+
+impl<EB> RegistersCommands for Addr<ManagedVulnerabilitySupervisor<EB>>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    fn register_with(self, bus: &mut CommandBus) {
+        bus.register_handler::<ManagedVulnerabilityCommand, Self>(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::shared::event::Event;
+    use crate::application::shared::event::error::EventBusError;
+    use crate::domain::component::context::{
+        DataSensitivity, ExecutionContext, Exposure, Isolation,
+    };
+    use crate::domain::component::id::ComponentId;
+    use crate::domain::vulnerability::classification::{Classification, Severity};
+    use crate::domain::vulnerability::event::ManagedVulnerabilityEvent;
+    use crate::domain::vulnerability::id::{AdvisoryId, VulnerabilityId};
+    use std::str::FromStr;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    const GHSA: &str = "GHSA-xvch-5gv4-984h";
+    const CVE: &str = "CVE-2021-44906";
+
+    #[derive(Default)]
+    struct RecordingBus(Mutex<Vec<ManagedVulnerabilityEvent>>);
+
+    impl EventBus for RecordingBus {
+        fn publish<E: Event + Clone + 'static>(&self, event: E) -> Result<(), EventBusError> {
+            if let Some(payload) = event.as_payload::<ManagedVulnerabilityEvent>() {
+                self.0.lock().unwrap().push(payload.clone());
+            }
+            Ok(())
+        }
+    }
+
+    fn managed_id(image: &str, vulnerability: VulnerabilityId) -> ManagedVulnerabilityId {
+        ManagedVulnerabilityId::new(ComponentId::from_str(image).unwrap(), vulnerability)
+    }
+
+    fn ghsa() -> VulnerabilityId {
+        VulnerabilityId::new(GHSA).unwrap()
+    }
+
+    fn cve_aliasing_ghsa() -> VulnerabilityId {
+        VulnerabilityId::new(CVE)
+            .unwrap()
+            .with_aliases([AdvisoryId::from_str(GHSA).unwrap()])
+    }
+
+    fn command(
+        id: &ManagedVulnerabilityId,
+        kind: ManagedVulnerabilityCommandKind,
+    ) -> ManagedVulnerabilityCommand {
+        ManagedVulnerabilityCommand {
+            id: id.clone(),
+            kind,
+        }
+    }
+
+    #[actix::test]
+    async fn aliases_of_a_tracked_vulnerability_should_resolve_to_it() {
+        let bus = Arc::new(RecordingBus::default());
+        let supervisor = ManagedVulnerabilitySupervisor::new(bus.clone()).start();
+        let reported_as_ghsa = managed_id("app/api:1.0", ghsa());
+        let reported_as_cve = managed_id("app/api:1.0", cve_aliasing_ghsa());
+        let other_component = managed_id("app/api:1.1", cve_aliasing_ghsa());
+        assert_ne!(reported_as_ghsa, reported_as_cve);
+
+        let register = ManagedVulnerabilityCommandKind::Register;
+        supervisor
+            .send(command(&reported_as_ghsa, register.clone()))
+            .await
+            .unwrap()
+            .unwrap();
+        let err = supervisor
+            .send(command(&reported_as_cve, register.clone()))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ManagedVulnerabilityError::AlreadyRegistered(Box::new(reported_as_ghsa.clone()))
+        );
+        supervisor
+            .send(command(&other_component, register))
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Commands addressed under the CVE reach the aggregate registered under the GHSA.
+        let context = ExecutionContext::new(
+            Exposure::Internal,
+            Isolation::Sandboxed,
+            DataSensitivity::Internal,
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+        )
+        .unwrap();
+        for kind in [
+            ManagedVulnerabilityCommandKind::Classify(Severity::High, context.clone()),
+            ManagedVulnerabilityCommandKind::MarkOutdated,
+        ] {
+            supervisor
+                .send(command(&reported_as_cve, kind))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        for _ in 0..50 {
+            if bus.0.lock().unwrap().len() == 4 {
+                break;
+            }
+            actix::clock::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(
+            *bus.0.lock().unwrap(),
+            vec![
+                ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered {
+                    id: reported_as_ghsa.clone()
+                },
+                ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered {
+                    id: other_component
+                },
+                ManagedVulnerabilityEvent::ManagedVulnerabilityClassified {
+                    id: reported_as_ghsa.clone(),
+                    classification: Classification::new(Severity::High, context),
+                },
+                ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated {
+                    id: reported_as_ghsa
+                },
+            ]
+        );
+    }
+}
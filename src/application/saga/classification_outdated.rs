@@ -0,0 +1,305 @@
+use actix::{Actor, Addr, Context, Handler};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use crate::application::aggregate::component::event::ExecutionContextReplacedEvent;
+use crate::application::aggregate::vulnerability::cmd::{
+    ManagedVulnerabilityCommand, ManagedVulnerabilityCommandKind,
+};
+use crate::application::aggregate::vulnerability::event::VulnerabilityUpdatedEvent;
+use crate::application::service::managed_vulnerability_index::ManagedVulnerabilityIndex;
+use crate::application::shared::command::CommandBus;
+use crate::application::shared::event::Event;
+use crate::application::shared::event::listener::EventListener;
+use crate::domain::component::event::ComponentEvent;
+use crate::domain::vulnerability::event::VulnerabilityEvent;
+use crate::domain::vulnerability::id::ManagedVulnerabilityId;
+
+/// Saga actor flagging classifications as outdated when their inputs change.
+///
+/// A classification is computed from the execution context of a component and from the
+/// CVSS vectors of a vulnerability: replacing the former or updating the latter outdates
+/// every managed vulnerability built on them.
+pub struct ClassificationOutdatedSaga {
+    pub command_bus: Arc<Mutex<CommandBus>>,
+    pub index: Arc<dyn ManagedVulnerabilityIndex>,
+}
+
+impl ClassificationOutdatedSaga {
+    pub fn new(
+        command_bus: Arc<Mutex<CommandBus>>,
+        index: Arc<dyn ManagedVulnerabilityIndex>,
+    ) -> Self {
+        Self { command_bus, index }
+    }
+
+    fn mark_outdated(&self, ids: Vec<ManagedVulnerabilityId>) {
+        let cmd_bus = self.command_bus.lock().unwrap();
+        for id in ids {
+            tracing::info!("Marking classification of {id} as outdated");
+            let _ = cmd_bus.dispatch(Box::new(ManagedVulnerabilityCommand {
+                id,
+                kind: ManagedVulnerabilityCommandKind::MarkOutdated,
+            }));
+        }
+    }
+}
+
+impl Actor for ClassificationOutdatedSaga {
+    type Context = Context<Self>;
+}
+
+impl Handler<ExecutionContextReplacedEvent> for ClassificationOutdatedSaga {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        event: ExecutionContextReplacedEvent,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        tracing::info!("Handling event {event:?}");
+
+        if let Some(ComponentEvent::ExecutionContextReplaced { component_id, .. }) =
+            event.as_payload()
+        {
+            self.mark_outdated(self.index.by_component(component_id));
+        }
+    }
+}
+
+impl Handler<VulnerabilityUpdatedEvent> for ClassificationOutdatedSaga {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        event: VulnerabilityUpdatedEvent,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        tracing::info!("Handling event {event:?}");
+
+        if let Some(payload) = event.as_payload::<VulnerabilityEvent>()
+            && payload.cvss_changed()
+        {
+            self.mark_outdated(self.index.by_vulnerability(payload.vulnerability_id()));
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<ExecutionContextReplacedEvent> for Addr<ClassificationOutdatedSaga> {
+    async fn on_event(&self, event: &ExecutionContextReplacedEvent) {
+        self.do_send(event.clone());
+    }
+}
+
+#[async_trait]
+impl EventListener<VulnerabilityUpdatedEvent> for Addr<ClassificationOutdatedSaga> {
+    async fn on_event(&self, event: &VulnerabilityUpdatedEvent) {
+        self.do_send(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::aggregate::vulnerability::actor::ManagedVulnerabilityActor;
+    use crate::application::shared::command::RegistersCommands;
+    use crate::application::shared::command::handler::HandlesCommand;
+    use crate::application::shared::event::bus::EventBus;
+    use crate::application::shared::event::error::EventBusError;
+    use crate::domain::component::context::{
+        DataSensitivity, ExecutionContext, Exposure, Isolation,
+    };
+    use crate::domain::component::id::ComponentId;
+    use crate::domain::shared::aggregate::EventSourcedAggregate;
+    use crate::domain::vulnerability::ManagedVulnerability;
+    use crate::domain::vulnerability::classification::Severity;
+    use crate::domain::vulnerability::cvss::Cvss;
+    use crate::domain::vulnerability::event::ManagedVulnerabilityEvent;
+    use crate::domain::vulnerability::id::VulnerabilityId;
+    use crate::infrastructure::projection::in_memory_managed_vulnerability::InMemoryManagedVulnerabilityIndex;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    const CVE: &str = "CVE-2024-1234";
+
+    #[derive(Default)]
+    struct RecordingBus(Mutex<Vec<ManagedVulnerabilityEvent>>);
+
+    impl EventBus for RecordingBus {
+        fn publish<E: Event + Clone + 'static>(&self, event: E) -> Result<(), EventBusError> {
+            if let Some(payload) = event.as_payload::<ManagedVulnerabilityEvent>() {
+                self.0.lock().unwrap().push(payload.clone());
+            }
+            Ok(())
+        }
+    }
+
+    /// Handles commands with the actor of a single managed vulnerability.
+    #[derive(Clone)]
+    struct Vulnerability(Addr<ManagedVulnerabilityActor<RecordingBus>>);
+
+    #[async_trait]
+    impl HandlesCommand<ManagedVulnerabilityCommand> for Vulnerability {
+        async fn handle(&self, cmd: ManagedVulnerabilityCommand) -> Result<(), String> {
+            self.0
+                .send(cmd)
+                .await
+                .map_err(|e| e.to_string())?
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    impl RegistersCommands for Vulnerability {
+        fn register_with(self, bus: &mut CommandBus) {
+            bus.register_handler::<ManagedVulnerabilityCommand, Self>(self);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum State {
+        Classified,
+        Inherited,
+    }
+
+    fn context(exposure: Exposure) -> ExecutionContext {
+        ExecutionContext::new(
+            exposure,
+            Isolation::Sandboxed,
+            DataSensitivity::Internal,
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+        )
+        .unwrap()
+    }
+
+    fn managed_id(image: &str) -> ManagedVulnerabilityId {
+        ManagedVulnerabilityId::new(
+            ComponentId::from_str(image).unwrap(),
+            VulnerabilityId::new(CVE).unwrap(),
+        )
+    }
+
+    fn managed_vulnerability(id: ManagedVulnerabilityId, state: State) -> ManagedVulnerability {
+        let mut vuln =
+            ManagedVulnerability::from_initial_event(&ManagedVulnerability::register(id)).unwrap();
+        let context = context(Exposure::Internal);
+        let event = match state {
+            State::Classified => vuln.classify(Severity::High, Some(&context)),
+            State::Inherited => {
+                let mut source = ManagedVulnerability::from_initial_event(
+                    &ManagedVulnerability::register(managed_id("app/api:0.9")),
+                )
+                .unwrap();
+                let classified = source.classify(Severity::High, Some(&context)).unwrap();
+                source.apply(&classified).unwrap();
+                vuln.inherit_classification(&source, Some(&context))
+            }
+        }
+        .unwrap();
+        vuln.apply(&event).unwrap();
+        vuln
+    }
+
+    /// Saga over a single managed vulnerability in the given state, and the bus its
+    /// aggregate publishes to.
+    fn saga(
+        id: &ManagedVulnerabilityId,
+        state: State,
+    ) -> (ClassificationOutdatedSaga, Arc<RecordingBus>) {
+        let bus = Arc::new(RecordingBus::default());
+        let actor =
+            ManagedVulnerabilityActor::new(managed_vulnerability(id.clone(), state), bus.clone())
+                .start();
+        let mut command_bus = CommandBus::default();
+        command_bus.register(Vulnerability(actor));
+
+        let index = Arc::new(InMemoryManagedVulnerabilityIndex::default());
+        index.insert(id.clone());
+        (
+            ClassificationOutdatedSaga::new(Arc::new(Mutex::new(command_bus)), index),
+            bus,
+        )
+    }
+
+    async fn published(bus: &RecordingBus) -> Vec<ManagedVulnerabilityEvent> {
+        for _ in 0..50 {
+            if !bus.0.lock().unwrap().is_empty() {
+                break;
+            }
+            actix::clock::sleep(Duration::from_millis(10)).await;
+        }
+        bus.0.lock().unwrap().clone()
+    }
+
+    #[actix::test]
+    async fn replacing_the_context_should_outdate_classifications() {
+        for state in [State::Classified, State::Inherited] {
+            let id = managed_id("app/api:1.0");
+            let (saga, bus) = saga(&id, state);
+            let saga = saga.start();
+
+            saga.send(ExecutionContextReplacedEvent::new(
+                ComponentEvent::ExecutionContextReplaced {
+                    component_id: id.component_id().clone(),
+                    context: context(Exposure::InternetFacing),
+                },
+            ))
+            .await
+            .unwrap();
+
+            assert_eq!(
+                published(&bus).await,
+                vec![ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated { id }],
+                "{state:?}"
+            );
+        }
+    }
+
+    #[actix::test]
+    async fn changing_the_cvss_should_outdate_classifications() {
+        for state in [State::Classified, State::Inherited] {
+            let id = managed_id("app/api:1.0");
+            let (saga, bus) = saga(&id, state);
+            let saga = saga.start();
+
+            saga.send(VulnerabilityUpdatedEvent::new(
+                VulnerabilityEvent::VulnerabilityUpdated {
+                    vulnerability_id: id.vulnerability_id().clone(),
+                    previous_cvss: vec![],
+                    cvss: vec![
+                        Cvss::from_str("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").unwrap(),
+                    ],
+                },
+            ))
+            .await
+            .unwrap();
+
+            assert_eq!(
+                published(&bus).await,
+                vec![ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated { id }],
+                "{state:?}"
+            );
+        }
+    }
+
+    #[actix::test]
+    async fn unchanged_cvss_should_keep_classifications() {
+        let id = managed_id("app/api:1.0");
+        let (saga, bus) = saga(&id, State::Classified);
+        let saga = saga.start();
+
+        saga.send(VulnerabilityUpdatedEvent::new(
+            VulnerabilityEvent::VulnerabilityUpdated {
+                vulnerability_id: id.vulnerability_id().clone(),
+                previous_cvss: vec![],
+                cvss: vec![],
+            },
+        ))
+        .await
+        .unwrap();
+        actix::clock::sleep(Duration::from_millis(50)).await;
+
+        assert!(bus.0.lock().unwrap().is_empty());
+    }
+}
@@ -1 +1,2 @@
+pub mod classification_outdated;
 pub mod sbom_generation;
@@ -1,27 +1,140 @@
-use actix::{Actor, Addr, Context, Handler};
+use actix::{Actor, ActorFutureExt, Addr, AsyncContext, Context, Handler, SpawnHandle, WrapFuture};
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
 
 use crate::application::aggregate::component::cmd::{ComponentCommand, ComponentCommandKind};
-use crate::application::aggregate::component::event::ComponentRegisteredEvent;
-use crate::application::service::sbom_generator::SbomGenerator;
+use crate::application::aggregate::component::event::{
+    ComponentDeprecatedEvent, ComponentRegisteredEvent, SbomGenerationRequestedEvent,
+};
+use crate::application::service::sbom_generator::{SbomGenerator, SbomGeneratorError};
 use crate::application::shared::command::CommandBus;
-use crate::application::shared::event::listener::EventListener;
 use crate::application::shared::event::Event;
+use crate::application::shared::event::listener::EventListener;
+use crate::application::shared::retry::RetryPolicy;
 use crate::domain::component::event::ComponentEvent;
+use crate::domain::component::id::ComponentId;
 
 /// Saga actor responsible for reacting to component registration events
+///
+/// Generations run as futures of the actor, so that a slow tool never holds up its mailbox.
+/// At most `max_concurrent` of them run at once, each attempt is abandoned after `timeout`,
+/// and the generation of a component is cancelled when it is deprecated.
+///
+/// Transient failures are retried following the retry policy. Once it gives up, the saga
+/// records `SbomGenerationFailed` on the component; generation starts over when an operator
+/// requests it again.
 pub struct SbomGenerationSaga {
     pub command_bus: Arc<Mutex<CommandBus>>,
-    pub generator: Box<dyn SbomGenerator>,
+    pub generator: Arc<dyn SbomGenerator>,
+    timeout: Duration,
+    retry: RetryPolicy,
+    permits: Arc<Semaphore>,
+    running: HashMap<ComponentId, SpawnHandle>,
 }
 
 impl SbomGenerationSaga {
-    pub fn new(command_bus: Arc<Mutex<CommandBus>>, generator: Box<dyn SbomGenerator>) -> Self {
+    pub fn new(
+        command_bus: Arc<Mutex<CommandBus>>,
+        generator: Arc<dyn SbomGenerator>,
+        timeout: Duration,
+        max_concurrent: NonZeroUsize,
+        retry: RetryPolicy,
+    ) -> Self {
         Self {
             command_bus,
             generator,
+            timeout,
+            retry,
+            permits: Arc::new(Semaphore::new(max_concurrent.get())),
+            running: HashMap::new(),
+        }
+    }
+
+    fn start(&mut self, component_id: &ComponentId, ctx: &mut Context<Self>) {
+        if self.running.contains_key(component_id) {
+            tracing::info!("SBOM generation for {component_id} is already running");
+            return;
         }
+
+        let generation = Self::generate(
+            self.command_bus.clone(),
+            self.generator.clone(),
+            self.permits.clone(),
+            self.timeout,
+            self.retry,
+            component_id.clone(),
+        );
+        let id = component_id.clone();
+        let handle = ctx.spawn(generation.into_actor(self).map(move |(), act, _| {
+            act.running.remove(&id);
+        }));
+        self.running.insert(component_id.clone(), handle);
+    }
+
+    async fn generate(
+        command_bus: Arc<Mutex<CommandBus>>,
+        generator: Arc<dyn SbomGenerator>,
+        permits: Arc<Semaphore>,
+        timeout: Duration,
+        retry: RetryPolicy,
+        component_id: ComponentId,
+    ) {
+        let mut attempt = 0;
+        let result = loop {
+            attempt += 1;
+            let result = {
+                // The semaphore is never closed. The permit is released while backing off.
+                let Ok(_permit) = permits.acquire().await else {
+                    return;
+                };
+                tokio::time::timeout(timeout, generator.generate(&component_id))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(SbomGeneratorError::TimedOut(
+                            component_id.to_string(),
+                            timeout,
+                        ))
+                    })
+            };
+
+            match result {
+                Err(err) if err.is_transient() => match retry.backoff(attempt) {
+                    Some(delay) => {
+                        tracing::warn!(
+                            "SBOM generation attempt {attempt} failed for component {component_id}, retrying in {delay:?}: {err}"
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    None => break Err(err),
+                },
+                result => break result,
+            }
+        };
+
+        let kind = match result {
+            Ok(sbom) => {
+                tracing::info!("SBOM generated successfully");
+                ComponentCommandKind::AssignSbom(sbom)
+            }
+            Err(err) => {
+                tracing::error!(
+                    "SBOM generation failed for component {component_id} after {attempt} attempt(s): {err}"
+                );
+                ComponentCommandKind::FailSbomGeneration {
+                    reason: err.to_string(),
+                    attempts: attempt,
+                }
+            }
+        };
+        let cmd_bus = command_bus.lock().unwrap();
+        let _ = cmd_bus.dispatch(Box::new(ComponentCommand {
+            id: component_id,
+            kind,
+        }));
     }
 }
 
@@ -32,27 +145,42 @@ impl Actor for SbomGenerationSaga {
 impl Handler<ComponentRegisteredEvent> for SbomGenerationSaga {
     type Result = ();
 
+    fn handle(&mut self, event: ComponentRegisteredEvent, ctx: &mut Self::Context) -> Self::Result {
+        tracing::info!("Handling event {event:?}");
+
+        if let ComponentEvent::ComponentRegistered { component_id } = event.as_payload().unwrap() {
+            self.start(component_id, ctx);
+        }
+    }
+}
+
+impl Handler<SbomGenerationRequestedEvent> for SbomGenerationSaga {
+    type Result = ();
+
     fn handle(
         &mut self,
-        event: ComponentRegisteredEvent,
-        _ctx: &mut Self::Context,
+        event: SbomGenerationRequestedEvent,
+        ctx: &mut Self::Context,
     ) -> Self::Result {
         tracing::info!("Handling event {event:?}");
 
-        if let ComponentEvent::ComponentRegistered { component_id } = event.as_payload().unwrap() {
-            match self.generator.generate(component_id) {
-                Ok(sbom) => {
-                    tracing::info!("SBOM generated successfully");
-                    let cmd_bus = self.command_bus.lock().unwrap();
-                    let _ = cmd_bus.dispatch(Box::new(ComponentCommand {
-                        id: component_id.clone(),
-                        kind: ComponentCommandKind::AssignSbom(sbom),
-                    }));
-                }
-                Err(err) => {
-                    tracing::error!("SBOM generation failed for component {component_id}: {err}");
-                }
-            }
+        if let ComponentEvent::SbomGenerationRequested { component_id } =
+            event.as_payload().unwrap()
+        {
+            self.start(component_id, ctx);
+        }
+    }
+}
+
+impl Handler<ComponentDeprecatedEvent> for SbomGenerationSaga {
+    type Result = ();
+
+    fn handle(&mut self, event: ComponentDeprecatedEvent, ctx: &mut Self::Context) -> Self::Result {
+        if let ComponentEvent::ComponentDeprecated { component_id } = event.as_payload().unwrap()
+            && let Some(handle) = self.running.remove(component_id)
+        {
+            tracing::info!("Cancelling SBOM generation for deprecated component {component_id}");
+            ctx.cancel_future(handle);
         }
     }
 }
@@ -63,3 +191,335 @@ impl EventListener<ComponentRegisteredEvent> for Addr<SbomGenerationSaga> {
         self.do_send(event.clone());
     }
 }
+
+#[async_trait]
+impl EventListener<ComponentDeprecatedEvent> for Addr<SbomGenerationSaga> {
+    async fn on_event(&self, event: &ComponentDeprecatedEvent) {
+        self.do_send(event.clone());
+    }
+}
+
+#[async_trait]
+impl EventListener<SbomGenerationRequestedEvent> for Addr<SbomGenerationSaga> {
+    async fn on_event(&self, event: &SbomGenerationRequestedEvent) {
+        self.do_send(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::shared::command::RegistersCommands;
+    use crate::application::shared::command::handler::HandlesCommand;
+    use crate::domain::component::sbom::Sbom;
+    use std::collections::VecDeque;
+    use std::str::FromStr;
+
+    /// Generator returning scripted results, one per attempt, then succeeding.
+    struct Scripted {
+        results: Mutex<VecDeque<Result<Sbom, SbomGeneratorError>>>,
+        attempts: Mutex<u32>,
+    }
+
+    impl Scripted {
+        fn new(results: impl IntoIterator<Item = Result<Sbom, SbomGeneratorError>>) -> Self {
+            Self {
+                results: Mutex::new(results.into_iter().collect()),
+                attempts: Mutex::new(0),
+            }
+        }
+
+        fn attempts(&self) -> u32 {
+            *self.attempts.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl SbomGenerator for Scripted {
+        async fn generate(&self, _id: &ComponentId) -> Result<Sbom, SbomGeneratorError> {
+            *self.attempts.lock().unwrap() += 1;
+            self.results
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Ok(sbom()))
+        }
+    }
+
+    /// Generator succeeding after `delay`, tracking how many generations run at once.
+    #[derive(Default)]
+    struct Slow {
+        delay: Duration,
+        running: Mutex<usize>,
+        peak: Mutex<usize>,
+        completed: Mutex<usize>,
+    }
+
+    /// Counts a generation as running until dropped, whether it completed or was cancelled.
+    struct Running<'a>(&'a Slow);
+
+    impl Drop for Running<'_> {
+        fn drop(&mut self) {
+            *self.0.running.lock().unwrap() -= 1;
+        }
+    }
+
+    impl Slow {
+        fn new(delay: Duration) -> Self {
+            Self {
+                delay,
+                ..Self::default()
+            }
+        }
+
+        fn running(&self) -> usize {
+            *self.running.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl SbomGenerator for Slow {
+        async fn generate(&self, _id: &ComponentId) -> Result<Sbom, SbomGeneratorError> {
+            let _running = {
+                let mut running = self.running.lock().unwrap();
+                *running += 1;
+                let mut peak = self.peak.lock().unwrap();
+                *peak = (*peak).max(*running);
+                Running(self)
+            };
+            tokio::time::sleep(self.delay).await;
+            *self.completed.lock().unwrap() += 1;
+            Ok(sbom())
+        }
+    }
+
+    /// Records the component commands dispatched by the saga.
+    #[derive(Clone, Default)]
+    struct Commands(Arc<Mutex<Vec<ComponentCommand>>>);
+
+    #[async_trait]
+    impl HandlesCommand<ComponentCommand> for Commands {
+        async fn handle(&self, cmd: ComponentCommand) -> Result<(), String> {
+            self.0.lock().unwrap().push(cmd);
+            Ok(())
+        }
+    }
+
+    impl RegistersCommands for Commands {
+        fn register_with(self, bus: &mut CommandBus) {
+            bus.register_handler::<ComponentCommand, Self>(self);
+        }
+    }
+
+    impl Commands {
+        fn command_bus(&self) -> Arc<Mutex<CommandBus>> {
+            let mut bus = CommandBus::default();
+            bus.register(self.clone());
+            Arc::new(Mutex::new(bus))
+        }
+
+        /// Kinds of the commands dispatched so far, once `count` of them have been.
+        async fn wait_for(&self, count: usize) -> Vec<ComponentCommandKind> {
+            for _ in 0..100 {
+                if self.0.lock().unwrap().len() >= count {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|cmd| cmd.kind.clone())
+                .collect()
+        }
+    }
+
+    fn sbom() -> Sbom {
+        Sbom::from_url_str("https://example.com/sbom.json")
+            .unwrap()
+            .sealed(b"{}")
+    }
+
+    fn component(name: &str) -> ComponentId {
+        ComponentId::from_str(&format!("registry.test/namespace/{name}:v0")).unwrap()
+    }
+
+    fn failed(name: &str) -> SbomGeneratorError {
+        SbomGeneratorError::GenerationFailed(component(name).to_string(), "pull failed".into())
+    }
+
+    fn retry(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(
+            max_attempts,
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+        )
+    }
+
+    async fn run(generator: &Arc<Scripted>, retry: RetryPolicy) -> Vec<ComponentCommandKind> {
+        let commands = Commands::default();
+        SbomGenerationSaga::generate(
+            commands.command_bus(),
+            generator.clone(),
+            Arc::new(Semaphore::new(1)),
+            Duration::from_secs(5),
+            retry,
+            component("a"),
+        )
+        .await;
+        commands.wait_for(1).await
+    }
+
+    #[actix::test]
+    async fn transient_failures_should_be_retried_until_success() {
+        let generator = Arc::new(Scripted::new([Err(failed("a")), Err(failed("a"))]));
+
+        let kinds = run(&generator, retry(3)).await;
+
+        assert_eq!(generator.attempts(), 3);
+        assert!(
+            matches!(&kinds[..], [ComponentCommandKind::AssignSbom(s)] if *s == sbom()),
+            "{kinds:?}"
+        );
+    }
+
+    #[actix::test]
+    async fn exhausted_retries_should_fail_the_generation() {
+        let generator = Arc::new(Scripted::new((0..3).map(|_| Err(failed("a")))));
+
+        let kinds = run(&generator, retry(3)).await;
+
+        assert_eq!(generator.attempts(), 3);
+        assert!(
+            matches!(
+                &kinds[..],
+                [ComponentCommandKind::FailSbomGeneration { reason, attempts: 3 }]
+                    if *reason == failed("a").to_string()
+            ),
+            "{kinds:?}"
+        );
+    }
+
+    #[actix::test]
+    async fn permanent_failures_should_not_be_retried() {
+        for err in [
+            SbomGeneratorError::NotFound(component("a").to_string(), "no SBOM".into()),
+            SbomGeneratorError::ToolUnavailable("trivy".into(), "not found".into()),
+        ] {
+            let reason = err.to_string();
+            let generator = Arc::new(Scripted::new([Err(err)]));
+
+            let kinds = run(&generator, retry(3)).await;
+
+            assert_eq!(generator.attempts(), 1);
+            assert!(
+                matches!(
+                    &kinds[..],
+                    [ComponentCommandKind::FailSbomGeneration { reason: r, attempts: 1 }] if *r == reason
+                ),
+                "{kinds:?}"
+            );
+        }
+    }
+
+    #[actix::test]
+    async fn slow_generations_should_time_out() {
+        let commands = Commands::default();
+        let timeout = Duration::from_millis(10);
+        SbomGenerationSaga::generate(
+            commands.command_bus(),
+            Arc::new(Slow::new(Duration::from_secs(5))),
+            Arc::new(Semaphore::new(1)),
+            timeout,
+            retry(2),
+            component("a"),
+        )
+        .await;
+
+        let kinds = commands.wait_for(1).await;
+        let reason = SbomGeneratorError::TimedOut(component("a").to_string(), timeout).to_string();
+        assert!(
+            matches!(
+                &kinds[..],
+                [ComponentCommandKind::FailSbomGeneration { reason: r, attempts: 2 }] if *r == reason
+            ),
+            "{kinds:?}"
+        );
+    }
+
+    #[actix::test]
+    async fn generations_should_not_exceed_the_concurrency_limit() {
+        let commands = Commands::default();
+        let generator = Arc::new(Slow::new(Duration::from_millis(30)));
+        let saga = SbomGenerationSaga::new(
+            commands.command_bus(),
+            generator.clone(),
+            Duration::from_secs(5),
+            NonZeroUsize::new(2).unwrap(),
+            retry(1),
+        )
+        .start();
+
+        for name in ["a", "b", "c", "d", "e"] {
+            saga.do_send(ComponentRegisteredEvent::new(
+                ComponentEvent::ComponentRegistered {
+                    component_id: component(name),
+                },
+            ));
+        }
+
+        let kinds = commands.wait_for(5).await;
+        assert_eq!(kinds.len(), 5);
+        assert!(
+            kinds
+                .iter()
+                .all(|kind| matches!(kind, ComponentCommandKind::AssignSbom(_)))
+        );
+        assert_eq!(*generator.peak.lock().unwrap(), 2);
+    }
+
+    #[actix::test]
+    async fn deprecation_should_cancel_the_generation() {
+        let commands = Commands::default();
+        let generator = Arc::new(Slow::new(Duration::from_secs(5)));
+        let saga = SbomGenerationSaga::new(
+            commands.command_bus(),
+            generator.clone(),
+            Duration::from_secs(10),
+            NonZeroUsize::MIN,
+            retry(1),
+        )
+        .start();
+
+        saga.do_send(ComponentRegisteredEvent::new(
+            ComponentEvent::ComponentRegistered {
+                component_id: component("a"),
+            },
+        ));
+        for _ in 0..50 {
+            if generator.running() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(generator.running(), 1);
+
+        saga.do_send(ComponentDeprecatedEvent::new(
+            ComponentEvent::ComponentDeprecated {
+                component_id: component("a"),
+            },
+        ));
+        for _ in 0..50 {
+            if generator.running() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // The generation future was dropped without completing nor dispatching anything.
+        assert_eq!(generator.running(), 0);
+        assert_eq!(*generator.completed.lock().unwrap(), 0);
+        assert!(commands.0.lock().unwrap().is_empty());
+    }
+}
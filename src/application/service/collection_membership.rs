@@ -0,0 +1,208 @@
+use crate::domain::component::Component;
+use crate::domain::component::event::ComponentEvent;
+use crate::domain::component::id::ComponentId;
+use crate::domain::shared::aggregate::EventSourcedAggregate;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Read access to the event stream of each component.
+pub trait ComponentEventStream: Send + Sync {
+    /// Events of the given component in emission order, empty if it was never registered.
+    fn events(&self, id: &ComponentId) -> Vec<ComponentEvent>;
+}
+
+/// Checks that the members of a collection exist and are not deprecated.
+///
+/// Collections only hold component ids, so this invariant spans aggregates: each component
+/// is rehydrated from its own event stream before `CollectionCreated` or `ComponentAdded`
+/// is emitted.
+pub struct CollectionMembershipValidator<S>
+where
+    S: ComponentEventStream + ?Sized,
+{
+    streams: Arc<S>,
+}
+
+impl<S> CollectionMembershipValidator<S>
+where
+    S: ComponentEventStream + ?Sized,
+{
+    pub const fn new(streams: Arc<S>) -> Self {
+        Self { streams }
+    }
+
+    /// Validate a proposed set of collection members.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollectionMembershipError::InvalidMembers`] listing every unknown and every
+    /// deprecated component, or [`CollectionMembershipError::InconsistentStream`] if the
+    /// events of a component cannot be replayed.
+    pub fn validate(
+        &self,
+        components: &HashSet<ComponentId>,
+    ) -> Result<(), CollectionMembershipError> {
+        let mut unknown = Vec::new();
+        let mut deprecated = Vec::new();
+
+        for id in components {
+            let events = self.streams.events(id);
+            if events.is_empty() {
+                unknown.push(id.clone());
+                continue;
+            }
+
+            let component = Component::rehydrate(&events).map_err(|e| {
+                CollectionMembershipError::InconsistentStream(Box::new(id.clone()), e.to_string())
+            })?;
+            if component.is_deprecated() {
+                deprecated.push(id.clone());
+            }
+        }
+
+        if unknown.is_empty() && deprecated.is_empty() {
+            return Ok(());
+        }
+
+        // Sets have no order: sort so that errors are stable and readable.
+        unknown.sort_by_cached_key(ToString::to_string);
+        deprecated.sort_by_cached_key(ToString::to_string);
+        Err(CollectionMembershipError::InvalidMembers(InvalidMembers {
+            unknown,
+            deprecated,
+        }))
+    }
+}
+
+/// Components rejected as collection members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMembers {
+    pub unknown: Vec<ComponentId>,
+    pub deprecated: Vec<ComponentId>,
+}
+
+impl fmt::Display for InvalidMembers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |ids: &[ComponentId]| {
+            ids.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(
+            f,
+            "unknown [{}], deprecated [{}]",
+            join(&self.unknown),
+            join(&self.deprecated)
+        )
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CollectionMembershipError {
+    #[error("Invalid collection members: {0}")]
+    InvalidMembers(InvalidMembers),
+
+    #[error("Events of component `{0}` cannot be replayed: {1}")]
+    InconsistentStream(Box<ComponentId>, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::ComponentError;
+    use crate::domain::component::sbom::Sbom;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    struct Streams(HashMap<ComponentId, Vec<ComponentEvent>>);
+
+    impl ComponentEventStream for Streams {
+        fn events(&self, id: &ComponentId) -> Vec<ComponentEvent> {
+            self.0.get(id).cloned().unwrap_or_default()
+        }
+    }
+
+    fn dummy_id(name: &str) -> ComponentId {
+        ComponentId::from_str(&format!("registry.test/namespace/{name}:v0")).unwrap()
+    }
+
+    fn registered(id: &ComponentId) -> Vec<ComponentEvent> {
+        vec![Component::register(id.clone())]
+    }
+
+    fn deprecated(id: &ComponentId) -> Vec<ComponentEvent> {
+        let mut events = registered(id);
+        events.push(ComponentEvent::ComponentDeprecated {
+            component_id: id.clone(),
+        });
+        events
+    }
+
+    fn validator(
+        streams: Vec<(ComponentId, Vec<ComponentEvent>)>,
+    ) -> CollectionMembershipValidator<Streams> {
+        CollectionMembershipValidator::new(Arc::new(Streams(streams.into_iter().collect())))
+    }
+
+    #[test]
+    fn existing_active_components_should_be_accepted() {
+        let (a, b) = (dummy_id("a"), dummy_id("b"));
+        let validator = validator(vec![
+            (a.clone(), registered(&a)),
+            (b.clone(), registered(&b)),
+        ]);
+
+        assert!(validator.validate(&HashSet::from([a, b])).is_ok());
+    }
+
+    #[test]
+    fn unknown_and_deprecated_components_should_all_be_listed() {
+        let (a, b, c, d) = (dummy_id("a"), dummy_id("b"), dummy_id("c"), dummy_id("d"));
+        let validator = validator(vec![
+            (a.clone(), registered(&a)),
+            (b.clone(), deprecated(&b)),
+            (d.clone(), deprecated(&d)),
+        ]);
+        let unregistered = dummy_id("z");
+
+        let err = validator
+            .validate(&HashSet::from([
+                a,
+                b.clone(),
+                c.clone(),
+                d.clone(),
+                unregistered.clone(),
+            ]))
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            CollectionMembershipError::InvalidMembers(InvalidMembers {
+                unknown: vec![c, unregistered],
+                deprecated: vec![b, d],
+            })
+        );
+    }
+
+    #[test]
+    fn unreplayable_stream_should_be_reported() {
+        let a = dummy_id("a");
+        let events = vec![ComponentEvent::SbomAssigned {
+            component_id: a.clone(),
+            sbom: Sbom::from_url_str("https://example.com/sbom.json").unwrap(),
+        }];
+        let validator = validator(vec![(a.clone(), events)]);
+
+        let err = validator.validate(&HashSet::from([a.clone()])).unwrap_err();
+        assert_eq!(
+            err,
+            CollectionMembershipError::InconsistentStream(
+                Box::new(a),
+                ComponentError::InvalidInitialEvent.to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use crate::domain::component::id::ComponentId;
+use crate::domain::vulnerability::id::{ManagedVulnerabilityId, VulnerabilityId};
+
+/// Lookup of the managed vulnerabilities tracked so far.
+pub trait ManagedVulnerabilityIndex: Send + Sync {
+    /// Managed vulnerabilities affecting the given component.
+    fn by_component(&self, id: &ComponentId) -> Vec<ManagedVulnerabilityId>;
+
+    /// Managed vulnerabilities tracking the given vulnerability or one of its aliases, on any component.
+    fn by_vulnerability(&self, id: &VulnerabilityId) -> Vec<ManagedVulnerabilityId>;
+}
@@ -1 +1,9 @@
+pub mod collection_membership;
+pub mod managed_vulnerability_index;
+pub mod sbom_fetcher;
+pub mod sbom_generation_failures;
 pub mod sbom_generator;
+pub mod sbom_parser;
+pub mod sbom_store;
+pub mod vulnerability_ingestion;
+pub mod vulnerability_store;
@@ -0,0 +1,50 @@
+use crate::domain::component::sbom::{Sbom, SbomIntegrityError};
+use async_trait::async_trait;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Retrieves the contents of SBOMs hosted outside of venom, e.g. published by a vendor.
+#[async_trait]
+pub trait SbomFetcher: Send + Sync {
+    /// Fetch the document an SBOM points to.
+    ///
+    /// Fetching must not block the calling thread.
+    ///
+    /// When the SBOM already carries a digest, the contents must match it. Otherwise the
+    /// returned [`FetchedSbom`] pins the contents that were retrieved, so that it can be
+    /// assigned to a component and read again later.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomFetcherError`] if the location cannot be fetched, the document is too
+    /// large, the contents do not match the digest of the SBOM, or they cannot be cached.
+    async fn fetch(&self, sbom: &Sbom) -> Result<FetchedSbom, SbomFetcherError>;
+}
+
+/// Contents of a fetched SBOM, with the SBOM sealed with their digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedSbom {
+    pub sbom: Sbom,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SbomFetcherError {
+    #[error("SBOM location '{0}' cannot be fetched")]
+    UnsupportedLocation(String),
+
+    #[error("Could not fetch SBOM '{0}': {1}")]
+    Unreachable(String, String),
+
+    #[error("Fetching SBOM '{0}' timed out after {1:?}")]
+    TimedOut(String, Duration),
+
+    #[error("SBOM '{0}' is larger than {1} bytes")]
+    TooLarge(String, u64),
+
+    #[error(transparent)]
+    Integrity(#[from] SbomIntegrityError),
+
+    #[error("SBOM cache '{0}' is unavailable: {1}")]
+    CacheUnavailable(String, String),
+}
@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::application::aggregate::component::cmd::{ComponentCommand, ComponentCommandKind};
+use crate::application::shared::command::CommandBus;
+use crate::domain::component::id::ComponentId;
+
+/// A component left without SBOM after its last generation attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SbomGenerationFailure {
+    pub component_id: ComponentId,
+    pub reason: String,
+    pub attempts: u32,
+    pub date: SystemTime,
+}
+
+/// Read model of the components whose SBOM generation failed, fed from `SbomGenerationFailed`
+/// events and cleared once an SBOM is assigned or the component is deprecated.
+pub trait SbomGenerationFailureIndex: Send + Sync {
+    /// Latest failure of each component still without SBOM, ordered by component.
+    fn failures(&self) -> Vec<SbomGenerationFailure>;
+}
+
+/// Operator entry point to inspect failed SBOM generations and trigger them again.
+pub struct SbomGenerationFailures {
+    command_bus: Arc<Mutex<CommandBus>>,
+    index: Arc<dyn SbomGenerationFailureIndex>,
+}
+
+impl SbomGenerationFailures {
+    pub fn new(
+        command_bus: Arc<Mutex<CommandBus>>,
+        index: Arc<dyn SbomGenerationFailureIndex>,
+    ) -> Self {
+        Self { command_bus, index }
+    }
+
+    #[must_use]
+    pub fn list(&self) -> Vec<SbomGenerationFailure> {
+        self.index.failures()
+    }
+
+    /// Request a new generation for the given component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no handler is registered for component commands.
+    pub fn retrigger(&self, component_id: &ComponentId) -> Result<(), String> {
+        tracing::info!("Requesting SBOM generation again for {component_id}");
+        self.command_bus
+            .lock()
+            .unwrap()
+            .dispatch(Box::new(ComponentCommand {
+                id: component_id.clone(),
+                kind: ComponentCommandKind::RequestSbomGeneration,
+            }))
+    }
+
+    /// Request a new generation for every failed component, returning their ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no handler is registered for component commands.
+    pub fn retrigger_all(&self) -> Result<Vec<ComponentId>, String> {
+        self.list()
+            .into_iter()
+            .map(|failure| {
+                self.retrigger(&failure.component_id)
+                    .map(|()| failure.component_id)
+            })
+            .collect()
+    }
+}
@@ -1,14 +1,21 @@
+use crate::application::service::sbom_store::SbomStoreError;
 use crate::domain::component::{id::ComponentId, sbom::Sbom};
+use async_trait::async_trait;
+use std::time::Duration;
 use thiserror::Error;
 
+#[async_trait]
 pub trait SbomGenerator: Send + Sync {
     /// Generates a Software Bill of Materials (SBOM) for the given component ID.
     ///
+    /// Generation must not block the calling thread. Dropping the returned future cancels
+    /// the generation, including any process it started.
+    ///
     /// # Errors
     ///
     /// Returns [`SbomGeneratorError`] if the SBOM generation process fails
-    /// due to missing tools, filesystem errors, or command execution issues.
-    fn generate(&self, id: &ComponentId) -> Result<Sbom, SbomGeneratorError>;
+    /// due to missing tools, command execution issues, or storage errors.
+    async fn generate(&self, id: &ComponentId) -> Result<Sbom, SbomGeneratorError>;
 }
 
 #[derive(Debug, Error)]
@@ -16,12 +23,25 @@ pub enum SbomGeneratorError {
     #[error("Required tool '{0}' is not available: {1}")]
     ToolUnavailable(String, String),
 
-    #[error("Could not create destination '{0}': {1}")]
-    DestinationUnavailable(String, String),
-
     #[error("Failed to generate SBOM for '{0}': {1}")]
     GenerationFailed(String, String),
 
-    #[error("Destination '{0}' already exists")]
-    DestinationAlreadyExists(String),
+    #[error("No SBOM available for '{0}': {1}")]
+    NotFound(String, String),
+
+    #[error("SBOM generation for '{0}' timed out after {1:?}")]
+    TimedOut(String, Duration),
+
+    #[error("Could not store generated SBOM: {0}")]
+    Store(#[from] SbomStoreError),
+}
+
+impl SbomGeneratorError {
+    /// Whether trying again later may succeed.
+    ///
+    /// A missing tool or SBOM needs an operator, whereas pulls, timeouts and storage may recover.
+    #[must_use]
+    pub const fn is_transient(&self) -> bool {
+        !matches!(self, Self::ToolUnavailable(..) | Self::NotFound(..))
+    }
 }
@@ -0,0 +1,48 @@
+use crate::application::service::sbom_fetcher::SbomFetcherError;
+use crate::domain::component::{
+    inventory::{PackageInventory, PackageInventoryError},
+    sbom::{Sbom, SbomIntegrityError},
+};
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[async_trait]
+pub trait SbomParser: Send + Sync {
+    /// Reads the packages listed in the given SBOM.
+    ///
+    /// Reading must not block the calling thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomParserError`] if the SBOM cannot be read, does not match its digest,
+    /// is not in a supported format or version, or does not describe a consistent package
+    /// inventory.
+    async fn parse(&self, sbom: &Sbom) -> Result<PackageInventory, SbomParserError>;
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SbomParserError {
+    #[error("SBOM location '{0}' cannot be read by this parser")]
+    UnsupportedLocation(String),
+
+    #[error("Could not read SBOM '{0}': {1}")]
+    Unreadable(String, String),
+
+    #[error(transparent)]
+    Integrity(#[from] SbomIntegrityError),
+
+    #[error(transparent)]
+    Fetch(#[from] SbomFetcherError),
+
+    #[error("SBOM '{0}' is malformed: {1}")]
+    Malformed(String, String),
+
+    #[error("SBOM '{0}' is not a {1} document")]
+    UnsupportedFormat(String, String),
+
+    #[error("SBOM '{0}' uses unsupported {1} version '{2}'")]
+    UnsupportedSpecVersion(String, String, String),
+
+    #[error("SBOM '{0}' has an inconsistent package inventory: {1}")]
+    InvalidInventory(String, PackageInventoryError),
+}
@@ -0,0 +1,42 @@
+use crate::domain::component::{id::ComponentId, sbom::Sbom};
+use thiserror::Error;
+
+/// Storage of SBOM documents, indexed by the component they describe.
+///
+/// Generators hand their output to the store rather than choosing where to write it, so that
+/// identical documents are kept once and regenerating an SBOM simply replaces the index entry.
+pub trait SbomStore: Send + Sync {
+    /// Store the contents of an SBOM and index them under the given component.
+    ///
+    /// The returned [`Sbom`] points to the stored document and carries its digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomStoreError::Unavailable`] if the document or its index entry cannot be written.
+    fn put(&self, component: &ComponentId, content: &[u8]) -> Result<Sbom, SbomStoreError>;
+
+    /// SBOM currently indexed under the given component, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomStoreError::Unavailable`] if the index cannot be read, or
+    /// [`SbomStoreError::CorruptIndex`] if its entry for the component cannot be understood.
+    fn get(&self, component: &ComponentId) -> Result<Option<Sbom>, SbomStoreError>;
+
+    /// Every indexed SBOM with its component, ordered by component.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomStoreError::Unavailable`] if the index cannot be read, or
+    /// [`SbomStoreError::CorruptIndex`] if one of its entries cannot be understood.
+    fn list(&self) -> Result<Vec<(ComponentId, Sbom)>, SbomStoreError>;
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SbomStoreError {
+    #[error("SBOM storage '{0}' is unavailable: {1}")]
+    Unavailable(String, String),
+
+    #[error("SBOM index entry '{0}' is corrupt: {1}")]
+    CorruptIndex(String, String),
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::application::aggregate::vulnerability::event::{
+    VulnerabilityRegisteredEvent, VulnerabilityUpdatedEvent,
+};
+use crate::application::service::vulnerability_store::VulnerabilityStore;
+use crate::application::shared::event::bus::EventBus;
+use crate::domain::vulnerability::entity::Vulnerability;
+use crate::domain::vulnerability::event::VulnerabilityEvent;
+use crate::domain::vulnerability::id::AdvisoryId;
+
+/// Source of vulnerability records, such as a local export of an OSV database.
+pub trait VulnerabilityFeed: Send + Sync {
+    /// Read every record of the feed.
+    ///
+    /// Records that cannot be understood are reported in [`FeedRecords::rejected`] rather
+    /// than failing the whole feed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VulnerabilityFeedError::Unavailable`] if the feed itself cannot be read.
+    fn read(&self) -> Result<FeedRecords, VulnerabilityFeedError>;
+}
+
+/// Contents of a feed.
+#[derive(Debug, Default)]
+pub struct FeedRecords {
+    pub vulnerabilities: Vec<Vulnerability>,
+    pub rejected: Vec<VulnerabilityFeedError>,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum VulnerabilityFeedError {
+    #[error("Vulnerability feed '{0}' is unavailable: {1}")]
+    Unavailable(String, String),
+
+    #[error("Vulnerability record '{0}' is malformed: {1}")]
+    Malformed(String, String),
+
+    #[error("Vulnerability record '{0}' has no recognized identifier among {1:?}")]
+    Unidentified(String, Vec<String>),
+}
+
+/// Outcome of the ingestion of a feed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IngestionReport {
+    pub registered: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub rejected: Vec<VulnerabilityFeedError>,
+}
+
+/// Loads vulnerability feeds into the store.
+///
+/// Records published for the same vulnerability within a feed are merged first. Each
+/// vulnerability is then compared with the stored one: new ones are announced with
+/// `VulnerabilityRegistered`, changed ones with `VulnerabilityUpdated`, and identical ones
+/// are left alone, so that ingesting the same export twice emits nothing.
+pub struct VulnerabilityIngestion<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    store: Arc<dyn VulnerabilityStore>,
+    event_bus: Arc<EB>,
+}
+
+impl<EB> VulnerabilityIngestion<EB>
+where
+    EB: EventBus + Send + Sync + 'static,
+{
+    pub fn new(store: Arc<dyn VulnerabilityStore>, event_bus: Arc<EB>) -> Self {
+        Self { store, event_bus }
+    }
+
+    /// Ingest every record of the feed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VulnerabilityFeedError::Unavailable`] if the feed cannot be read.
+    pub fn ingest(
+        &self,
+        feed: &dyn VulnerabilityFeed,
+    ) -> Result<IngestionReport, VulnerabilityFeedError> {
+        let FeedRecords {
+            vulnerabilities,
+            rejected,
+        } = feed.read()?;
+        let mut report = IngestionReport {
+            rejected,
+            ..IngestionReport::default()
+        };
+
+        for vulnerability in merge(vulnerabilities) {
+            let previous = self.store.find(vulnerability.id());
+            let Some(event) = vulnerability.changes(previous.as_ref()) else {
+                report.unchanged += 1;
+                continue;
+            };
+
+            self.store.put(vulnerability);
+            let published = match event {
+                VulnerabilityEvent::VulnerabilityRegistered { .. } => {
+                    report.registered += 1;
+                    self.event_bus
+                        .publish(VulnerabilityRegisteredEvent::new(event))
+                }
+                VulnerabilityEvent::VulnerabilityUpdated { .. } => {
+                    report.updated += 1;
+                    self.event_bus
+                        .publish(VulnerabilityUpdatedEvent::new(event))
+                }
+            };
+            if let Err(e) = published {
+                tracing::error!("Could not publish vulnerability event: {e}");
+            }
+        }
+
+        tracing::info!(
+            "Ingested vulnerabilities: {} registered, {} updated, {} unchanged, {} rejected",
+            report.registered,
+            report.updated,
+            report.unchanged,
+            report.rejected.len()
+        );
+        Ok(report)
+    }
+}
+
+/// Merge the records sharing an identifier, keeping the order of their first appearance.
+///
+/// A record sharing identifiers with several earlier ones links them: they are all merged.
+fn merge(vulnerabilities: Vec<Vulnerability>) -> Vec<Vulnerability> {
+    let mut merged: Vec<Option<Vulnerability>> = Vec::new();
+    let mut positions: HashMap<AdvisoryId, usize> = HashMap::new();
+
+    for vulnerability in vulnerabilities {
+        let mut matches: Vec<usize> = vulnerability
+            .id()
+            .ids()
+            .filter_map(|id| positions.get(id).copied())
+            .collect();
+        matches.sort_unstable();
+        matches.dedup();
+
+        let position = matches.first().copied().unwrap_or(merged.len());
+        let linked = matches
+            .iter()
+            .filter_map(|&position| merged[position].take())
+            .chain(std::iter::once(vulnerability))
+            .reduce(Vulnerability::merge);
+        if position == merged.len() {
+            merged.push(linked);
+        } else {
+            merged[position] = linked;
+        }
+
+        if let Some(vulnerability) = &merged[position] {
+            for id in vulnerability.id().ids() {
+                positions.insert(id.clone(), position);
+            }
+        }
+    }
+    merged.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::shared::event::Event;
+    use crate::application::shared::event::error::EventBusError;
+    use crate::domain::vulnerability::cvss::Cvss;
+    use crate::domain::vulnerability::id::VulnerabilityId;
+    use crate::infrastructure::store::in_memory_vulnerability::InMemoryVulnerabilityStore;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingBus(Mutex<Vec<VulnerabilityEvent>>);
+
+    impl EventBus for RecordingBus {
+        fn publish<E: Event + Clone + 'static>(&self, event: E) -> Result<(), EventBusError> {
+            if let Some(payload) = event.as_payload::<VulnerabilityEvent>() {
+                self.0.lock().unwrap().push(payload.clone());
+            }
+            Ok(())
+        }
+    }
+
+    impl RecordingBus {
+        fn take(&self) -> Vec<VulnerabilityEvent> {
+            std::mem::take(&mut self.0.lock().unwrap())
+        }
+    }
+
+    struct Records(Vec<Vulnerability>);
+
+    impl VulnerabilityFeed for Records {
+        fn read(&self) -> Result<FeedRecords, VulnerabilityFeedError> {
+            Ok(FeedRecords {
+                vulnerabilities: self.0.clone(),
+                rejected: vec![VulnerabilityFeedError::Malformed(
+                    "broken.json".to_string(),
+                    "EOF".to_string(),
+                )],
+            })
+        }
+    }
+
+    fn vulnerability(primary: &str, aliases: &[&str]) -> Vulnerability {
+        Vulnerability::new(
+            VulnerabilityId::from_str(primary)
+                .unwrap()
+                .with_aliases(aliases.iter().map(|alias| alias.parse().unwrap())),
+        )
+    }
+
+    fn cvss(vector: &str) -> Cvss {
+        Cvss::from_str(vector).unwrap()
+    }
+
+    fn ingestion() -> (VulnerabilityIngestion<RecordingBus>, Arc<RecordingBus>) {
+        let bus = Arc::new(RecordingBus::default());
+        let store = Arc::new(InMemoryVulnerabilityStore::default());
+        (VulnerabilityIngestion::new(store, bus.clone()), bus)
+    }
+
+    #[test]
+    fn ingesting_the_same_records_twice_should_emit_nothing_the_second_time() {
+        let (ingestion, bus) = ingestion();
+        let feed = Records(vec![
+            vulnerability("GHSA-xvch-5gv4-984h", &["CVE-2021-44906"]),
+            vulnerability("PYSEC-2024-12", &[]),
+        ]);
+
+        let report = ingestion.ingest(&feed).unwrap();
+        assert_eq!(
+            (report.registered, report.updated, report.unchanged),
+            (2, 0, 0)
+        );
+        assert_eq!(report.rejected.len(), 1);
+        assert!(matches!(
+            &bus.take()[..],
+            [
+                VulnerabilityEvent::VulnerabilityRegistered { .. },
+                VulnerabilityEvent::VulnerabilityRegistered { .. }
+            ]
+        ));
+
+        let report = ingestion.ingest(&feed).unwrap();
+        assert_eq!(
+            (report.registered, report.updated, report.unchanged),
+            (0, 0, 2)
+        );
+        assert!(bus.take().is_empty());
+    }
+
+    #[test]
+    fn changed_records_should_be_updated() {
+        let (ingestion, bus) = ingestion();
+        let low = cvss("CVSS:3.1/AV:N/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N");
+        let high = cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H");
+        let original = vulnerability("GHSA-xvch-5gv4-984h", &[]).with_cvss([low]);
+        ingestion.ingest(&Records(vec![original])).unwrap();
+        bus.take();
+
+        let rescored = vulnerability("GHSA-xvch-5gv4-984h", &["CVE-2021-44906"]).with_cvss([high]);
+        let report = ingestion.ingest(&Records(vec![rescored])).unwrap();
+
+        assert_eq!((report.registered, report.updated), (0, 1));
+        assert_eq!(
+            bus.take(),
+            vec![VulnerabilityEvent::VulnerabilityUpdated {
+                vulnerability_id: vulnerability("CVE-2021-44906", &[]).id().clone(),
+                previous_cvss: vec![low],
+                cvss: vec![high],
+            }]
+        );
+    }
+
+    #[test]
+    fn records_linking_earlier_records_should_merge_them_all() {
+        let (ingestion, bus) = ingestion();
+        let feed = Records(vec![
+            vulnerability("GHSA-xvch-5gv4-984h", &[]).with_summary("GHSA"),
+            vulnerability("PYSEC-2024-12", &[]).with_summary("PYSEC"),
+            vulnerability("GHSA-xvch-5gv4-984h", &["PYSEC-2024-12"]),
+        ]);
+
+        let report = ingestion.ingest(&feed).unwrap();
+        assert_eq!(report.registered, 1);
+        assert_eq!(bus.take().len(), 1);
+        let [merged] = &ingestion.store.list()[..] else {
+            panic!("Expected a single vulnerability");
+        };
+        assert!(merged.id().is_known_as(&"PYSEC-2024-12".parse().unwrap()));
+        assert!(
+            merged
+                .id()
+                .is_known_as(&"GHSA-xvch-5gv4-984h".parse().unwrap())
+        );
+        assert_eq!(merged.summary(), Some("GHSA"));
+
+        let report = ingestion.ingest(&feed).unwrap();
+        assert_eq!(
+            (report.registered, report.updated, report.unchanged),
+            (0, 0, 1)
+        );
+        assert!(bus.take().is_empty());
+    }
+
+    #[test]
+    fn records_sharing_an_identifier_should_be_merged() {
+        let (ingestion, bus) = ingestion();
+        let feed = Records(vec![
+            vulnerability("GHSA-xvch-5gv4-984h", &["CVE-2021-44906"]).with_summary("GHSA"),
+            vulnerability("PYSEC-2024-12", &[]),
+            vulnerability("CVE-2021-44906", &["PYSEC-2022-1"]).with_summary("PYSEC"),
+        ]);
+
+        let report = ingestion.ingest(&feed).unwrap();
+
+        assert_eq!(report.registered, 2);
+        assert_eq!(bus.take().len(), 2);
+        let merged = ingestion
+            .store
+            .find(vulnerability("PYSEC-2022-1", &[]).id())
+            .unwrap();
+        assert_eq!(merged.id().as_str(), "CVE-2021-44906");
+        assert!(
+            merged
+                .id()
+                .is_known_as(&"GHSA-xvch-5gv4-984h".parse().unwrap())
+        );
+        assert_eq!(merged.summary(), Some("GHSA"));
+    }
+}
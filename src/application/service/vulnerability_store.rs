@@ -0,0 +1,18 @@
+use crate::domain::vulnerability::entity::Vulnerability;
+use crate::domain::vulnerability::id::VulnerabilityId;
+
+/// Storage of the vulnerabilities ingested from external databases.
+///
+/// Lookups go through every identifier of a vulnerability, so that a record keeps replacing
+/// the same entry when its primary identifier changes, e.g. once a GHSA advisory gets a CVE.
+pub trait VulnerabilityStore: Send + Sync {
+    /// Stored vulnerability sharing at least one identifier with `id`.
+    fn find(&self, id: &VulnerabilityId) -> Option<Vulnerability>;
+
+    /// Store a vulnerability, replacing and returning every one sharing an identifier with it:
+    /// a record aliasing two stored vulnerabilities reveals they are the same.
+    fn put(&self, vulnerability: Vulnerability) -> Vec<Vulnerability>;
+
+    /// Every stored vulnerability, ordered by identifier.
+    fn list(&self) -> Vec<Vulnerability>;
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+/// Exponential backoff between a bounded number of attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// At least one attempt is always made, even if `max_attempts` is zero.
+    #[must_use]
+    pub const fn new(max_attempts: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    #[must_use]
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay to wait after the given failed attempt (starting at 1) before trying again,
+    /// doubling after every attempt up to the maximum backoff.
+    ///
+    /// Returns `None` once the last attempt has been made.
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let factor = 2u32
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        Some(
+            self.initial_backoff
+                .checked_mul(factor)
+                .map_or(self.max_backoff, |delay| delay.min(self.max_backoff)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_should_double_up_to_the_maximum() {
+        let policy = RetryPolicy::new(6, Duration::from_secs(1), Duration::from_secs(10));
+        let delays: Vec<_> = (1..=6).map(|attempt| policy.backoff(attempt)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(8)),
+                Some(Duration::from_secs(10)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn backoff_should_stop_after_the_last_attempt() {
+        let once = RetryPolicy::new(1, Duration::from_secs(1), Duration::from_secs(10));
+        assert_eq!(once.backoff(1), None);
+
+        let never = RetryPolicy::new(0, Duration::from_secs(1), Duration::from_secs(10));
+        assert_eq!(never.backoff(1), None);
+
+        let long = RetryPolicy::new(u32::MAX, Duration::from_secs(1), Duration::from_secs(60));
+        assert_eq!(long.backoff(100), Some(Duration::from_secs(60)));
+    }
+}
@@ -1,5 +1,6 @@
 use config::{Config, ConfigError, Environment, File};
 use serde::Deserialize;
+use std::num::{NonZeroU64, NonZeroUsize};
 
 const CONFIG_PATH_ENV: &str = "VULMAN_CONFIG_PATH";
 const DEFAULT_CONFIG_FILE_PATH: &str = "config";
@@ -9,6 +10,55 @@ const DEFAULT_HOST: &str = "0.0.0.0";
 pub struct VenomConfig {
     pub server: Server,
     pub sboms_path: String,
+    #[serde(default)]
+    pub sbom_generator: SbomGeneratorKind,
+    /// Directories, OCI layouts and `docker save` tarballs searched by the offline generator.
+    #[serde(default)]
+    pub offline_sbom_sources: Vec<String>,
+    #[serde(default)]
+    pub sbom_generation: SbomGeneration,
+    /// Local OSV exports ingested at startup: directories of JSON records or zip archives.
+    #[serde(default)]
+    pub osv_sources: Vec<String>,
+}
+
+/// Limits applied to SBOM generations.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct SbomGeneration {
+    /// Time after which a generation is abandoned and its tool killed.
+    pub timeout_secs: NonZeroU64,
+    /// Number of generations allowed to run at the same time.
+    pub max_concurrent: NonZeroUsize,
+    /// Attempts made before a generation is recorded as failed.
+    pub max_attempts: u32,
+    /// Delay before the second attempt, doubled after every further attempt.
+    pub initial_backoff_secs: u64,
+    /// Upper bound of the delay between two attempts.
+    pub max_backoff_secs: u64,
+}
+
+impl Default for SbomGeneration {
+    fn default() -> Self {
+        Self {
+            timeout_secs: NonZeroU64::new(600).unwrap(),
+            max_concurrent: NonZeroUsize::new(8).unwrap(),
+            max_attempts: 4,
+            initial_backoff_secs: 30,
+            max_backoff_secs: 600,
+        }
+    }
+}
+
+/// Tool used to generate the SBOMs of registered components.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomGeneratorKind {
+    #[default]
+    Syft,
+    Trivy,
+    /// Pre-built SBOMs read from [`VenomConfig::offline_sbom_sources`], without network access.
+    Offline,
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,3 +101,26 @@ impl VenomConfig {
         config.try_deserialize()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use config::FileFormat;
+
+    fn sbom_generation(yaml: &str) -> Result<SbomGeneration, ConfigError> {
+        Config::builder()
+            .add_source(File::from_str(yaml, FileFormat::Yaml))
+            .build()?
+            .try_deserialize()
+    }
+
+    #[test]
+    fn sbom_generation_limits_should_not_be_zero() {
+        let limits = sbom_generation("max_concurrent: 2").unwrap();
+        assert_eq!(limits.max_concurrent.get(), 2);
+        assert_eq!(limits.timeout_secs.get(), 600);
+
+        assert!(sbom_generation("max_concurrent: 0").is_err());
+        assert!(sbom_generation("timeout_secs: 0").is_err());
+    }
+}
@@ -1,4 +1,294 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use thiserror::Error;
+
+/// How reachable a component is from untrusted networks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Exposure {
+    /// Reachable from the internet.
+    InternetFacing,
+    /// Reachable only from internal networks.
+    Internal,
+    /// Not reachable over the network.
+    Isolated,
+}
+
+impl fmt::Display for Exposure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::InternetFacing => "internet-facing",
+            Self::Internal => "internal",
+            Self::Isolated => "isolated",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Privileges granted to the component at runtime, and how isolated it is from its host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Isolation {
+    /// Runs as root, without effective isolation from the host.
+    Root,
+    /// Runs with elevated capabilities or host access, but not as root.
+    Privileged,
+    /// Runs unprivileged within a sandbox.
+    Sandboxed,
+}
+
+impl fmt::Display for Isolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Root => "root",
+            Self::Privileged => "privileged",
+            Self::Sandboxed => "sandboxed",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Sensitivity of the data the component processes or stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DataSensitivity {
+    Public,
+    Internal,
+    Confidential,
+    Restricted,
+}
+
+impl fmt::Display for DataSensitivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Public => "public",
+            Self::Internal => "internal",
+            Self::Confidential => "confidential",
+            Self::Restricted => "restricted",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Runtime conditions of a component: exposure level, isolation, policies and roles.
+///
+/// Policies and roles are kept as ordered sets, so two contexts are equal whenever they hold
+/// the same values, regardless of the order in which policies and roles were given.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum ExecutionContext {
-    None,
+pub struct ExecutionContext {
+    exposure: Exposure,
+    isolation: Isolation,
+    data_sensitivity: DataSensitivity,
+    policies: BTreeSet<String>,
+    roles: BTreeSet<String>,
+}
+
+impl ExecutionContext {
+    /// Create an execution context, validating the names of its policies and roles.
+    ///
+    /// Names are trimmed and duplicates are collapsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExecutionContextError::InvalidPolicy`] or [`ExecutionContextError::InvalidRole`]
+    /// if a name is empty or contains whitespace or control characters.
+    pub fn new<P, R>(
+        exposure: Exposure,
+        isolation: Isolation,
+        data_sensitivity: DataSensitivity,
+        policies: P,
+        roles: R,
+    ) -> Result<Self, ExecutionContextError>
+    where
+        P: IntoIterator,
+        P::Item: Into<String>,
+        R: IntoIterator,
+        R::Item: Into<String>,
+    {
+        Ok(Self {
+            exposure,
+            isolation,
+            data_sensitivity,
+            policies: validate_names(policies, ExecutionContextError::InvalidPolicy)?,
+            roles: validate_names(roles, ExecutionContextError::InvalidRole)?,
+        })
+    }
+
+    #[must_use]
+    pub const fn exposure(&self) -> Exposure {
+        self.exposure
+    }
+
+    #[must_use]
+    pub const fn isolation(&self) -> Isolation {
+        self.isolation
+    }
+
+    #[must_use]
+    pub const fn data_sensitivity(&self) -> DataSensitivity {
+        self.data_sensitivity
+    }
+
+    #[must_use]
+    pub const fn policies(&self) -> &BTreeSet<String> {
+        &self.policies
+    }
+
+    #[must_use]
+    pub const fn roles(&self) -> &BTreeSet<String> {
+        &self.roles
+    }
+
+    #[must_use]
+    pub fn has_policy(&self, policy: &str) -> bool {
+        self.policies.contains(policy)
+    }
+
+    #[must_use]
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.contains(role)
+    }
+}
+
+impl fmt::Display for ExecutionContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            self.exposure, self.isolation, self.data_sensitivity
+        )?;
+        if !self.policies.is_empty() {
+            let policies: Vec<&str> = self.policies.iter().map(String::as_str).collect();
+            write!(f, " policies=[{}]", policies.join(","))?;
+        }
+        if !self.roles.is_empty() {
+            let roles: Vec<&str> = self.roles.iter().map(String::as_str).collect();
+            write!(f, " roles=[{}]", roles.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_names<I>(
+    names: I,
+    error: fn(String) -> ExecutionContextError,
+) -> Result<BTreeSet<String>, ExecutionContextError>
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    names
+        .into_iter()
+        .map(|name| {
+            let name: String = name.into();
+            let trimmed = name.trim();
+            let valid = !trimmed.is_empty()
+                && !trimmed
+                    .chars()
+                    .any(|c| c.is_whitespace() || c.is_control() || c == ',');
+            if valid {
+                Ok(trimmed.to_string())
+            } else {
+                Err(error(name))
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ExecutionContextError {
+    #[error("Invalid policy name `{0}`")]
+    InvalidPolicy(String),
+
+    #[error("Invalid role name `{0}`")]
+    InvalidRole(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(policies: &[&str], roles: &[&str]) -> ExecutionContext {
+        ExecutionContext::new(
+            Exposure::InternetFacing,
+            Isolation::Sandboxed,
+            DataSensitivity::Confidential,
+            policies.iter().copied(),
+            roles.iter().copied(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn new_should_normalize_policies_and_roles() {
+        let ctx = context(&[" pci-dss ", "gdpr", "pci-dss"], &["payments"]);
+
+        assert_eq!(ctx.exposure(), Exposure::InternetFacing);
+        assert_eq!(ctx.isolation(), Isolation::Sandboxed);
+        assert_eq!(ctx.data_sensitivity(), DataSensitivity::Confidential);
+        assert_eq!(ctx.policies().len(), 2);
+        assert!(ctx.has_policy("pci-dss"));
+        assert!(ctx.has_role("payments"));
+        assert!(!ctx.has_role("admin"));
+    }
+
+    #[test]
+    fn equality_should_ignore_order_of_policies_and_roles() {
+        let a = context(&["gdpr", "pci-dss"], &["payments", "billing"]);
+        let b = context(&["pci-dss", "gdpr"], &["billing", "payments"]);
+        assert_eq!(a, b);
+
+        let c = context(&["gdpr"], &["payments", "billing"]);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn equality_should_take_levels_into_account() {
+        let a = context(&[], &[]);
+        let b = ExecutionContext::new(
+            Exposure::Internal,
+            Isolation::Sandboxed,
+            DataSensitivity::Confidential,
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+        )
+        .unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn new_should_reject_invalid_names() {
+        let err = ExecutionContext::new(
+            Exposure::Isolated,
+            Isolation::Root,
+            DataSensitivity::Public,
+            ["  "],
+            Vec::<String>::new(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ExecutionContextError::InvalidPolicy("  ".to_string()));
+
+        let err = ExecutionContext::new(
+            Exposure::Isolated,
+            Isolation::Root,
+            DataSensitivity::Public,
+            Vec::<String>::new(),
+            ["cluster admin"],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ExecutionContextError::InvalidRole("cluster admin".to_string())
+        );
+    }
+
+    #[test]
+    fn display_should_list_levels_policies_and_roles() {
+        let ctx = context(&["pci-dss", "gdpr"], &["payments"]);
+        assert_eq!(
+            ctx.to_string(),
+            "internet-facing/sandboxed/confidential policies=[gdpr,pci-dss] roles=[payments]"
+        );
+        assert_eq!(
+            context(&[], &[]).to_string(),
+            "internet-facing/sandboxed/confidential"
+        );
+    }
 }
@@ -34,4 +34,32 @@ pub enum ComponentEvent {
         component_id: ComponentId,
         context: ExecutionContext,
     },
+
+    /// Every attempt to generate the SBOM of the component failed.
+    ///
+    /// The component keeps waiting for an SBOM until generation is requested again.
+    SbomGenerationFailed {
+        component_id: ComponentId,
+        reason: String,
+        attempts: u32,
+    },
+
+    /// Generation of the SBOM was requested again, typically after a failure.
+    SbomGenerationRequested { component_id: ComponentId },
+}
+
+impl ComponentEvent {
+    /// Identifier of the component the event belongs to.
+    #[must_use]
+    pub const fn component_id(&self) -> &ComponentId {
+        match self {
+            Self::ComponentRegistered { component_id }
+            | Self::ComponentDeprecated { component_id }
+            | Self::SbomAssigned { component_id, .. }
+            | Self::ExecutionContextAssigned { component_id, .. }
+            | Self::ExecutionContextReplaced { component_id, .. }
+            | Self::SbomGenerationFailed { component_id, .. }
+            | Self::SbomGenerationRequested { component_id } => component_id,
+        }
+    }
 }
@@ -1,15 +1,119 @@
+use sha2::{Digest as _, Sha256, Sha384, Sha512};
 use std::{fmt, str::FromStr};
 use thiserror::Error;
 
 const DEFAULT_REGISTRY: &str = "docker.io";
+const DOCKER_HUB_ALIASES: [&str; 1] = ["index.docker.io"];
+const DOCKER_HUB_NAMESPACE: &str = "library";
 const LATEST_TAG: &str = "latest";
+const MAX_NAME_LEN: usize = 255;
+const MAX_TAG_LEN: usize = 128;
+
+/// Algorithm of a content digest, as allowed by the OCI image specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// Number of hexadecimal characters of an encoded digest.
+    #[must_use]
+    pub const fn hex_len(self) -> usize {
+        match self {
+            Self::Sha256 => 64,
+            Self::Sha384 => 96,
+            Self::Sha512 => 128,
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Sha256 => "sha256",
+            Self::Sha384 => "sha384",
+            Self::Sha512 => "sha512",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Content digest pinning an image manifest (e.g. `sha256:<hex>`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Digest {
+    algorithm: DigestAlgorithm,
+    hex: String,
+}
+
+impl Digest {
+    /// Hash `content` with the given algorithm.
+    #[must_use]
+    pub fn compute(algorithm: DigestAlgorithm, content: &[u8]) -> Self {
+        let hex = match algorithm {
+            DigestAlgorithm::Sha256 => hex::encode(Sha256::digest(content)),
+            DigestAlgorithm::Sha384 => hex::encode(Sha384::digest(content)),
+            DigestAlgorithm::Sha512 => hex::encode(Sha512::digest(content)),
+        };
+        Self { algorithm, hex }
+    }
+
+    #[must_use]
+    pub const fn algorithm(&self) -> DigestAlgorithm {
+        self.algorithm
+    }
+
+    #[must_use]
+    pub fn hex(&self) -> &str {
+        &self.hex
+    }
+}
+
+impl FromStr for Digest {
+    type Err = ComponentIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algorithm, hex) = s
+            .split_once(':')
+            .ok_or_else(|| ComponentIdError::InvalidDigest(s.to_string()))?;
+
+        let algorithm = match algorithm {
+            "sha256" => DigestAlgorithm::Sha256,
+            "sha384" => DigestAlgorithm::Sha384,
+            "sha512" => DigestAlgorithm::Sha512,
+            _ => {
+                return Err(ComponentIdError::UnsupportedDigestAlgorithm(
+                    algorithm.to_string(),
+                ));
+            }
+        };
+
+        let is_lower_hex = |c: char| c.is_ascii_digit() || ('a'..='f').contains(&c);
+        if hex.len() != algorithm.hex_len() || !hex.chars().all(is_lower_hex) {
+            return Err(ComponentIdError::InvalidDigest(s.to_string()));
+        }
+
+        Ok(Self {
+            algorithm,
+            hex: hex.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.hex)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ComponentId {
     registry: String,
     namespace: Option<String>,
     name: String,
-    tag: String,
+    tag: Option<String>,
+    digest: Option<Digest>,
 }
 
 impl ComponentId {
@@ -25,91 +129,210 @@ impl ComponentId {
     pub fn name(&self) -> &str {
         &self.name
     }
+    /// Tag of the image, if any. References without tag nor digest default to `latest`.
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+    #[must_use]
+    pub const fn digest(&self) -> Option<&Digest> {
+        self.digest.as_ref()
+    }
+
+    /// Whether both references point into the same repository, whatever their tag or digest.
     #[must_use]
-    pub fn tag(&self) -> &str {
-        &self.tag
+    pub fn same_repository(&self, other: &Self) -> bool {
+        self.registry == other.registry
+            && self.namespace == other.namespace
+            && self.name == other.name
     }
 }
 
 impl FromStr for ComponentId {
     type Err = ComponentIdError;
 
+    /// Parse an image reference following the OCI distribution reference grammar.
+    ///
+    /// The result is canonical: the registry is lowercased, `index.docker.io` is folded into
+    /// `docker.io`, and single-segment Docker Hub names get the implicit `library` namespace.
+    /// So `nginx` and `docker.io/library/nginx` denote the same component.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.trim().is_empty() {
             return Err(ComponentIdError::InvalidFormat(s.to_string()));
         }
 
-        // Extract optional tag, if present
-        let (reference, tag) = match s.rsplit_once(':') {
-            Some((left, right)) if !right.contains('/') => (left, right.to_string()),
-            _ => (s, LATEST_TAG.to_string()),
+        // Extract optional digest, if present
+        let (reference, digest) = match s.split_once('@') {
+            Some((left, right)) => (left, Some(Digest::from_str(right)?)),
+            None => (s, None),
         };
 
-        if tag.eq_ignore_ascii_case(LATEST_TAG) {
-            tracing::warn!("{s} uses '{LATEST_TAG}' as a tag, which is discouraged.");
-        }
-
-        let segments: Vec<&str> = reference.split('/').collect();
-        let is_registry = |s: &str| s.contains('.') || s.contains(':') || s == "localhost";
-
-        let component = match segments.as_slice() {
-            // Single-part: image name
-            [name] => Self {
-                registry: DEFAULT_REGISTRY.to_string(),
-                namespace: None,
-                name: (*name).to_string(),
-                tag,
-            },
-
-            // Registry + image name (no namespace)
-            [registry, name] if is_registry(registry) => Self {
-                registry: (*registry).to_string(),
-                namespace: None,
-                name: (*name).to_string(),
-                tag,
-            },
-
-            // Namespace + image name
-            [namespace, name] => Self {
-                registry: DEFAULT_REGISTRY.to_string(),
-                namespace: Some((*namespace).to_string()),
-                name: (*name).to_string(),
-                tag,
-            },
-
-            // Registry + namespace(s) + image name
-            [registry, middle @ .., name] if is_registry(registry) => Self {
-                registry: (*registry).to_string(),
-                namespace: Some(middle.join("/")),
-                name: (*name).to_string(),
-                tag,
-            },
-
-            // Implicit registry + nested namespace + name
-            [middle @ .., name] => Self {
-                registry: DEFAULT_REGISTRY.to_string(),
-                namespace: Some(middle.join("/")),
-                name: (*name).to_string(),
-                tag,
-            },
-
-            _ => return Err(ComponentIdError::InvalidFormat(s.to_string())),
+        // Extract optional tag, if present
+        let (reference, tag) = match reference.rsplit_once(':') {
+            Some((left, right)) if !right.contains('/') => (left, Some(right.to_string())),
+            _ if digest.is_some() => (reference, None),
+            _ => (reference, Some(LATEST_TAG.to_string())),
         };
 
-        if component.name.is_empty() {
+        if reference.is_empty() {
             return Err(ComponentIdError::InvalidFormat(s.to_string()));
         }
 
-        Ok(component)
+        if let Some(tag) = &tag {
+            validate_tag(tag)?;
+            if tag.eq_ignore_ascii_case(LATEST_TAG) {
+                tracing::warn!("{s} uses '{LATEST_TAG}' as a tag, which is discouraged.");
+            }
+        }
+
+        if reference.len() > MAX_NAME_LEN {
+            return Err(ComponentIdError::NameTooLong(reference.len()));
+        }
+
+        // The first segment is a registry only if it looks like a host: it holds a `.` or
+        // a `:`, is `localhost`, or has uppercase letters, which repositories cannot hold.
+        let is_registry = |s: &str| {
+            s.contains(['.', ':']) || s == "localhost" || s.chars().any(|c| c.is_ascii_uppercase())
+        };
+        let (registry, path) = match reference.split_once('/') {
+            Some((first, rest)) if is_registry(first) => (canonical_registry(first)?, rest),
+            _ => (DEFAULT_REGISTRY.to_string(), reference),
+        };
+
+        let mut segments: Vec<&str> = path.split('/').collect();
+        for segment in &segments {
+            validate_path_component(segment)?;
+        }
+
+        let name = segments
+            .pop()
+            .ok_or_else(|| ComponentIdError::InvalidFormat(s.to_string()))?
+            .to_string();
+
+        let namespace = if !segments.is_empty() {
+            Some(segments.join("/"))
+        } else if registry == DEFAULT_REGISTRY {
+            Some(DOCKER_HUB_NAMESPACE.to_string())
+        } else {
+            None
+        };
+
+        Ok(Self {
+            registry,
+            namespace,
+            name,
+            tag,
+            digest,
+        })
+    }
+}
+
+/// Lowercase a registry host and resolve aliases, after validating it against the grammar:
+/// dot-separated alphanumeric labels (or a bracketed IPv6 address) and an optional port.
+fn canonical_registry(registry: &str) -> Result<String, ComponentIdError> {
+    let invalid = || ComponentIdError::InvalidRegistry(registry.to_string());
+
+    let (host, port) = if let Some(rest) = registry.strip_prefix('[') {
+        let (address, rest) = rest.split_once(']').ok_or_else(invalid)?;
+        let is_ipv6 =
+            !address.is_empty() && address.chars().all(|c| c.is_ascii_hexdigit() || c == ':');
+        if !is_ipv6 {
+            return Err(invalid());
+        }
+        let port = match rest {
+            "" => None,
+            _ => Some(rest.strip_prefix(':').ok_or_else(invalid)?),
+        };
+        (&registry[..address.len() + 2], port)
+    } else {
+        match registry.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (registry, None),
+        }
+    };
+
+    if !host.starts_with('[') {
+        let valid_label = |label: &str| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        };
+        if !host.split('.').all(valid_label) {
+            return Err(invalid());
+        }
+    }
+
+    if let Some(port) = port {
+        let valid_port = !port.is_empty()
+            && port.chars().all(|c| c.is_ascii_digit())
+            && port.parse::<u16>().is_ok_and(|p| p != 0);
+        if !valid_port {
+            return Err(ComponentIdError::InvalidPort(registry.to_string()));
+        }
+    }
+
+    let registry = registry.to_ascii_lowercase();
+    if DOCKER_HUB_ALIASES.contains(&registry.as_str()) {
+        Ok(DEFAULT_REGISTRY.to_string())
+    } else {
+        Ok(registry)
+    }
+}
+
+/// A path component is lowercase alphanumeric runs joined by a single separator:
+/// `.`, `_`, `__` or any number of `-`.
+fn validate_path_component(component: &str) -> Result<(), ComponentIdError> {
+    if component.is_empty() {
+        return Err(ComponentIdError::EmptyPathComponent);
+    }
+    if component.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(ComponentIdError::UppercasePathComponent(
+            component.to_string(),
+        ));
+    }
+
+    let invalid = || ComponentIdError::InvalidPathComponent(component.to_string());
+    let is_alphanumeric = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+    if !component.starts_with(is_alphanumeric) || !component.ends_with(is_alphanumeric) {
+        return Err(invalid());
+    }
+
+    for separator in component.split(is_alphanumeric).filter(|s| !s.is_empty()) {
+        let valid = matches!(separator, "." | "_" | "__") || separator.chars().all(|c| c == '-');
+        if !valid {
+            return Err(invalid());
+        }
+    }
+    Ok(())
+}
+
+/// A tag is up to 128 word characters, dots and dashes, not starting with a dot or a dash.
+fn validate_tag(tag: &str) -> Result<(), ComponentIdError> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let valid = tag.len() <= MAX_TAG_LEN
+        && tag.starts_with(is_word)
+        && tag.chars().all(|c| is_word(c) || c == '.' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(ComponentIdError::InvalidTag(tag.to_string()))
     }
 }
 
 impl fmt::Display for ComponentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.namespace {
-            Some(ns) => write!(f, "{}/{}/{}:{}", self.registry, ns, self.name, self.tag),
-            None => write!(f, "{}/{}:{}", self.registry, self.name, self.tag),
+            Some(ns) => write!(f, "{}/{}/{}", self.registry, ns, self.name)?,
+            None => write!(f, "{}/{}", self.registry, self.name)?,
+        }
+        if let Some(tag) = &self.tag {
+            write!(f, ":{tag}")?;
         }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{digest}")?;
+        }
+        Ok(())
     }
 }
 
@@ -117,6 +340,33 @@ impl fmt::Display for ComponentId {
 pub enum ComponentIdError {
     #[error("Invalid component id format: {0}")]
     InvalidFormat(String),
+
+    #[error("Invalid registry: {0}")]
+    InvalidRegistry(String),
+
+    #[error("Invalid registry port: {0}")]
+    InvalidPort(String),
+
+    #[error("Repository path contains an empty component")]
+    EmptyPathComponent,
+
+    #[error("Repository path component must be lowercase: {0}")]
+    UppercasePathComponent(String),
+
+    #[error("Invalid repository path component: {0}")]
+    InvalidPathComponent(String),
+
+    #[error("Repository name is {0} characters long, at most 255 are allowed")]
+    NameTooLong(usize),
+
+    #[error("Invalid tag: {0}")]
+    InvalidTag(String),
+
+    #[error("Unsupported digest algorithm: {0}")]
+    UnsupportedDigestAlgorithm(String),
+
+    #[error("Invalid digest: {0}")]
+    InvalidDigest(String),
 }
 
 #[cfg(test)]
@@ -132,18 +382,18 @@ mod tests {
     fn test_defaults_to_docker_io_and_library() {
         let c = comp("nginx");
         assert_eq!(c.registry, "docker.io");
-        assert_eq!(c.namespace, None);
+        assert_eq!(c.namespace, Some("library".to_string()));
         assert_eq!(c.name, "nginx");
-        assert_eq!(c.tag, "latest");
+        assert_eq!(c.tag.as_deref(), Some("latest"));
     }
 
     #[test]
     fn test_custom_tag_without_registry_or_namespace() {
         let c = comp("nginx:1.21.0");
         assert_eq!(c.registry, "docker.io");
-        assert_eq!(c.namespace, None);
+        assert_eq!(c.namespace, Some("library".to_string()));
         assert_eq!(c.name, "nginx");
-        assert_eq!(c.tag, "1.21.0");
+        assert_eq!(c.tag.as_deref(), Some("1.21.0"));
     }
 
     #[test]
@@ -152,7 +402,7 @@ mod tests {
         assert_eq!(c.registry, "docker.io");
         assert_eq!(c.namespace, Some("stratio".to_string()));
         assert_eq!(c.name, "nginx");
-        assert_eq!(c.tag, "1.2");
+        assert_eq!(c.tag.as_deref(), Some("1.2"));
     }
 
     #[test]
@@ -161,7 +411,7 @@ mod tests {
         assert_eq!(c.registry, "ghcr.io");
         assert_eq!(c.namespace, Some("stratio".to_string()));
         assert_eq!(c.name, "nginx");
-        assert_eq!(c.tag, "v2.0");
+        assert_eq!(c.tag.as_deref(), Some("v2.0"));
     }
 
     #[test]
@@ -170,7 +420,7 @@ mod tests {
         assert_eq!(c.registry, "ghcr.io");
         assert_eq!(c.namespace, Some("team/a/b".to_string()));
         assert_eq!(c.name, "c");
-        assert_eq!(c.tag, "v4");
+        assert_eq!(c.tag.as_deref(), Some("v4"));
     }
 
     #[test]
@@ -191,4 +441,215 @@ mod tests {
         let err = ComponentId::from_str(":").unwrap_err();
         assert!(matches!(err, ComponentIdError::InvalidFormat(_)));
     }
+
+    const SHA256: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_with_tag_and_digest() {
+        let c = comp(&format!("ghcr.io/stratio/nginx:v2.0@{SHA256}"));
+        assert_eq!(c.registry, "ghcr.io");
+        assert_eq!(c.namespace, Some("stratio".to_string()));
+        assert_eq!(c.name, "nginx");
+        assert_eq!(c.tag(), Some("v2.0"));
+        let digest = c.digest().unwrap();
+        assert_eq!(digest.algorithm(), DigestAlgorithm::Sha256);
+        assert_eq!(digest.to_string(), SHA256);
+    }
+
+    #[test]
+    fn test_with_digest_only_has_no_tag() {
+        let c = comp(&format!("nginx@{SHA256}"));
+        assert_eq!(c.registry, "docker.io");
+        assert_eq!(c.name, "nginx");
+        assert_eq!(c.tag(), None);
+        assert!(c.digest().is_some());
+        assert_eq!(c.to_string(), format!("docker.io/library/nginx@{SHA256}"));
+    }
+
+    #[test]
+    fn test_with_registry_port_and_digest() {
+        let c = comp(&format!("localhost:5000/team/app@{SHA256}"));
+        assert_eq!(c.registry, "localhost:5000");
+        assert_eq!(c.namespace, Some("team".to_string()));
+        assert_eq!(c.name, "app");
+        assert_eq!(c.tag(), None);
+    }
+
+    #[test]
+    fn test_digest_display_roundtrip() {
+        let input = format!("ghcr.io/stratio/nginx:v2.0@{SHA256}");
+        assert_eq!(comp(&input).to_string(), input);
+    }
+
+    #[test]
+    fn test_digest_compute() {
+        let digest = Digest::compute(DigestAlgorithm::Sha256, b"abc");
+        assert_eq!(
+            digest.to_string(),
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(Digest::from_str(&digest.to_string()).unwrap(), digest);
+
+        let digest = Digest::compute(DigestAlgorithm::Sha512, b"abc");
+        assert_eq!(digest.hex().len(), DigestAlgorithm::Sha512.hex_len());
+    }
+
+    #[test]
+    fn test_digest_is_part_of_identity() {
+        use std::collections::HashSet;
+
+        let tagged = comp("ghcr.io/stratio/nginx:v2.0");
+        let pinned = comp(&format!("ghcr.io/stratio/nginx:v2.0@{SHA256}"));
+        let other = comp(&format!(
+            "ghcr.io/stratio/nginx:v2.0@sha256:{}",
+            "f".repeat(64)
+        ));
+        assert_ne!(tagged, pinned);
+        assert_ne!(pinned, other);
+
+        let set: HashSet<_> = [tagged, pinned.clone(), other, pinned]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_same_repository_ignores_tag_and_digest() {
+        let v1 = ComponentId::from_str("nginx:1.25").unwrap();
+        let v2 = ComponentId::from_str(&format!(
+            "docker.io/library/nginx@sha256:{}",
+            "a".repeat(64)
+        ))
+        .unwrap();
+        let other = ComponentId::from_str("docker.io/bitnami/nginx:1.25").unwrap();
+
+        assert!(v1.same_repository(&v2));
+        assert!(!v1.same_repository(&other));
+    }
+
+    #[test]
+    fn test_invalid_digest_algorithm() {
+        let err = ComponentId::from_str(&format!("nginx@md5:{}", "a".repeat(32))).unwrap_err();
+        assert!(matches!(err, ComponentIdError::UnsupportedDigestAlgorithm(a) if a == "md5"));
+    }
+
+    #[test]
+    fn test_invalid_digest_length_or_encoding() {
+        let inputs = [
+            "nginx@sha256:abc".to_string(),
+            format!("nginx@sha256:{}", "A".repeat(64)),
+            format!("nginx@sha512:{}", "a".repeat(64)),
+            "nginx@sha256".to_string(),
+        ];
+        for input in inputs {
+            let err = ComponentId::from_str(&input).unwrap_err();
+            assert!(matches!(err, ComponentIdError::InvalidDigest(_)), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_docker_hub_spellings_are_canonicalized() {
+        let canonical = comp("docker.io/library/nginx:1.25");
+        for input in [
+            "nginx:1.25",
+            "library/nginx:1.25",
+            "docker.io/nginx:1.25",
+            "index.docker.io/library/nginx:1.25",
+            "Docker.IO/library/nginx:1.25",
+            "INDEX.DOCKER.IO/nginx:1.25",
+        ] {
+            assert_eq!(comp(input), canonical, "{input}");
+        }
+        assert_eq!(canonical.to_string(), "docker.io/library/nginx:1.25");
+    }
+
+    #[test]
+    fn test_registry_is_lowercased_but_not_aliased_elsewhere() {
+        let c = comp("GHCR.io/stratio/nginx:v2.0");
+        assert_eq!(c.registry, "ghcr.io");
+        let c = comp("registry.test/nginx:v2.0");
+        assert_eq!(c.namespace, None);
+    }
+
+    #[test]
+    fn test_registry_with_port_and_ipv6() {
+        let c = comp("Registry.Local:5000/team/app:1.0");
+        assert_eq!(c.registry, "registry.local:5000");
+        let c = comp("[::1]:5000/app:1.0");
+        assert_eq!(c.registry, "[::1]:5000");
+        assert_eq!(c.name, "app");
+    }
+
+    #[test]
+    fn test_valid_path_component_separators() {
+        for input in ["a.b/c_d/e__f/g-h/i---j:1", "ghcr.io/x1/y2z:v"] {
+            assert!(ComponentId::from_str(input).is_ok(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_references_have_typed_errors() {
+        let long = format!("{}:v1", "a".repeat(256));
+        let long_tag = format!("nginx:{}", "a".repeat(129));
+        let empty = String::new;
+        let cases = [
+            (
+                "Nginx:1.0",
+                ComponentIdError::UppercasePathComponent(empty()),
+            ),
+            (
+                "stratio/Nginx:1.0",
+                ComponentIdError::UppercasePathComponent(empty()),
+            ),
+            ("ghcr.io//nginx:1.0", ComponentIdError::EmptyPathComponent),
+            ("ghcr.io/stratio/:1.0", ComponentIdError::EmptyPathComponent),
+            (
+                "ghcr.io/stra tio/nginx:1.0",
+                ComponentIdError::InvalidPathComponent(empty()),
+            ),
+            (
+                "ghcr.io/-stratio/nginx:1.0",
+                ComponentIdError::InvalidPathComponent(empty()),
+            ),
+            (
+                "ghcr.io/a..b/nginx:1.0",
+                ComponentIdError::InvalidPathComponent(empty()),
+            ),
+            (
+                "ghcr.io/a___b/nginx:1.0",
+                ComponentIdError::InvalidPathComponent(empty()),
+            ),
+            (
+                "gh_cr.io/nginx:1.0",
+                ComponentIdError::InvalidRegistry(empty()),
+            ),
+            (
+                "-ghcr.io/nginx:1.0",
+                ComponentIdError::InvalidRegistry(empty()),
+            ),
+            (
+                "ghcr.io:http/nginx:1.0",
+                ComponentIdError::InvalidPort(empty()),
+            ),
+            (
+                "ghcr.io:70000/nginx:1.0",
+                ComponentIdError::InvalidPort(empty()),
+            ),
+            ("nginx:-1.0", ComponentIdError::InvalidTag(empty())),
+            ("nginx:1.0+build", ComponentIdError::InvalidTag(empty())),
+        ];
+        for (input, expected) in cases {
+            let err = ComponentId::from_str(input).unwrap_err();
+            assert_eq!(
+                std::mem::discriminant(&err),
+                std::mem::discriminant(&expected),
+                "{input}: {err:?}"
+            );
+        }
+
+        let err = ComponentId::from_str(&long).unwrap_err();
+        assert!(matches!(err, ComponentIdError::NameTooLong(256)));
+        let err = ComponentId::from_str(&long_tag).unwrap_err();
+        assert!(matches!(err, ComponentIdError::InvalidTag(_)));
+    }
 }
@@ -0,0 +1,443 @@
+use crate::domain::component::purl::PackageUrl;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use thiserror::Error;
+
+/// Hash algorithm of a package checksum, as named by SBOM specifications.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Blake2b256,
+    Blake2b384,
+    Blake2b512,
+    Blake3,
+    /// Any algorithm not listed above, kept as spelled in the document.
+    Other(String),
+}
+
+impl HashAlgorithm {
+    /// Recognize an algorithm name, ignoring case and separators (`SHA-256`, `SHA256`, `sha_256`).
+    #[must_use]
+    pub fn parse(name: &str) -> Self {
+        let key: String = name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        match key.as_str() {
+            "MD5" => Self::Md5,
+            "SHA1" => Self::Sha1,
+            "SHA256" => Self::Sha256,
+            "SHA384" => Self::Sha384,
+            "SHA512" => Self::Sha512,
+            "SHA3256" => Self::Sha3_256,
+            "SHA3384" => Self::Sha3_384,
+            "SHA3512" => Self::Sha3_512,
+            "BLAKE2B256" => Self::Blake2b256,
+            "BLAKE2B384" => Self::Blake2b384,
+            "BLAKE2B512" => Self::Blake2b512,
+            "BLAKE3" => Self::Blake3,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+            Self::Sha3_256 => "SHA3-256",
+            Self::Sha3_384 => "SHA3-384",
+            Self::Sha3_512 => "SHA3-512",
+            Self::Blake2b256 => "BLAKE2b-256",
+            Self::Blake2b384 => "BLAKE2b-384",
+            Self::Blake2b512 => "BLAKE2b-512",
+            Self::Blake3 => "BLAKE3",
+            Self::Other(name) => name,
+        };
+        f.write_str(s)
+    }
+}
+
+/// Checksum of a package artifact. The value is kept in lowercase hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Checksum {
+    algorithm: HashAlgorithm,
+    value: String,
+}
+
+impl Checksum {
+    #[must_use]
+    pub fn new(algorithm: HashAlgorithm, value: &str) -> Self {
+        Self {
+            algorithm,
+            value: value.trim().to_ascii_lowercase(),
+        }
+    }
+
+    #[must_use]
+    pub const fn algorithm(&self) -> &HashAlgorithm {
+        &self.algorithm
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm, self.value)
+    }
+}
+
+/// A package listed in an SBOM.
+///
+/// Packages are identified within their inventory by a reference, such as the CycloneDX
+/// `bom-ref` or the SPDX identifier, which dependency edges point to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    reference: String,
+    name: String,
+    version: Option<String>,
+    purl: Option<PackageUrl>,
+    cpes: BTreeSet<String>,
+    licenses: BTreeSet<String>,
+    hashes: BTreeSet<Checksum>,
+}
+
+impl Package {
+    #[must_use]
+    pub fn new(reference: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            reference: reference.into(),
+            name: name.into(),
+            version: None,
+            purl: None,
+            cpes: BTreeSet::new(),
+            licenses: BTreeSet::new(),
+            hashes: BTreeSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_purl(mut self, purl: PackageUrl) -> Self {
+        self.purl = Some(purl);
+        self
+    }
+
+    #[must_use]
+    pub fn with_cpes<I: IntoIterator<Item: Into<String>>>(mut self, cpes: I) -> Self {
+        self.cpes.extend(cpes.into_iter().map(Into::into));
+        self
+    }
+
+    #[must_use]
+    pub fn with_licenses<I: IntoIterator<Item: Into<String>>>(mut self, licenses: I) -> Self {
+        self.licenses.extend(licenses.into_iter().map(Into::into));
+        self
+    }
+
+    #[must_use]
+    pub fn with_hashes(mut self, hashes: impl IntoIterator<Item = Checksum>) -> Self {
+        self.hashes.extend(hashes);
+        self
+    }
+
+    #[must_use]
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Identity of the package across SBOMs and ecosystems, when the document provides it.
+    #[must_use]
+    pub const fn purl(&self) -> Option<&PackageUrl> {
+        self.purl.as_ref()
+    }
+
+    #[must_use]
+    pub const fn cpes(&self) -> &BTreeSet<String> {
+        &self.cpes
+    }
+
+    #[must_use]
+    pub const fn licenses(&self) -> &BTreeSet<String> {
+        &self.licenses
+    }
+
+    #[must_use]
+    pub const fn hashes(&self) -> &BTreeSet<Checksum> {
+        &self.hashes
+    }
+}
+
+/// Directed dependency edge: the package referenced by `from` depends on the one referenced by `to`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Dependency {
+    from: String,
+    to: String,
+}
+
+impl Dependency {
+    #[must_use]
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    #[must_use]
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+}
+
+/// Contents of an SBOM: the packages of a component and the dependencies between them.
+///
+/// The subject is the component the SBOM describes (e.g. the image itself). It may take part
+/// in dependency edges but is not one of the packages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageInventory {
+    subject: Option<Package>,
+    packages: BTreeMap<String, Package>,
+    dependencies: BTreeSet<Dependency>,
+}
+
+impl PackageInventory {
+    /// Build an inventory, checking that references are unique and that edges are not dangling.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PackageInventoryError::DuplicateReference`] if two packages share a reference,
+    /// or [`PackageInventoryError::UnknownReference`] if a dependency points to no package.
+    pub fn new(
+        subject: Option<Package>,
+        packages: impl IntoIterator<Item = Package>,
+        dependencies: impl IntoIterator<Item = Dependency>,
+    ) -> Result<Self, PackageInventoryError> {
+        let mut by_reference = BTreeMap::new();
+        for package in packages {
+            let reference = package.reference().to_string();
+            let duplicate = subject.as_ref().is_some_and(|s| s.reference() == reference)
+                || by_reference.contains_key(&reference);
+            if duplicate {
+                return Err(PackageInventoryError::DuplicateReference(reference));
+            }
+            by_reference.insert(reference, package);
+        }
+
+        let inventory = Self {
+            subject,
+            packages: by_reference,
+            dependencies: BTreeSet::new(),
+        };
+        let dependencies = dependencies
+            .into_iter()
+            .map(|dependency| {
+                [dependency.from(), dependency.to()]
+                    .into_iter()
+                    .find(|reference| !inventory.contains(reference))
+                    .map_or(Ok(dependency.clone()), |reference| {
+                        Err(PackageInventoryError::UnknownReference(
+                            reference.to_string(),
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            dependencies,
+            ..inventory
+        })
+    }
+
+    #[must_use]
+    pub const fn subject(&self) -> Option<&Package> {
+        self.subject.as_ref()
+    }
+
+    /// Packages ordered by reference.
+    pub fn packages(&self) -> impl Iterator<Item = &Package> {
+        self.packages.values()
+    }
+
+    #[must_use]
+    pub fn package(&self, reference: &str) -> Option<&Package> {
+        self.packages.get(reference)
+    }
+
+    /// Packages identified by the given purl, whatever their version, qualifiers or subpath.
+    pub fn packages_of<'a>(
+        &'a self,
+        purl: &PackageUrl,
+    ) -> impl Iterator<Item = &'a Package> + use<'a> {
+        let purl = purl.clone();
+        self.packages().filter(move |p| {
+            p.purl()
+                .is_some_and(|candidate| candidate.same_package(&purl))
+        })
+    }
+
+    #[must_use]
+    pub const fn dependencies(&self) -> &BTreeSet<Dependency> {
+        &self.dependencies
+    }
+
+    /// Direct dependencies of the package or subject with the given reference.
+    pub fn dependencies_of<'a>(&'a self, reference: &'a str) -> impl Iterator<Item = &'a str> {
+        self.dependencies
+            .iter()
+            .filter(move |d| d.from() == reference)
+            .map(Dependency::to)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.packages.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    fn contains(&self, reference: &str) -> bool {
+        self.packages.contains_key(reference)
+            || self
+                .subject
+                .as_ref()
+                .is_some_and(|s| s.reference() == reference)
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PackageInventoryError {
+    #[error("Package reference `{0}` is used more than once")]
+    DuplicateReference(String),
+
+    #[error("Dependency refers to unknown package `{0}`")]
+    UnknownReference(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(reference: &str) -> Package {
+        Package::new(reference, reference).with_version("1.0.0")
+    }
+
+    #[test]
+    fn inventory_should_index_packages_and_dependencies() {
+        let inventory = PackageInventory::new(
+            Some(Package::new("image", "registry.test/app")),
+            [package("openssl"), package("zlib"), package("curl")],
+            [
+                Dependency::new("image", "curl"),
+                Dependency::new("curl", "openssl"),
+                Dependency::new("curl", "zlib"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(inventory.len(), 3);
+        assert_eq!(inventory.subject().unwrap().name(), "registry.test/app");
+        assert_eq!(inventory.package("zlib").unwrap().version(), Some("1.0.0"));
+        assert_eq!(
+            inventory.dependencies_of("curl").collect::<Vec<_>>(),
+            vec!["openssl", "zlib"]
+        );
+        assert_eq!(inventory.dependencies_of("zlib").count(), 0);
+    }
+
+    #[test]
+    fn inventory_should_reject_duplicates_and_dangling_edges() {
+        let err = PackageInventory::new(None, [package("a"), package("a")], []).unwrap_err();
+        assert_eq!(
+            err,
+            PackageInventoryError::DuplicateReference("a".to_string())
+        );
+
+        let err =
+            PackageInventory::new(None, [package("a")], [Dependency::new("a", "b")]).unwrap_err();
+        assert_eq!(
+            err,
+            PackageInventoryError::UnknownReference("b".to_string())
+        );
+    }
+
+    #[test]
+    fn packages_should_be_found_by_purl_whatever_their_version() {
+        let purl = |s: &str| s.parse::<PackageUrl>().unwrap();
+        let inventory = PackageInventory::new(
+            None,
+            [
+                package("openssl-3.0").with_purl(purl("pkg:deb/debian/openssl@3.0.11")),
+                package("openssl-1.1").with_purl(purl("pkg:deb/debian/openssl@1.1.1w")),
+                package("zlib").with_purl(purl("pkg:deb/debian/zlib1g@1.2.13")),
+                package("curl"),
+            ],
+            [],
+        )
+        .unwrap();
+
+        let found: Vec<_> = inventory
+            .packages_of(&purl("pkg:deb/Debian/OpenSSL"))
+            .map(Package::reference)
+            .collect();
+        assert_eq!(found, vec!["openssl-1.1", "openssl-3.0"]);
+    }
+
+    #[test]
+    fn hash_algorithms_should_be_recognized_across_spellings() {
+        assert_eq!(HashAlgorithm::parse("SHA-256"), HashAlgorithm::Sha256);
+        assert_eq!(HashAlgorithm::parse("sha256"), HashAlgorithm::Sha256);
+        assert_eq!(HashAlgorithm::parse("SHA3-512"), HashAlgorithm::Sha3_512);
+        assert_eq!(
+            HashAlgorithm::parse("BLAKE2b-256"),
+            HashAlgorithm::Blake2b256
+        );
+        assert_eq!(
+            HashAlgorithm::parse("ADLER32"),
+            HashAlgorithm::Other("ADLER32".to_string())
+        );
+        assert_eq!(
+            Checksum::new(HashAlgorithm::Sha1, " ABCDEF ").to_string(),
+            "SHA-1:abcdef"
+        );
+    }
+}
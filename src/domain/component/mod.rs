@@ -1,12 +1,16 @@
 pub mod context;
 pub mod event;
 pub mod id;
+pub mod inventory;
+pub mod purl;
 pub mod sbom;
+pub mod state;
 
 use crate::domain::component::context::ExecutionContext;
 use crate::domain::component::event::ComponentEvent;
 use crate::domain::component::id::ComponentId;
 use crate::domain::component::sbom::Sbom;
+use crate::domain::component::state::ComponentState;
 use crate::domain::shared::aggregate::EventSourcedAggregate;
 use std::convert::TryFrom;
 use thiserror::Error;
@@ -14,9 +18,7 @@ use thiserror::Error;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Component {
     id: ComponentId,
-    sbom: Option<Sbom>,
-    context: Option<ExecutionContext>,
-    deprecated: bool,
+    state: ComponentState,
 }
 
 impl Component {
@@ -24,9 +26,7 @@ impl Component {
     pub const fn new(id: ComponentId) -> Self {
         Self {
             id,
-            sbom: None,
-            context: None,
-            deprecated: false,
+            state: ComponentState::Registered,
         }
     }
 
@@ -44,72 +44,185 @@ impl Component {
     ///
     /// Returns [`ComponentError::AlreadyDeprecated`] if the component has already been deprecated.
     pub fn deprecate(&self) -> Result<ComponentEvent, ComponentError> {
-        if self.deprecated {
-            Err(ComponentError::AlreadyDeprecated(self.id.clone()))
-        } else {
-            Ok(ComponentEvent::ComponentDeprecated {
-                component_id: self.id.clone(),
-            })
-        }
+        self.validated(ComponentEvent::ComponentDeprecated {
+            component_id: self.id.clone(),
+        })
     }
 
     /// Emit an event to assign the initial SBOM to the component.
     ///
-    /// This method ensures that an SBOM can only be assigned once.
+    /// This method ensures that an SBOM can only be assigned once, and only with a digest
+    /// of its contents so that later reads can detect tampering.
     ///
     /// # Errors
     ///
-    /// Returns [`ComponentError::SbomAlreadyAssigned`] if the component already has an SBOM.
+    /// Returns [`ComponentError::SbomAlreadyAssigned`] if the component already has an SBOM,
+    /// [`ComponentError::SbomAssignmentOnDeprecated`] if the component is deprecated,
+    /// or [`ComponentError::SbomWithoutDigest`] if the SBOM carries no digest.
     pub fn assign_sbom(&self, sbom: Sbom) -> Result<ComponentEvent, ComponentError> {
-        if self.sbom.is_some() {
-            Err(ComponentError::SbomAlreadyAssigned(self.id.clone()))
-        } else {
-            Ok(ComponentEvent::SbomAssigned {
-                component_id: self.id.clone(),
-                sbom,
-            })
-        }
+        self.validated(ComponentEvent::SbomAssigned {
+            component_id: self.id.clone(),
+            sbom,
+        })
+    }
+
+    /// Emit an event recording that the SBOM of the component could not be generated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ComponentError::SbomAlreadyAssigned`] if the component already has an SBOM,
+    /// or [`ComponentError::SbomGenerationOnDeprecated`] if the component is deprecated.
+    pub fn fail_sbom_generation(
+        &self,
+        reason: impl Into<String>,
+        attempts: u32,
+    ) -> Result<ComponentEvent, ComponentError> {
+        self.validated(ComponentEvent::SbomGenerationFailed {
+            component_id: self.id.clone(),
+            reason: reason.into(),
+            attempts,
+        })
+    }
+
+    /// Emit an event requesting the SBOM of the component to be generated again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ComponentError::SbomAlreadyAssigned`] if the component already has an SBOM,
+    /// or [`ComponentError::SbomGenerationOnDeprecated`] if the component is deprecated.
+    pub fn request_sbom_generation(&self) -> Result<ComponentEvent, ComponentError> {
+        self.validated(ComponentEvent::SbomGenerationRequested {
+            component_id: self.id.clone(),
+        })
     }
 
     /// Emit an event to assign an initial execution context to the component.
     ///
-    /// This method fails if the component already has an execution context assigned.
+    /// This method fails if the component has no SBOM yet or already has an execution context assigned.
     ///
     /// # Errors
     ///
-    /// Returns [`ComponentError::ExecutionContextAlreadyAssigned`] if a context has already been assigned.
+    /// Returns [`ComponentError::SbomNotAssigned`] if no SBOM was assigned yet,
+    /// [`ComponentError::ExecutionContextAlreadyAssigned`] if a context has already been assigned,
+    /// or [`ComponentError::ExecutionContextAssignmentOnDeprecated`] if the component is deprecated.
     pub fn assign_execution_context(
         &self,
         context: ExecutionContext,
     ) -> Result<ComponentEvent, ComponentError> {
-        if self.context.is_some() {
-            Err(ComponentError::ExecutionContextAlreadyAssigned(
-                self.id.clone(),
-            ))
-        } else {
-            Ok(ComponentEvent::ExecutionContextAssigned {
-                component_id: self.id.clone(),
-                context,
-            })
-        }
+        self.validated(ComponentEvent::ExecutionContextAssigned {
+            component_id: self.id.clone(),
+            context,
+        })
     }
 
     /// Emit an event to replace the current execution context of the component.
     ///
     /// # Errors
     ///
-    /// Returns [`ComponentError::ExecutionContextNotAssigned`] if no execution context was assigned yet.
+    /// Returns [`ComponentError::ExecutionContextNotAssigned`] if no execution context was assigned yet,
+    /// [`ComponentError::ExecutionContextUnchanged`] if the new context equals the current one,
+    /// or [`ComponentError::ExecutionContextReplacementOnDeprecated`] if the component is deprecated.
     pub fn replace_execution_context(
         &self,
         context: ExecutionContext,
     ) -> Result<ComponentEvent, ComponentError> {
-        if self.context.is_none() {
-            Err(ComponentError::ExecutionContextNotAssigned(self.id.clone()))
-        } else {
-            Ok(ComponentEvent::ExecutionContextReplaced {
-                component_id: self.id.clone(),
-                context,
-            })
+        self.validated(ComponentEvent::ExecutionContextReplaced {
+            component_id: self.id.clone(),
+            context,
+        })
+    }
+
+    /// Return the event if the current state accepts it.
+    fn validated(&self, event: ComponentEvent) -> Result<ComponentEvent, ComponentError> {
+        self.transition(&event)?;
+        Ok(event)
+    }
+
+    /// Compute the state reached by applying `event`, rejecting illegal transitions.
+    ///
+    /// Commands and [`EventSourcedAggregate::apply`] share this single source of truth.
+    fn transition(&self, event: &ComponentEvent) -> Result<ComponentState, ComponentError> {
+        use ComponentState::{Deprecated, Registered, WithExecutionContext, WithSbom};
+
+        let id = || Box::new(self.id.clone());
+        match (event, &self.state) {
+            (ComponentEvent::ComponentRegistered { .. }, _) => {
+                Err(ComponentError::RegisteredEventNotAllowed)
+            }
+
+            (ComponentEvent::ComponentDeprecated { .. }, Deprecated { .. }) => {
+                Err(ComponentError::AlreadyDeprecated(id()))
+            }
+            (ComponentEvent::ComponentDeprecated { .. }, state) => Ok(Deprecated {
+                sbom: state.sbom().cloned(),
+                context: state.context().cloned(),
+            }),
+
+            (ComponentEvent::SbomAssigned { sbom, .. }, Registered) => match sbom.digest() {
+                Some(_) => Ok(WithSbom(sbom.clone())),
+                None => Err(ComponentError::SbomWithoutDigest(id())),
+            },
+            (ComponentEvent::SbomAssigned { .. }, WithSbom(_) | WithExecutionContext { .. }) => {
+                Err(ComponentError::SbomAlreadyAssigned(id()))
+            }
+            (ComponentEvent::SbomAssigned { .. }, Deprecated { .. }) => {
+                Err(ComponentError::SbomAssignmentOnDeprecated(id()))
+            }
+
+            (
+                ComponentEvent::SbomGenerationFailed { .. }
+                | ComponentEvent::SbomGenerationRequested { .. },
+                Registered,
+            ) => Ok(Registered),
+            (
+                ComponentEvent::SbomGenerationFailed { .. }
+                | ComponentEvent::SbomGenerationRequested { .. },
+                WithSbom(_) | WithExecutionContext { .. },
+            ) => Err(ComponentError::SbomAlreadyAssigned(id())),
+            (
+                ComponentEvent::SbomGenerationFailed { .. }
+                | ComponentEvent::SbomGenerationRequested { .. },
+                Deprecated { .. },
+            ) => Err(ComponentError::SbomGenerationOnDeprecated(id())),
+
+            (ComponentEvent::ExecutionContextAssigned { .. }, Registered) => {
+                Err(ComponentError::SbomNotAssigned(id()))
+            }
+            (ComponentEvent::ExecutionContextAssigned { context, .. }, WithSbom(sbom)) => {
+                Ok(WithExecutionContext {
+                    sbom: sbom.clone(),
+                    context: context.clone(),
+                })
+            }
+            (ComponentEvent::ExecutionContextAssigned { .. }, WithExecutionContext { .. }) => {
+                Err(ComponentError::ExecutionContextAlreadyAssigned(id()))
+            }
+            (ComponentEvent::ExecutionContextAssigned { .. }, Deprecated { .. }) => {
+                Err(ComponentError::ExecutionContextAssignmentOnDeprecated(id()))
+            }
+
+            (ComponentEvent::ExecutionContextReplaced { .. }, Registered | WithSbom(_)) => {
+                Err(ComponentError::ExecutionContextNotAssigned(id()))
+            }
+            (
+                ComponentEvent::ExecutionContextReplaced { context, .. },
+                WithExecutionContext {
+                    sbom,
+                    context: current,
+                },
+            ) => {
+                if current == context {
+                    Err(ComponentError::ExecutionContextUnchanged(id()))
+                } else {
+                    Ok(WithExecutionContext {
+                        sbom: sbom.clone(),
+                        context: context.clone(),
+                    })
+                }
+            }
+            (ComponentEvent::ExecutionContextReplaced { .. }, Deprecated { .. }) => {
+                Err(ComponentError::ExecutionContextReplacementOnDeprecated(id()))
+            }
         }
     }
 
@@ -120,19 +233,24 @@ impl Component {
         &self.id
     }
 
+    #[must_use]
+    pub const fn state(&self) -> &ComponentState {
+        &self.state
+    }
+
     #[must_use]
     pub const fn sbom(&self) -> Option<&Sbom> {
-        self.sbom.as_ref()
+        self.state.sbom()
     }
 
     #[must_use]
     pub const fn context(&self) -> Option<&ExecutionContext> {
-        self.context.as_ref()
+        self.state.context()
     }
 
     #[must_use]
     pub const fn is_deprecated(&self) -> bool {
-        self.deprecated
+        self.state.is_deprecated()
     }
 }
 
@@ -155,24 +273,15 @@ impl EventSourcedAggregate<ComponentEvent, ComponentError> for Component {
     }
 
     fn apply(&mut self, event: &ComponentEvent) -> Result<(), ComponentError> {
-        match event {
-            ComponentEvent::ComponentRegistered { .. } => {
-                Err(ComponentError::RegisteredEventNotAllowed)
-            }
-            ComponentEvent::ComponentDeprecated { .. } => {
-                self.deprecated = true;
-                Ok(())
-            }
-            ComponentEvent::SbomAssigned { sbom, .. } => {
-                self.sbom = Some(sbom.clone());
-                Ok(())
-            }
-            ComponentEvent::ExecutionContextAssigned { context, .. }
-            | ComponentEvent::ExecutionContextReplaced { context, .. } => {
-                self.context = Some(context.clone());
-                Ok(())
-            }
+        if event.component_id() != &self.id {
+            return Err(ComponentError::InconsistentIds(
+                event.component_id().to_string(),
+                self.id.to_string(),
+            ));
         }
+
+        self.state = self.transition(event)?;
+        Ok(())
     }
 
     fn invalid_initial_event() -> ComponentError {
@@ -183,19 +292,43 @@ impl EventSourcedAggregate<ComponentEvent, ComponentError> for Component {
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ComponentError {
     #[error("Component `{0}` is already registered")]
-    AlreadyRegistered(ComponentId),
+    AlreadyRegistered(Box<ComponentId>),
+
+    #[error("Component `{0}` is not registered")]
+    NotRegistered(Box<ComponentId>),
 
     #[error("Component `{0}` is already deprecated")]
-    AlreadyDeprecated(ComponentId),
+    AlreadyDeprecated(Box<ComponentId>),
 
     #[error("Component `{0}` already has an SBOM assigned")]
-    SbomAlreadyAssigned(ComponentId),
+    SbomAlreadyAssigned(Box<ComponentId>),
+
+    #[error("Component `{0}` has no SBOM assigned")]
+    SbomNotAssigned(Box<ComponentId>),
+
+    #[error("Component `{0}` is deprecated and cannot receive an SBOM")]
+    SbomAssignmentOnDeprecated(Box<ComponentId>),
+
+    #[error("Component `{0}` cannot receive an SBOM without a content digest")]
+    SbomWithoutDigest(Box<ComponentId>),
+
+    #[error("Component `{0}` is deprecated and no longer needs an SBOM")]
+    SbomGenerationOnDeprecated(Box<ComponentId>),
 
     #[error("Component `{0}` has no execution context assigned")]
-    ExecutionContextNotAssigned(ComponentId),
+    ExecutionContextNotAssigned(Box<ComponentId>),
 
     #[error("Component `{0}` already has an execution context assigned")]
-    ExecutionContextAlreadyAssigned(ComponentId),
+    ExecutionContextAlreadyAssigned(Box<ComponentId>),
+
+    #[error("Component `{0}` already has an identical execution context")]
+    ExecutionContextUnchanged(Box<ComponentId>),
+
+    #[error("Component `{0}` is deprecated and cannot receive an execution context")]
+    ExecutionContextAssignmentOnDeprecated(Box<ComponentId>),
+
+    #[error("Component `{0}` is deprecated and cannot have its execution context replaced")]
+    ExecutionContextReplacementOnDeprecated(Box<ComponentId>),
 
     #[error("Event `ComponentRegistered` cannot be applied to an existing aggregate")]
     RegisteredEventNotAllowed,
@@ -210,7 +343,9 @@ pub enum ComponentError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::component::context::ExecutionContext;
+    use crate::domain::component::context::{
+        DataSensitivity, ExecutionContext, Exposure, Isolation,
+    };
     use crate::domain::component::event::ComponentEvent;
     use crate::domain::component::id::ComponentId;
     use crate::domain::component::sbom::Sbom;
@@ -222,11 +357,55 @@ mod tests {
     }
 
     fn dummy_sbom() -> Sbom {
-        Sbom::from_url_str("https://example.com/sbom.json").unwrap()
+        Sbom::from_url_str("https://example.com/sbom.json")
+            .unwrap()
+            .sealed(b"{}")
     }
 
     fn dummy_context() -> ExecutionContext {
-        ExecutionContext::None
+        ExecutionContext::new(
+            Exposure::Internal,
+            Isolation::Sandboxed,
+            DataSensitivity::Internal,
+            ["baseline"],
+            ["backend"],
+        )
+        .unwrap()
+    }
+
+    fn other_context() -> ExecutionContext {
+        ExecutionContext::new(
+            Exposure::InternetFacing,
+            Isolation::Privileged,
+            DataSensitivity::Confidential,
+            ["baseline", "pci-dss"],
+            ["backend"],
+        )
+        .unwrap()
+    }
+
+    fn registered() -> Component {
+        Component::from_initial_event(&Component::register(dummy_id())).unwrap()
+    }
+
+    fn with_sbom() -> Component {
+        let mut component = registered();
+        let event = component.assign_sbom(dummy_sbom()).unwrap();
+        component.apply(&event).unwrap();
+        component
+    }
+
+    fn with_context() -> Component {
+        let mut component = with_sbom();
+        let event = component.assign_execution_context(dummy_context()).unwrap();
+        component.apply(&event).unwrap();
+        component
+    }
+
+    fn deprecated(mut component: Component) -> Component {
+        let event = component.deprecate().unwrap();
+        component.apply(&event).unwrap();
+        component
     }
 
     #[test]
@@ -268,11 +447,43 @@ mod tests {
         assert!(matches!(err, ComponentError::SbomAlreadyAssigned(_)));
     }
 
+    #[test]
+    fn sbom_generation_failures_and_requests_should_wait_for_an_sbom() {
+        let mut component = registered();
+        let failed = component.fail_sbom_generation("syft exited", 3).unwrap();
+        component.apply(&failed).unwrap();
+        let requested = component.request_sbom_generation().unwrap();
+        component.apply(&requested).unwrap();
+        assert_eq!(component.state(), &ComponentState::Registered);
+
+        let event = component.assign_sbom(dummy_sbom()).unwrap();
+        component.apply(&event).unwrap();
+        let err = component.request_sbom_generation().unwrap_err();
+        assert_eq!(
+            err,
+            ComponentError::SbomAlreadyAssigned(Box::new(dummy_id()))
+        );
+
+        let err = deprecated(registered())
+            .fail_sbom_generation("syft exited", 3)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ComponentError::SbomGenerationOnDeprecated(Box::new(dummy_id()))
+        );
+    }
+
+    #[test]
+    fn assign_sbom_without_digest_should_fail() {
+        let sbom = Sbom::from_url_str("https://example.com/sbom.json").unwrap();
+
+        let err = registered().assign_sbom(sbom).unwrap_err();
+        assert_eq!(err, ComponentError::SbomWithoutDigest(Box::new(dummy_id())));
+    }
+
     #[test]
     fn assign_and_replace_execution_context() {
-        let id = dummy_id();
-        let register = Component::register(id.clone());
-        let mut component = Component::from_initial_event(&register).unwrap();
+        let mut component = with_sbom();
 
         let ctx1 = dummy_context();
         let event1 = component.assign_execution_context(ctx1.clone()).unwrap();
@@ -280,7 +491,7 @@ mod tests {
 
         assert_eq!(component.context(), Some(&ctx1));
 
-        let ctx2 = ExecutionContext::None;
+        let ctx2 = other_context();
         let event2 = component.replace_execution_context(ctx2.clone()).unwrap();
         component.apply(&event2).unwrap();
 
@@ -288,10 +499,30 @@ mod tests {
     }
 
     #[test]
-    fn assign_context_twice_should_fail() {
+    fn replace_context_with_identical_context_should_fail() {
         let id = dummy_id();
-        let register = Component::register(id.clone());
-        let mut component = Component::from_initial_event(&register).unwrap();
+        let mut component = with_sbom();
+
+        let err = component
+            .replace_execution_context(dummy_context())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ComponentError::ExecutionContextNotAssigned(Box::new(id.clone()))
+        );
+
+        let event = component.assign_execution_context(dummy_context()).unwrap();
+        component.apply(&event).unwrap();
+
+        let err = component
+            .replace_execution_context(dummy_context())
+            .unwrap_err();
+        assert_eq!(err, ComponentError::ExecutionContextUnchanged(Box::new(id)));
+    }
+
+    #[test]
+    fn assign_context_twice_should_fail() {
+        let mut component = with_sbom();
 
         let ctx = dummy_context();
         let e1 = component.assign_execution_context(ctx.clone()).unwrap();
@@ -328,7 +559,7 @@ mod tests {
         assert!(component.is_deprecated());
 
         let err = component.deprecate().unwrap_err();
-        assert_eq!(err, ComponentError::AlreadyDeprecated(id));
+        assert_eq!(err, ComponentError::AlreadyDeprecated(Box::new(id)));
     }
 
     #[test]
@@ -361,4 +592,163 @@ mod tests {
         assert_eq!(component.context(), Some(&ctx));
         assert!(component.is_deprecated());
     }
+
+    #[test]
+    fn states_should_follow_design_diagram() {
+        assert_eq!(registered().state(), &ComponentState::Registered);
+        assert_eq!(with_sbom().state(), &ComponentState::WithSbom(dummy_sbom()));
+        assert_eq!(
+            with_context().state(),
+            &ComponentState::WithExecutionContext {
+                sbom: dummy_sbom(),
+                context: dummy_context(),
+            }
+        );
+        assert_eq!(
+            deprecated(registered()).state(),
+            &ComponentState::Deprecated {
+                sbom: None,
+                context: None,
+            }
+        );
+        assert_eq!(
+            deprecated(with_sbom()).state(),
+            &ComponentState::Deprecated {
+                sbom: Some(dummy_sbom()),
+                context: None,
+            }
+        );
+        assert_eq!(
+            deprecated(with_context()).state(),
+            &ComponentState::Deprecated {
+                sbom: Some(dummy_sbom()),
+                context: Some(dummy_context()),
+            }
+        );
+    }
+
+    #[test]
+    fn every_command_should_be_validated_against_every_state() {
+        use ComponentError::*;
+
+        type Command = fn(&Component) -> Result<ComponentEvent, ComponentError>;
+        let commands: [(&str, Command); 4] = [
+            ("deprecate", Component::deprecate),
+            ("assign_sbom", |c| c.assign_sbom(dummy_sbom())),
+            ("assign_context", |c| {
+                c.assign_execution_context(dummy_context())
+            }),
+            ("replace_context", |c| {
+                c.replace_execution_context(other_context())
+            }),
+        ];
+
+        let id = Box::new(dummy_id());
+        let states = [
+            (
+                "registered",
+                registered(),
+                [
+                    None,
+                    None,
+                    Some(SbomNotAssigned(id.clone())),
+                    Some(ExecutionContextNotAssigned(id.clone())),
+                ],
+            ),
+            (
+                "with_sbom",
+                with_sbom(),
+                [
+                    None,
+                    Some(SbomAlreadyAssigned(id.clone())),
+                    None,
+                    Some(ExecutionContextNotAssigned(id.clone())),
+                ],
+            ),
+            (
+                "with_context",
+                with_context(),
+                [
+                    None,
+                    Some(SbomAlreadyAssigned(id.clone())),
+                    Some(ExecutionContextAlreadyAssigned(id.clone())),
+                    None,
+                ],
+            ),
+            (
+                "deprecated",
+                deprecated(with_context()),
+                [
+                    Some(AlreadyDeprecated(id.clone())),
+                    Some(SbomAssignmentOnDeprecated(id.clone())),
+                    Some(ExecutionContextAssignmentOnDeprecated(id.clone())),
+                    Some(ExecutionContextReplacementOnDeprecated(id.clone())),
+                ],
+            ),
+        ];
+
+        for (state, component, expected) in states {
+            for ((command, run), expected) in commands.iter().zip(expected) {
+                let result = run(&component);
+                match expected {
+                    None => {
+                        let event = result.unwrap_or_else(|e| panic!("{state}/{command}: {e}"));
+                        component.clone().apply(&event).unwrap();
+                    }
+                    Some(err) => assert_eq!(result.unwrap_err(), err, "{state}/{command}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn apply_should_reject_illegal_transitions_when_rehydrating() {
+        let id = dummy_id();
+        let register = ComponentEvent::ComponentRegistered {
+            component_id: id.clone(),
+        };
+        let deprecate = ComponentEvent::ComponentDeprecated {
+            component_id: id.clone(),
+        };
+        let sbom = ComponentEvent::SbomAssigned {
+            component_id: id.clone(),
+            sbom: dummy_sbom(),
+        };
+        let context = ComponentEvent::ExecutionContextAssigned {
+            component_id: id.clone(),
+            context: dummy_context(),
+        };
+
+        let err = Component::rehydrate(&[register.clone(), context.clone()]).unwrap_err();
+        assert_eq!(err, ComponentError::SbomNotAssigned(Box::new(id.clone())));
+
+        let err = Component::rehydrate(&[register.clone(), deprecate, sbom.clone()]).unwrap_err();
+        assert_eq!(
+            err,
+            ComponentError::SbomAssignmentOnDeprecated(Box::new(id.clone()))
+        );
+
+        let err = Component::rehydrate(&[register, sbom, context.clone(), context]).unwrap_err();
+        assert_eq!(
+            err,
+            ComponentError::ExecutionContextAlreadyAssigned(Box::new(id))
+        );
+    }
+
+    #[test]
+    fn apply_event_of_another_component_should_fail() {
+        let mut component = registered();
+        let other = ComponentId::from_str("registry.test/namespace/other:v0").unwrap();
+        let err = component
+            .apply(&ComponentEvent::ComponentDeprecated {
+                component_id: other.clone(),
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            ComponentError::InconsistentIds(other.to_string(), dummy_id().to_string())
+        );
+        assert!(!component.is_deprecated());
+    }
 }
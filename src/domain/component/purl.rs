@@ -0,0 +1,466 @@
+use std::collections::BTreeMap;
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+const SCHEME: &str = "pkg";
+/// Qualifier naming the distribution a `deb`, `rpm` or `apk` package was built for.
+const DISTRO_QUALIFIER: &str = "distro";
+/// Qualifiers whose values are case-insensitive for distribution packages.
+const DISTRO_CASE_INSENSITIVE_QUALIFIERS: [&str; 2] = [DISTRO_QUALIFIER, "arch"];
+
+/// Package URL (purl), identifying a package across ecosystems and SBOM formats.
+///
+/// Follows the [purl specification](https://github.com/package-url/purl-spec):
+/// `pkg:type/namespace/name@version?qualifiers#subpath`.
+///
+/// Values are normalized on parsing, so that equivalent purls compare equal and serialize
+/// the same: the type and qualifier keys are lowercased, qualifiers are sorted, and the rules
+/// of the package type are applied (e.g. `pkg:PyPI/Django_Rest` becomes `pkg:pypi/django-rest`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackageUrl {
+    package_type: String,
+    namespace: Option<String>,
+    name: String,
+    version: Option<String>,
+    qualifiers: BTreeMap<String, String>,
+    subpath: Option<String>,
+}
+
+impl PackageUrl {
+    /// Ecosystem of the package, such as `npm`, `pypi`, `maven` or `deb`.
+    #[must_use]
+    pub fn package_type(&self) -> &str {
+        &self.package_type
+    }
+
+    /// Type-specific name prefix, such as a Maven group, an npm scope or a distribution vendor.
+    #[must_use]
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Qualifiers ordered by key.
+    #[must_use]
+    pub const fn qualifiers(&self) -> &BTreeMap<String, String> {
+        &self.qualifiers
+    }
+
+    #[must_use]
+    pub fn qualifier(&self, key: &str) -> Option<&str> {
+        self.qualifiers.get(key).map(String::as_str)
+    }
+
+    /// Distribution a `deb`, `rpm` or `apk` package was built for (e.g. `debian-12`).
+    #[must_use]
+    pub fn distro(&self) -> Option<&str> {
+        self.qualifier(DISTRO_QUALIFIER)
+    }
+
+    /// Path inside the package, without leading or trailing slash.
+    #[must_use]
+    pub fn subpath(&self) -> Option<&str> {
+        self.subpath.as_deref()
+    }
+
+    /// The same package, whatever its version, qualifiers or subpath.
+    #[must_use]
+    pub fn same_package(&self, other: &Self) -> bool {
+        self.package_type == other.package_type
+            && self.namespace == other.namespace
+            && self.name == other.name
+    }
+
+    /// Apply the rules of the package type to the parsed components.
+    fn normalize(mut self) -> Self {
+        let lowercase = |s: &mut String| *s = s.to_lowercase();
+        match self.package_type.as_str() {
+            "pypi" => {
+                self.name = self.name.to_lowercase().replace('_', "-");
+            }
+            "npm" | "bitbucket" | "github" | "composer" | "hex" | "apk" | "deb" => {
+                self.namespace.as_mut().map(lowercase);
+                lowercase(&mut self.name);
+            }
+            "rpm" => {
+                self.namespace.as_mut().map(lowercase);
+            }
+            _ => {}
+        }
+
+        if matches!(self.package_type.as_str(), "deb" | "rpm" | "apk") {
+            for key in DISTRO_CASE_INSENSITIVE_QUALIFIERS {
+                self.qualifiers.get_mut(key).map(lowercase);
+            }
+        }
+        self
+    }
+
+    /// Check the constraints of the package type.
+    fn validate(self, purl: &str) -> Result<Self, PackageUrlError> {
+        let namespace_required = matches!(self.package_type.as_str(), "maven" | "swift");
+        if namespace_required && self.namespace.is_none() {
+            return Err(PackageUrlError::MissingNamespace(
+                purl.to_string(),
+                self.package_type,
+            ));
+        }
+        Ok(self)
+    }
+}
+
+impl FromStr for PackageUrl {
+    type Err = PackageUrlError;
+
+    /// Parse a purl following the parsing procedure of the specification.
+    ///
+    /// Components are percent-decoded, empty qualifiers are dropped, and `.`, `..` and empty
+    /// subpath segments are discarded.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| PackageUrlError::InvalidFormat(s.to_string(), reason.into());
+
+        let (rest, subpath) = match s.rsplit_once('#') {
+            Some((rest, subpath)) => (rest, decode_segments(subpath, s, true)?),
+            None => (s, None),
+        };
+
+        let (rest, qualifiers) = match rest.rsplit_once('?') {
+            Some((rest, qualifiers)) => (rest, parse_qualifiers(qualifiers, s)?),
+            None => (rest, BTreeMap::new()),
+        };
+
+        let (scheme, rest) = rest
+            .split_once(':')
+            .ok_or_else(|| PackageUrlError::MissingScheme(s.to_string()))?;
+        if !scheme.eq_ignore_ascii_case(SCHEME) {
+            return Err(PackageUrlError::MissingScheme(s.to_string()));
+        }
+
+        let rest = rest.trim_matches('/');
+        let (package_type, rest) = rest
+            .split_once('/')
+            .ok_or_else(|| invalid("expected `type/name`"))?;
+        let valid_type = package_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-'))
+            && package_type.starts_with(|c: char| c.is_ascii_alphabetic());
+        if !valid_type {
+            return Err(invalid("type must be alphanumeric and start with a letter"));
+        }
+
+        // An `@` before the name is an unencoded npm scope, not a version separator.
+        let (rest, version) = match rest.rsplit_once('@') {
+            Some((rest, version)) if !version.contains('/') => (rest, Some(decode(version, s)?)),
+            _ => (rest, None),
+        };
+
+        let (namespace, name) = match rest.rsplit_once('/') {
+            Some((namespace, name)) => (decode_segments(namespace, s, false)?, name),
+            None => (None, rest),
+        };
+        let name = decode(name, s)?;
+        if name.is_empty() {
+            return Err(invalid("name is required"));
+        }
+
+        Self {
+            package_type: package_type.to_ascii_lowercase(),
+            namespace,
+            name,
+            version: version.filter(|v| !v.is_empty()),
+            qualifiers,
+            subpath,
+        }
+        .normalize()
+        .validate(s)
+    }
+}
+
+impl fmt::Display for PackageUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{SCHEME}:{}/", self.package_type)?;
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{}/", encode_segments(namespace))?;
+        }
+        f.write_str(&encode(&self.name))?;
+        if let Some(version) = &self.version {
+            write!(f, "@{}", encode(version))?;
+        }
+        let mut separator = '?';
+        for (key, value) in &self.qualifiers {
+            write!(f, "{separator}{key}={}", encode(value))?;
+            separator = '&';
+        }
+        if let Some(subpath) = &self.subpath {
+            write!(f, "#{}", encode_segments(subpath))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_qualifiers(
+    qualifiers: &str,
+    purl: &str,
+) -> Result<BTreeMap<String, String>, PackageUrlError> {
+    let mut parsed = BTreeMap::new();
+    for pair in qualifiers.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| PackageUrlError::InvalidQualifier(purl.to_string(), pair.to_string()))?;
+        let key = key.to_ascii_lowercase();
+        let valid_key = !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if !valid_key {
+            return Err(PackageUrlError::InvalidQualifier(purl.to_string(), key));
+        }
+
+        let value = decode(value, purl)?;
+        if value.is_empty() {
+            continue;
+        }
+        if parsed.insert(key.clone(), value).is_some() {
+            return Err(PackageUrlError::DuplicateQualifier(purl.to_string(), key));
+        }
+    }
+    Ok(parsed)
+}
+
+/// Decode the segments of a namespace or subpath, dropping empty ones, and `.` and `..` ones
+/// from subpaths. Returns `None` when no segment is left.
+fn decode_segments(
+    path: &str,
+    purl: &str,
+    subpath: bool,
+) -> Result<Option<String>, PackageUrlError> {
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .filter(|segment| !subpath || !matches!(*segment, "." | ".."))
+        .map(|segment| decode(segment, purl))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((!segments.is_empty()).then(|| segments.join("/")))
+}
+
+fn decode(component: &str, purl: &str) -> Result<String, PackageUrlError> {
+    let invalid = || PackageUrlError::InvalidEncoding(purl.to_string(), component.to_string());
+
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).ok_or_else(invalid)?;
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| invalid())
+}
+
+fn encode_segments(path: &str) -> String {
+    path.split('/').map(encode).collect::<Vec<_>>().join("/")
+}
+
+/// Percent-encode everything but unreserved characters and `:`, which the spec keeps as is.
+fn encode(component: &str) -> String {
+    let mut encoded = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b':') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PackageUrlError {
+    #[error("Package URL `{0}` must start with `pkg:`")]
+    MissingScheme(String),
+
+    #[error("Package URL `{0}` is malformed: {1}")]
+    InvalidFormat(String, String),
+
+    #[error("Package URL `{0}` has an invalid qualifier `{1}`")]
+    InvalidQualifier(String, String),
+
+    #[error("Package URL `{0}` repeats qualifier `{1}`")]
+    DuplicateQualifier(String, String),
+
+    #[error("Package URL `{0}` has an invalid percent-encoded component `{1}`")]
+    InvalidEncoding(String, String),
+
+    #[error("Package URL `{0}` lacks the namespace required for `{1}` packages")]
+    MissingNamespace(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purl(s: &str) -> PackageUrl {
+        PackageUrl::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn purl_should_parse_every_component() {
+        let parsed = purl(
+            "pkg:maven/org.apache.commons/commons-io@2.11.0?type=jar&classifier=sources#META-INF/MANIFEST.MF",
+        );
+        assert_eq!(parsed.package_type(), "maven");
+        assert_eq!(parsed.namespace(), Some("org.apache.commons"));
+        assert_eq!(parsed.name(), "commons-io");
+        assert_eq!(parsed.version(), Some("2.11.0"));
+        assert_eq!(parsed.qualifier("type"), Some("jar"));
+        assert_eq!(parsed.qualifier("classifier"), Some("sources"));
+        assert_eq!(parsed.subpath(), Some("META-INF/MANIFEST.MF"));
+        assert_eq!(
+            parsed.to_string(),
+            "pkg:maven/org.apache.commons/commons-io@2.11.0?classifier=sources&type=jar#META-INF/MANIFEST.MF"
+        );
+
+        let golang = purl("pkg:golang/github.com/gorilla/context@234fd47e07d1004f0aed9c");
+        assert_eq!(golang.namespace(), Some("github.com/gorilla"));
+        assert_eq!(golang.name(), "context");
+    }
+
+    #[test]
+    fn purl_should_round_trip_percent_encoding() {
+        let npm = purl("pkg:npm/%40angular/animation@12.3.1");
+        assert_eq!(npm.namespace(), Some("@angular"));
+        assert_eq!(npm.to_string(), "pkg:npm/%40angular/animation@12.3.1");
+        assert_eq!(
+            purl("pkg:npm/@angular/animation"),
+            purl("pkg:npm/%40angular/animation")
+        );
+
+        let encoded =
+            purl("pkg:generic/my%20tool@1.0.0%2Bbuild.5?download_url=https://x.test/a%3Fb");
+        assert_eq!(encoded.name(), "my tool");
+        assert_eq!(encoded.version(), Some("1.0.0+build.5"));
+        assert_eq!(
+            encoded.qualifier("download_url"),
+            Some("https://x.test/a?b")
+        );
+        assert_eq!(
+            encoded.to_string(),
+            "pkg:generic/my%20tool@1.0.0%2Bbuild.5?download_url=https:%2F%2Fx.test%2Fa%3Fb"
+        );
+        assert_eq!(purl(&encoded.to_string()), encoded);
+    }
+
+    #[test]
+    fn purl_should_apply_type_specific_normalization() {
+        let cases = [
+            (
+                "pkg:PyPI/Django_Rest_Framework@3.14",
+                "pkg:pypi/django-rest-framework@3.14",
+            ),
+            (
+                "pkg:npm/%40Angular/Core@16.0.0",
+                "pkg:npm/%40angular/core@16.0.0",
+            ),
+            (
+                "pkg:GitHub/Package-URL/Purl-Spec@244fd47e",
+                "pkg:github/package-url/purl-spec@244fd47e",
+            ),
+            (
+                "pkg:deb/Debian/OpenSSL@3.0.11-1?Distro=Debian-12&ARCH=AMD64",
+                "pkg:deb/debian/openssl@3.0.11-1?arch=amd64&distro=debian-12",
+            ),
+            (
+                "pkg:rpm/RedHat/NetworkManager@1.42.2?distro=RHEL-9.2&epoch=1",
+                "pkg:rpm/redhat/NetworkManager@1.42.2?distro=rhel-9.2&epoch=1",
+            ),
+            (
+                "pkg:apk/Alpine/BusyBox@1.36.1-r2?arch=x86_64&distro=Alpine-3.18.4",
+                "pkg:apk/alpine/busybox@1.36.1-r2?arch=x86_64&distro=alpine-3.18.4",
+            ),
+            // Maven coordinates are case sensitive.
+            (
+                "pkg:maven/Org.Example/Lib@1.0",
+                "pkg:maven/Org.Example/Lib@1.0",
+            ),
+        ];
+        for (input, canonical) in cases {
+            assert_eq!(purl(input).to_string(), canonical, "{input}");
+        }
+
+        assert_eq!(
+            purl("pkg:deb/debian/curl?distro=Debian-12").distro(),
+            Some("debian-12")
+        );
+        assert_eq!(purl("pkg:pypi/Django@4.2"), purl("pkg:pypi/django@4.2"));
+        assert!(purl("pkg:pypi/django@4.2").same_package(&purl("pkg:pypi/Django@5.0?x=y")));
+    }
+
+    #[test]
+    fn purl_should_be_lenient_where_the_spec_allows() {
+        let parsed = purl("pkg://gem/rails@?platform=&os=linux#/./lib//../app/");
+        assert_eq!(parsed.package_type(), "gem");
+        assert_eq!(parsed.version(), None);
+        assert_eq!(parsed.qualifier("platform"), None);
+        assert_eq!(parsed.subpath(), Some("lib/app"));
+        assert_eq!(parsed.to_string(), "pkg:gem/rails?os=linux#lib/app");
+    }
+
+    #[test]
+    fn invalid_purls_should_be_rejected() {
+        let err = |s: &str| PackageUrl::from_str(s).unwrap_err();
+
+        assert!(matches!(
+            err("npm/lodash@4.17.21"),
+            PackageUrlError::MissingScheme(_)
+        ));
+        assert!(matches!(
+            err("urn:npm/lodash"),
+            PackageUrlError::MissingScheme(_)
+        ));
+        assert!(matches!(err("pkg:npm"), PackageUrlError::InvalidFormat(..)));
+        assert!(matches!(
+            err("pkg:1npm/lodash"),
+            PackageUrlError::InvalidFormat(..)
+        ));
+        assert!(matches!(
+            err("pkg:npm/@4.17.21"),
+            PackageUrlError::InvalidFormat(..)
+        ));
+        assert!(matches!(
+            err("pkg:npm/lo%2"),
+            PackageUrlError::InvalidEncoding(..)
+        ));
+        assert!(matches!(
+            err("pkg:npm/lodash?arch"),
+            PackageUrlError::InvalidQualifier(..)
+        ));
+        assert!(matches!(
+            err("pkg:npm/lodash?1x=y"),
+            PackageUrlError::InvalidQualifier(..)
+        ));
+        assert!(matches!(
+            err("pkg:npm/lodash?a=b&A=c"),
+            PackageUrlError::DuplicateQualifier(..)
+        ));
+        assert!(matches!(
+            err("pkg:maven/commons-io@2.11.0"),
+            PackageUrlError::MissingNamespace(..)
+        ));
+    }
+}
@@ -1,18 +1,71 @@
+use crate::domain::component::id::{Digest, DigestAlgorithm};
 use std::convert::TryFrom;
 use std::fmt;
 use std::path::PathBuf;
 use url::Url;
 
 /// Immutable reference to a Software Bill of Materials (SBOM)
+///
+/// The digest pins the document contents: it is computed when the SBOM is generated or
+/// assigned, and every later read must be checked against it with [`Sbom::verify`].
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Sbom {
     location: SbomLocation,
+    spec: Option<SbomSpec>,
+    digest: Option<Digest>,
 }
 
 impl Sbom {
     #[must_use]
     pub const fn new(location: SbomLocation) -> Self {
-        Self { location }
+        Self {
+            location,
+            spec: None,
+            digest: None,
+        }
+    }
+
+    /// Record a digest of the document contents computed elsewhere.
+    #[must_use]
+    pub fn with_digest(mut self, digest: Digest) -> Self {
+        self.digest = Some(digest);
+        self
+    }
+
+    /// Record the SHA-256 digest of the given document contents.
+    #[must_use]
+    pub fn sealed(self, content: &[u8]) -> Self {
+        self.with_digest(Digest::compute(DigestAlgorithm::Sha256, content))
+    }
+
+    /// Check document contents read from the location against the recorded digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomIntegrityError::MissingDigest`] if no digest was recorded, or
+    /// [`SbomIntegrityError::Tampered`] if the contents changed since it was computed.
+    pub fn verify(&self, content: &[u8]) -> Result<(), SbomIntegrityError> {
+        let expected = self
+            .digest
+            .as_ref()
+            .ok_or_else(|| SbomIntegrityError::MissingDigest(self.to_string()))?;
+        let actual = Digest::compute(expected.algorithm(), content);
+        if &actual == expected {
+            Ok(())
+        } else {
+            Err(SbomIntegrityError::Tampered {
+                sbom: self.to_string(),
+                expected: expected.clone(),
+                actual,
+            })
+        }
+    }
+
+    /// Record the format and specification version of the document.
+    #[must_use]
+    pub fn with_spec(mut self, spec: SbomSpec) -> Self {
+        self.spec = Some(spec);
+        self
     }
 
     #[must_use]
@@ -20,6 +73,17 @@ impl Sbom {
         &self.location
     }
 
+    /// Format and specification version, unless not known yet (e.g. for remote documents).
+    #[must_use]
+    pub const fn spec(&self) -> Option<&SbomSpec> {
+        self.spec.as_ref()
+    }
+
+    #[must_use]
+    pub const fn digest(&self) -> Option<&Digest> {
+        self.digest.as_ref()
+    }
+
     /// Attempt to create an SBOM from a remote URL string.
     ///
     /// The input must be a well-formed URL (e.g. `https://`, `s3://`, etc.).
@@ -81,6 +145,58 @@ impl TryFrom<&str> for Sbom {
     }
 }
 
+/// Serialization format of an SBOM document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SbomFormat {
+    CycloneDxJson,
+    SpdxJson,
+    SpdxTagValue,
+}
+
+impl fmt::Display for SbomFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::CycloneDxJson => "cyclonedx-json",
+            Self::SpdxJson => "spdx-json",
+            Self::SpdxTagValue => "spdx-tag-value",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Format of an SBOM document and version of the specification it follows (e.g. `1.6`, `2.3`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SbomSpec {
+    format: SbomFormat,
+    version: String,
+}
+
+impl SbomSpec {
+    #[must_use]
+    pub fn new(format: SbomFormat, version: impl Into<String>) -> Self {
+        Self {
+            format,
+            version: version.into(),
+        }
+    }
+
+    #[must_use]
+    pub const fn format(&self) -> SbomFormat {
+        self.format
+    }
+
+    #[must_use]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+impl fmt::Display for SbomSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.format, self.version)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SbomLocation {
     Local(PathBuf),
@@ -122,6 +238,19 @@ pub enum SbomLocationError {
     NotUrlNorPath(String),
 }
 
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum SbomIntegrityError {
+    #[error("SBOM `{0}` has no digest to verify its contents against")]
+    MissingDigest(String),
+
+    #[error("SBOM `{sbom}` was tampered with: expected digest `{expected}`, found `{actual}`")]
+    Tampered {
+        sbom: String,
+        expected: Digest,
+        actual: Digest,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,12 +275,12 @@ mod tests {
 
     #[test]
     fn test_sbom_from_existing_path_str() {
-        let path = std::env::temp_dir().join("test_sbom.json");
-        std::fs::write(&path, "dummy").unwrap();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path();
+        std::fs::write(path, "dummy").unwrap();
         let sbom =
             Sbom::from_path_str(path.to_str().unwrap()).expect("Expected Sbom from valid path");
         assert!(matches!(sbom.location(), SbomLocation::Local(_)));
-        std::fs::remove_file(&path).unwrap();
     }
 
     #[test]
@@ -173,12 +302,47 @@ mod tests {
 
     #[test]
     fn test_sbom_try_from_existing_path_str() {
-        let path = std::env::temp_dir().join("test_sbom2.json");
-        std::fs::write(&path, "dummy").unwrap();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path();
+        std::fs::write(path, "dummy").unwrap();
         let sbom = Sbom::try_from(path.to_str().unwrap())
             .expect("Expected TryFrom to succeed with valid path");
         assert!(matches!(sbom.location(), SbomLocation::Local(_)));
-        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sbom_records_spec() {
+        let sbom = Sbom::from_url_str("https://example.com/sbom.spdx").unwrap();
+        assert!(sbom.spec().is_none());
+
+        let sbom = sbom.with_spec(SbomSpec::new(SbomFormat::SpdxTagValue, "2.3"));
+        let spec = sbom.spec().unwrap();
+        assert_eq!(spec.format(), SbomFormat::SpdxTagValue);
+        assert_eq!(spec.version(), "2.3");
+        assert_eq!(spec.to_string(), "spdx-tag-value 2.3");
+    }
+
+    #[test]
+    fn test_sbom_verifies_contents_against_digest() {
+        let sbom = Sbom::from_url_str("https://example.com/sbom.json").unwrap();
+        assert_eq!(
+            sbom.verify(b"{}"),
+            Err(SbomIntegrityError::MissingDigest(sbom.to_string()))
+        );
+
+        let sbom = sbom.sealed(b"{}");
+        assert_eq!(sbom.digest().unwrap().algorithm(), DigestAlgorithm::Sha256);
+        assert!(sbom.verify(b"{}").is_ok());
+
+        let err = sbom.verify(b"{ }").unwrap_err();
+        assert_eq!(
+            err,
+            SbomIntegrityError::Tampered {
+                sbom: sbom.to_string(),
+                expected: sbom.digest().unwrap().clone(),
+                actual: Digest::compute(DigestAlgorithm::Sha256, b"{ }"),
+            }
+        );
     }
 
     #[test]
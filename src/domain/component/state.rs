@@ -0,0 +1,57 @@
+use crate::domain::component::context::ExecutionContext;
+use crate::domain::component::sbom::Sbom;
+
+/// Lifecycle state of a component, following the design state diagram:
+///
+/// ```text
+/// Registered -> WithSbom -> WithExecutionContext
+///      \            |              /
+///       `------> Deprecated <-----'
+/// ```
+///
+/// Modelled as an enum so that an execution context can never exist without an SBOM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComponentState {
+    /// Registered, without SBOM nor execution context.
+    Registered,
+
+    /// An SBOM has been assigned.
+    WithSbom(Sbom),
+
+    /// Both an SBOM and an execution context have been assigned.
+    WithExecutionContext {
+        sbom: Sbom,
+        context: ExecutionContext,
+    },
+
+    /// No longer monitored. Keeps whatever the component held when it was deprecated.
+    Deprecated {
+        sbom: Option<Sbom>,
+        context: Option<ExecutionContext>,
+    },
+}
+
+impl ComponentState {
+    #[must_use]
+    pub const fn sbom(&self) -> Option<&Sbom> {
+        match self {
+            Self::Registered => None,
+            Self::WithSbom(sbom) | Self::WithExecutionContext { sbom, .. } => Some(sbom),
+            Self::Deprecated { sbom, .. } => sbom.as_ref(),
+        }
+    }
+
+    #[must_use]
+    pub const fn context(&self) -> Option<&ExecutionContext> {
+        match self {
+            Self::Registered | Self::WithSbom(_) => None,
+            Self::WithExecutionContext { context, .. } => Some(context),
+            Self::Deprecated { context, .. } => context.as_ref(),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_deprecated(&self) -> bool {
+        matches!(self, Self::Deprecated { .. })
+    }
+}
@@ -1,3 +1,4 @@
 pub mod collection;
 pub mod component;
 pub mod shared;
+pub mod vulnerability;
@@ -0,0 +1,105 @@
+use crate::domain::component::context::ExecutionContext;
+use crate::domain::vulnerability::id::ManagedVulnerabilityId;
+use std::fmt;
+
+/// Qualitative severity of a vulnerability in the context of a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    None,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Qualitative rating of a CVSS score, as defined by the FIRST specification.
+    ///
+    /// The same ranges apply to CVSS v3.x and v4.0 scores.
+    #[must_use]
+    pub fn from_score(score: f64) -> Self {
+        match score {
+            s if s <= 0.0 => Self::None,
+            s if s < 4.0 => Self::Low,
+            s if s < 7.0 => Self::Medium,
+            s if s < 9.0 => Self::High,
+            _ => Self::Critical,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::None => "None",
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+            Self::Critical => "Critical",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Business-relevant classification of a managed vulnerability.
+///
+/// A classification is always bound to the execution context it was computed for,
+/// so that later context changes can be detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    severity: Severity,
+    context: ExecutionContext,
+}
+
+impl Classification {
+    #[must_use]
+    pub const fn new(severity: Severity, context: ExecutionContext) -> Self {
+        Self { severity, context }
+    }
+
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    #[must_use]
+    pub const fn context(&self) -> &ExecutionContext {
+        &self.context
+    }
+}
+
+/// Current classification state of a managed vulnerability.
+///
+/// Modelled as an enum so that a vulnerability holds exactly one state at any time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassificationState {
+    /// Registered, but not yet classified.
+    Unclassified,
+
+    /// Classified directly from its own context and CVSS data.
+    Classified(Classification),
+
+    /// Adopted from an already classified vulnerability on a similar component.
+    Inherited {
+        classification: Classification,
+        source: ManagedVulnerabilityId,
+    },
+
+    /// The last known classification no longer reflects the context or CVSS data.
+    Outdated(Classification),
+}
+
+impl ClassificationState {
+    /// The classification held in this state, if any.
+    #[must_use]
+    pub const fn classification(&self) -> Option<&Classification> {
+        match self {
+            Self::Unclassified => None,
+            Self::Classified(c)
+            | Self::Outdated(c)
+            | Self::Inherited {
+                classification: c, ..
+            } => Some(c),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use crate::domain::vulnerability::classification::Severity;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Declare a CVSS metric as an enum of values, each bound to its vector abbreviation.
+macro_rules! metric {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $abbr:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// Abbreviated value, as written in a vector string.
+            #[must_use]
+            pub const fn abbreviation(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $abbr),+
+                }
+            }
+
+            fn parse(key: &str, value: &str) -> Result<Self, CvssError> {
+                match value {
+                    $($abbr => Ok(Self::$variant),)+
+                    _ => Err(CvssError::InvalidValue(key.to_string(), value.to_string())),
+                }
+            }
+        }
+    };
+}
+
+pub mod v3;
+pub mod v4;
+
+/// CVSS vector of any supported version, dispatched on its `CVSS:<version>/` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cvss {
+    V3(v3::CvssV3),
+    V4(v4::CvssV4),
+}
+
+impl Cvss {
+    /// Score from 0.0 to 10.0, taking every defined metric group into account.
+    ///
+    /// For CVSS v3.1 see [`v3::CvssV3::score`].
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        match self {
+            Self::V3(cvss) => cvss.score(),
+            Self::V4(cvss) => cvss.score(),
+        }
+    }
+
+    /// Qualitative severity of [`Self::score`].
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        Severity::from_score(self.score())
+    }
+}
+
+impl From<v3::CvssV3> for Cvss {
+    fn from(cvss: v3::CvssV3) -> Self {
+        Self::V3(cvss)
+    }
+}
+
+impl From<v4::CvssV4> for Cvss {
+    fn from(cvss: v4::CvssV4) -> Self {
+        Self::V4(cvss)
+    }
+}
+
+impl FromStr for Cvss {
+    type Err = CvssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/').map(|(version, _)| version) {
+            Some("CVSS:3.1") => s.parse().map(Self::V3),
+            Some("CVSS:4.0") => s.parse().map(Self::V4),
+            Some(version) if version.starts_with("CVSS:") => {
+                Err(CvssError::UnsupportedVersion(version.to_string()))
+            }
+            _ => Err(CvssError::Malformed(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Cvss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V3(cvss) => cvss.fmt(f),
+            Self::V4(cvss) => cvss.fmt(f),
+        }
+    }
+}
+
+/// Metrics of a vector string, indexed by abbreviation and consumed while parsing.
+struct VectorMetrics<'a> {
+    metrics: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> VectorMetrics<'a> {
+    /// Split a vector string into its metrics, after checking its `prefix` (e.g. `CVSS:3.1`).
+    fn parse(vector: &'a str, prefix: &str) -> Result<Self, CvssError> {
+        let (version, body) = vector
+            .split_once('/')
+            .ok_or_else(|| CvssError::Malformed(vector.to_string()))?;
+
+        if !version.starts_with("CVSS:") {
+            return Err(CvssError::Malformed(vector.to_string()));
+        }
+        if version != prefix {
+            return Err(CvssError::UnsupportedVersion(version.to_string()));
+        }
+
+        let mut metrics = HashMap::new();
+        for part in body.split('/') {
+            let (key, value) = part
+                .split_once(':')
+                .filter(|(k, v)| !k.is_empty() && !v.is_empty())
+                .ok_or_else(|| CvssError::Malformed(vector.to_string()))?;
+
+            if metrics.insert(key, value).is_some() {
+                return Err(CvssError::DuplicateMetric(key.to_string()));
+            }
+        }
+
+        Ok(Self { metrics })
+    }
+
+    /// Take a mandatory metric.
+    fn required<T>(
+        &mut self,
+        key: &str,
+        parse: impl Fn(&str, &str) -> Result<T, CvssError>,
+    ) -> Result<T, CvssError> {
+        let value = self
+            .metrics
+            .remove(key)
+            .ok_or_else(|| CvssError::MissingMetric(key.to_string()))?;
+        parse(key, value)
+    }
+
+    /// Take an optional metric, treating both absence and `X` (Not Defined) as `None`.
+    fn optional<T>(
+        &mut self,
+        key: &str,
+        parse: impl Fn(&str, &str) -> Result<T, CvssError>,
+    ) -> Result<Option<T>, CvssError> {
+        match self.metrics.remove(key) {
+            None | Some("X") => Ok(None),
+            Some(value) => parse(key, value).map(Some),
+        }
+    }
+
+    /// Fail if any metric was left unconsumed.
+    fn finish(self) -> Result<(), CvssError> {
+        let mut unknown: Vec<&str> = self.metrics.into_keys().collect();
+        unknown.sort_unstable();
+        unknown.first().map_or(Ok(()), |key| {
+            Err(CvssError::UnknownMetric((*key).to_string()))
+        })
+    }
+}
+
+/// Errors raised while parsing a CVSS vector string.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CvssError {
+    #[error("Malformed CVSS vector `{0}`")]
+    Malformed(String),
+
+    #[error("Unsupported CVSS version `{0}`")]
+    UnsupportedVersion(String),
+
+    #[error("Metric `{0}` appears more than once")]
+    DuplicateMetric(String),
+
+    #[error("Mandatory metric `{0}` is missing")]
+    MissingMetric(String),
+
+    #[error("Unknown metric `{0}`")]
+    UnknownMetric(String),
+
+    #[error("Invalid value `{1}` for metric `{0}`")]
+    InvalidValue(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dispatches_on_version_prefix() {
+        let v3 = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H";
+        let v4 = "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N";
+
+        let cvss = Cvss::from_str(v3).unwrap();
+        assert!(matches!(cvss, Cvss::V3(_)));
+        assert_eq!(cvss.score(), 9.8);
+        assert_eq!(cvss.to_string(), v3);
+
+        let cvss = Cvss::from_str(v4).unwrap();
+        assert!(matches!(cvss, Cvss::V4(_)));
+        assert_eq!(cvss.score(), 9.3);
+        assert_eq!(cvss.severity(), Severity::Critical);
+        assert_eq!(cvss.to_string(), v4);
+
+        assert_eq!(
+            Cvss::from_str("CVSS:2.0/AV:N").unwrap_err(),
+            CvssError::UnsupportedVersion("CVSS:2.0".to_string())
+        );
+        assert_eq!(
+            Cvss::from_str("AV:N/AC:L").unwrap_err(),
+            CvssError::Malformed("AV:N/AC:L".to_string())
+        );
+    }
+
+    #[test]
+    fn v3_score_without_environmental_metrics_is_the_temporal_score() {
+        // Scope changes are scored differently by the environmental formula.
+        let cvss = Cvss::from_str("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:H/I:H/A:H").unwrap();
+        assert_eq!(cvss.score(), 9.9);
+
+        let cvss = Cvss::from_str("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:H/I:H/A:H/E:P").unwrap();
+        assert_eq!(cvss.score(), 9.4);
+
+        let cvss = Cvss::from_str("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:H/I:H/A:H/CR:L").unwrap();
+        let Cvss::V3(v3) = cvss else { unreachable!() };
+        assert_eq!(cvss.score(), v3.environmental_score());
+    }
+}
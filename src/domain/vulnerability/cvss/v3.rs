@@ -0,0 +1,612 @@
+use super::{CvssError, VectorMetrics};
+use crate::domain::vulnerability::classification::Severity;
+use std::fmt;
+use std::str::FromStr;
+
+const PREFIX: &str = "CVSS:3.1";
+
+metric!(AttackVector {
+    Network => "N",
+    Adjacent => "A",
+    Local => "L",
+    Physical => "P",
+});
+
+metric!(AttackComplexity {
+    Low => "L",
+    High => "H",
+});
+
+metric!(PrivilegesRequired {
+    None => "N",
+    Low => "L",
+    High => "H",
+});
+
+metric!(UserInteraction {
+    None => "N",
+    Required => "R",
+});
+
+metric!(Scope {
+    Unchanged => "U",
+    Changed => "C",
+});
+
+metric!(
+    /// Impact on confidentiality, integrity or availability.
+    Impact {
+        High => "H",
+        Low => "L",
+        None => "N",
+    }
+);
+
+metric!(ExploitCodeMaturity {
+    High => "H",
+    Functional => "F",
+    ProofOfConcept => "P",
+    Unproven => "U",
+});
+
+metric!(RemediationLevel {
+    Unavailable => "U",
+    Workaround => "W",
+    TemporaryFix => "T",
+    OfficialFix => "O",
+});
+
+metric!(ReportConfidence {
+    Confirmed => "C",
+    Reasonable => "R",
+    Unknown => "U",
+});
+
+metric!(
+    /// Security requirement (CR, IR, AR) of the affected asset.
+    Requirement {
+        High => "H",
+        Medium => "M",
+        Low => "L",
+    }
+);
+
+/// Mandatory base metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BaseMetrics {
+    pub attack_vector: AttackVector,
+    pub attack_complexity: AttackComplexity,
+    pub privileges_required: PrivilegesRequired,
+    pub user_interaction: UserInteraction,
+    pub scope: Scope,
+    pub confidentiality: Impact,
+    pub integrity: Impact,
+    pub availability: Impact,
+}
+
+/// Optional temporal metrics. `None` stands for Not Defined (`X`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TemporalMetrics {
+    pub exploit_code_maturity: Option<ExploitCodeMaturity>,
+    pub remediation_level: Option<RemediationLevel>,
+    pub report_confidence: Option<ReportConfidence>,
+}
+
+/// Optional environmental metrics. `None` stands for Not Defined (`X`).
+///
+/// Undefined modified base metrics fall back to their base counterpart when scoring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EnvironmentalMetrics {
+    pub confidentiality_requirement: Option<Requirement>,
+    pub integrity_requirement: Option<Requirement>,
+    pub availability_requirement: Option<Requirement>,
+    pub modified_attack_vector: Option<AttackVector>,
+    pub modified_attack_complexity: Option<AttackComplexity>,
+    pub modified_privileges_required: Option<PrivilegesRequired>,
+    pub modified_user_interaction: Option<UserInteraction>,
+    pub modified_scope: Option<Scope>,
+    pub modified_confidentiality: Option<Impact>,
+    pub modified_integrity: Option<Impact>,
+    pub modified_availability: Option<Impact>,
+}
+
+/// CVSS v3.1 vector, scored as defined by the FIRST specification.
+///
+/// Parsing is strict: the `CVSS:3.1/` prefix and every base metric are mandatory, and unknown,
+/// duplicated or invalid metrics are rejected. The canonical form (see [`fmt::Display`]) lists
+/// metrics in specification order and omits those that are Not Defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CvssV3 {
+    base: BaseMetrics,
+    temporal: TemporalMetrics,
+    environmental: EnvironmentalMetrics,
+}
+
+impl CvssV3 {
+    #[must_use]
+    pub const fn new(
+        base: BaseMetrics,
+        temporal: TemporalMetrics,
+        environmental: EnvironmentalMetrics,
+    ) -> Self {
+        Self {
+            base,
+            temporal,
+            environmental,
+        }
+    }
+
+    #[must_use]
+    pub const fn base(&self) -> &BaseMetrics {
+        &self.base
+    }
+
+    #[must_use]
+    pub const fn temporal(&self) -> &TemporalMetrics {
+        &self.temporal
+    }
+
+    #[must_use]
+    pub const fn environmental(&self) -> &EnvironmentalMetrics {
+        &self.environmental
+    }
+
+    /// Base score, from 0.0 to 10.0.
+    #[must_use]
+    pub fn base_score(&self) -> f64 {
+        let b = &self.base;
+        let iss = impact_subscore(
+            (1.0, b.confidentiality),
+            (1.0, b.integrity),
+            (1.0, b.availability),
+        );
+        let impact = match b.scope {
+            Scope::Unchanged => 6.42 * iss,
+            Scope::Changed => 7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15),
+        };
+        let exploitability = exploitability(
+            b.attack_vector,
+            b.attack_complexity,
+            b.privileges_required,
+            b.user_interaction,
+            b.scope,
+        );
+
+        combine(impact, exploitability, b.scope)
+    }
+
+    /// Temporal score: the base score adjusted by exploit maturity, remediation and confidence.
+    #[must_use]
+    pub fn temporal_score(&self) -> f64 {
+        round_up(self.base_score() * self.temporal_multiplier())
+    }
+
+    /// Environmental score: the score recomputed with modified base metrics and the
+    /// security requirements of the affected asset.
+    #[must_use]
+    pub fn environmental_score(&self) -> f64 {
+        let b = &self.base;
+        let e = &self.environmental;
+
+        let scope = e.modified_scope.unwrap_or(b.scope);
+        let miss = impact_subscore(
+            (
+                requirement_weight(e.confidentiality_requirement),
+                e.modified_confidentiality.unwrap_or(b.confidentiality),
+            ),
+            (
+                requirement_weight(e.integrity_requirement),
+                e.modified_integrity.unwrap_or(b.integrity),
+            ),
+            (
+                requirement_weight(e.availability_requirement),
+                e.modified_availability.unwrap_or(b.availability),
+            ),
+        )
+        .min(0.915);
+        let impact = match scope {
+            Scope::Unchanged => 6.42 * miss,
+            Scope::Changed => 7.52 * (miss - 0.029) - 3.25 * (miss * 0.9731 - 0.02).powi(13),
+        };
+        let exploitability = exploitability(
+            e.modified_attack_vector.unwrap_or(b.attack_vector),
+            e.modified_attack_complexity.unwrap_or(b.attack_complexity),
+            e.modified_privileges_required
+                .unwrap_or(b.privileges_required),
+            e.modified_user_interaction.unwrap_or(b.user_interaction),
+            scope,
+        );
+
+        round_up(combine(impact, exploitability, scope) * self.temporal_multiplier())
+    }
+
+    /// Most specific score of the vector: the environmental score when an environmental metric
+    /// is defined, the temporal score otherwise.
+    ///
+    /// The environmental formula differs from the base one for changed scopes, so it is only
+    /// used when the vector actually carries environmental metrics.
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        if self.environmental == EnvironmentalMetrics::default() {
+            self.temporal_score()
+        } else {
+            self.environmental_score()
+        }
+    }
+
+    /// Qualitative severity of the base score.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        Severity::from_score(self.base_score())
+    }
+
+    fn temporal_multiplier(&self) -> f64 {
+        let t = &self.temporal;
+        let e = match t.exploit_code_maturity {
+            None | Some(ExploitCodeMaturity::High) => 1.0,
+            Some(ExploitCodeMaturity::Functional) => 0.97,
+            Some(ExploitCodeMaturity::ProofOfConcept) => 0.94,
+            Some(ExploitCodeMaturity::Unproven) => 0.91,
+        };
+        let rl = match t.remediation_level {
+            None | Some(RemediationLevel::Unavailable) => 1.0,
+            Some(RemediationLevel::Workaround) => 0.97,
+            Some(RemediationLevel::TemporaryFix) => 0.96,
+            Some(RemediationLevel::OfficialFix) => 0.95,
+        };
+        let rc = match t.report_confidence {
+            None | Some(ReportConfidence::Confirmed) => 1.0,
+            Some(ReportConfidence::Reasonable) => 0.96,
+            Some(ReportConfidence::Unknown) => 0.92,
+        };
+        e * rl * rc
+    }
+}
+
+/// Impact sub score from (requirement weight, impact) pairs for C, I and A.
+fn impact_subscore(c: (f64, Impact), i: (f64, Impact), a: (f64, Impact)) -> f64 {
+    let weight = |impact| match impact {
+        Impact::High => 0.56,
+        Impact::Low => 0.22,
+        Impact::None => 0.0,
+    };
+    1.0 - (1.0 - c.0 * weight(c.1)) * (1.0 - i.0 * weight(i.1)) * (1.0 - a.0 * weight(a.1))
+}
+
+fn exploitability(
+    av: AttackVector,
+    ac: AttackComplexity,
+    pr: PrivilegesRequired,
+    ui: UserInteraction,
+    scope: Scope,
+) -> f64 {
+    let av = match av {
+        AttackVector::Network => 0.85,
+        AttackVector::Adjacent => 0.62,
+        AttackVector::Local => 0.55,
+        AttackVector::Physical => 0.2,
+    };
+    let ac = match ac {
+        AttackComplexity::Low => 0.77,
+        AttackComplexity::High => 0.44,
+    };
+    let pr = match (pr, scope) {
+        (PrivilegesRequired::None, _) => 0.85,
+        (PrivilegesRequired::Low, Scope::Unchanged) => 0.62,
+        (PrivilegesRequired::Low, Scope::Changed) => 0.68,
+        (PrivilegesRequired::High, Scope::Unchanged) => 0.27,
+        (PrivilegesRequired::High, Scope::Changed) => 0.5,
+    };
+    let ui = match ui {
+        UserInteraction::None => 0.85,
+        UserInteraction::Required => 0.62,
+    };
+    8.22 * av * ac * pr * ui
+}
+
+/// Combine impact and exploitability into a rounded score, honouring the scope.
+fn combine(impact: f64, exploitability: f64, scope: Scope) -> f64 {
+    if impact <= 0.0 {
+        return 0.0;
+    }
+    match scope {
+        Scope::Unchanged => round_up((impact + exploitability).min(10.0)),
+        Scope::Changed => round_up((1.08 * (impact + exploitability)).min(10.0)),
+    }
+}
+
+fn requirement_weight(requirement: Option<Requirement>) -> f64 {
+    match requirement {
+        None | Some(Requirement::Medium) => 1.0,
+        Some(Requirement::High) => 1.5,
+        Some(Requirement::Low) => 0.5,
+    }
+}
+
+/// Round up to one decimal, as defined in CVSS v3.1 Appendix A.
+///
+/// Works on an integer representation to avoid floating point artifacts such as
+/// `4.000000000000001` being rounded up to `4.1`.
+#[allow(clippy::cast_possible_truncation)]
+fn round_up(value: f64) -> f64 {
+    let int_input = (value * 100_000.0).round() as i64;
+    if int_input % 10_000 == 0 {
+        int_input as f64 / 100_000.0
+    } else {
+        ((int_input / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+impl FromStr for CvssV3 {
+    type Err = CvssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut m = VectorMetrics::parse(s, PREFIX)?;
+
+        let base = BaseMetrics {
+            attack_vector: m.required("AV", AttackVector::parse)?,
+            attack_complexity: m.required("AC", AttackComplexity::parse)?,
+            privileges_required: m.required("PR", PrivilegesRequired::parse)?,
+            user_interaction: m.required("UI", UserInteraction::parse)?,
+            scope: m.required("S", Scope::parse)?,
+            confidentiality: m.required("C", Impact::parse)?,
+            integrity: m.required("I", Impact::parse)?,
+            availability: m.required("A", Impact::parse)?,
+        };
+
+        let temporal = TemporalMetrics {
+            exploit_code_maturity: m.optional("E", ExploitCodeMaturity::parse)?,
+            remediation_level: m.optional("RL", RemediationLevel::parse)?,
+            report_confidence: m.optional("RC", ReportConfidence::parse)?,
+        };
+
+        let environmental = EnvironmentalMetrics {
+            confidentiality_requirement: m.optional("CR", Requirement::parse)?,
+            integrity_requirement: m.optional("IR", Requirement::parse)?,
+            availability_requirement: m.optional("AR", Requirement::parse)?,
+            modified_attack_vector: m.optional("MAV", AttackVector::parse)?,
+            modified_attack_complexity: m.optional("MAC", AttackComplexity::parse)?,
+            modified_privileges_required: m.optional("MPR", PrivilegesRequired::parse)?,
+            modified_user_interaction: m.optional("MUI", UserInteraction::parse)?,
+            modified_scope: m.optional("MS", Scope::parse)?,
+            modified_confidentiality: m.optional("MC", Impact::parse)?,
+            modified_integrity: m.optional("MI", Impact::parse)?,
+            modified_availability: m.optional("MA", Impact::parse)?,
+        };
+
+        m.finish()?;
+        Ok(Self::new(base, temporal, environmental))
+    }
+}
+
+impl fmt::Display for CvssV3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = &self.base;
+        write!(
+            f,
+            "{PREFIX}/AV:{}/AC:{}/PR:{}/UI:{}/S:{}/C:{}/I:{}/A:{}",
+            b.attack_vector.abbreviation(),
+            b.attack_complexity.abbreviation(),
+            b.privileges_required.abbreviation(),
+            b.user_interaction.abbreviation(),
+            b.scope.abbreviation(),
+            b.confidentiality.abbreviation(),
+            b.integrity.abbreviation(),
+            b.availability.abbreviation(),
+        )?;
+
+        let t = &self.temporal;
+        let e = &self.environmental;
+        let optional = [
+            (
+                "E",
+                t.exploit_code_maturity
+                    .map(ExploitCodeMaturity::abbreviation),
+            ),
+            (
+                "RL",
+                t.remediation_level.map(RemediationLevel::abbreviation),
+            ),
+            (
+                "RC",
+                t.report_confidence.map(ReportConfidence::abbreviation),
+            ),
+            (
+                "CR",
+                e.confidentiality_requirement.map(Requirement::abbreviation),
+            ),
+            ("IR", e.integrity_requirement.map(Requirement::abbreviation)),
+            (
+                "AR",
+                e.availability_requirement.map(Requirement::abbreviation),
+            ),
+            (
+                "MAV",
+                e.modified_attack_vector.map(AttackVector::abbreviation),
+            ),
+            (
+                "MAC",
+                e.modified_attack_complexity
+                    .map(AttackComplexity::abbreviation),
+            ),
+            (
+                "MPR",
+                e.modified_privileges_required
+                    .map(PrivilegesRequired::abbreviation),
+            ),
+            (
+                "MUI",
+                e.modified_user_interaction
+                    .map(UserInteraction::abbreviation),
+            ),
+            ("MS", e.modified_scope.map(Scope::abbreviation)),
+            ("MC", e.modified_confidentiality.map(Impact::abbreviation)),
+            ("MI", e.modified_integrity.map(Impact::abbreviation)),
+            ("MA", e.modified_availability.map(Impact::abbreviation)),
+        ];
+
+        for (key, value) in optional {
+            if let Some(value) = value {
+                write!(f, "/{key}:{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cvss(vector: &str) -> CvssV3 {
+        CvssV3::from_str(vector).unwrap()
+    }
+
+    #[test]
+    fn base_scores_match_specification_examples() {
+        let examples = [
+            // CVE-2013-1937 phpMyAdmin reflected XSS
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N", 6.1),
+            // CVE-2013-0375 MySQL stored SQL injection
+            ("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:L/I:L/A:N", 6.4),
+            // CVE-2014-3566 SSLv3 POODLE
+            ("CVSS:3.1/AV:N/AC:H/PR:N/UI:R/S:U/C:L/I:N/A:N", 3.1),
+            // CVE-2012-1516 VMware guest to host escape
+            ("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:H/I:H/A:H", 9.9),
+            // CVE-2009-0783 Apache Tomcat XML parser
+            ("CVSS:3.1/AV:L/AC:L/PR:H/UI:N/S:U/C:L/I:L/A:L", 4.2),
+            // CVE-2012-0384 Cisco IOS command injection
+            ("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:H", 8.8),
+            // CVE-2014-0160 OpenSSL Heartbleed
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N", 7.5),
+            // CVE-2014-6271 GNU Bash Shellshock
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", 9.8),
+            // CVE-2008-1447 DNS Kaminsky bug
+            ("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:C/C:N/I:H/A:N", 6.8),
+            // CVE-2014-2005 Sophos Disk Encryption
+            ("CVSS:3.1/AV:P/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", 6.8),
+            // CVE-2012-5376 Chrome IPC sandbox escape
+            ("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:H/I:H/A:H", 9.6),
+            // CVE-2004-0230 TCP reset spoofing
+            ("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:L", 3.7),
+        ];
+
+        for (vector, expected) in examples {
+            assert_eq!(cvss(vector).base_score(), expected, "{vector}");
+        }
+    }
+
+    #[test]
+    fn no_impact_should_score_zero() {
+        let v = cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N");
+        assert_eq!(v.base_score(), 0.0);
+        assert_eq!(v.environmental_score(), 0.0);
+        assert_eq!(v.severity(), Severity::None);
+    }
+
+    #[test]
+    fn temporal_score_applies_multipliers() {
+        let v = cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/E:P/RL:O/RC:C");
+        assert_eq!(v.base_score(), 9.8);
+        assert_eq!(v.temporal_score(), 8.8);
+    }
+
+    #[test]
+    fn environmental_score_uses_modified_metrics_and_requirements() {
+        let v = cvss(
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/E:U/RL:O/RC:R/CR:L/IR:L/AR:L/MAV:L/MS:C",
+        );
+        assert_eq!(v.base_score(), 9.8);
+        assert_eq!(v.temporal_score(), 8.2);
+        assert_eq!(v.environmental_score(), 6.4);
+
+        let v = cvss("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:H/CR:H/IR:H/AR:H/MPR:N/MS:C");
+        assert_eq!(v.base_score(), 8.8);
+        assert_eq!(v.environmental_score(), 10.0);
+    }
+
+    #[test]
+    fn undefined_environment_matches_base_for_unchanged_scope() {
+        let v = cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N");
+        assert_eq!(v.environmental_score(), v.base_score());
+    }
+
+    #[test]
+    fn round_up_avoids_floating_point_artifacts() {
+        assert_eq!(round_up(4.000_000_000_000_001), 4.0);
+        assert_eq!(round_up(4.02), 4.1);
+        assert_eq!(round_up(4.0), 4.0);
+    }
+
+    #[test]
+    fn severity_follows_qualitative_rating_scale() {
+        assert_eq!(
+            cvss("CVSS:3.1/AV:N/AC:H/PR:N/UI:R/S:U/C:L/I:N/A:N").severity(),
+            Severity::Low
+        );
+        assert_eq!(
+            cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N").severity(),
+            Severity::Medium
+        );
+        assert_eq!(
+            cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N").severity(),
+            Severity::High
+        );
+        assert_eq!(
+            cvss("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").severity(),
+            Severity::Critical
+        );
+    }
+
+    #[test]
+    fn display_is_canonical() {
+        let v = cvss("CVSS:3.1/C:H/I:H/A:H/AV:N/AC:L/PR:N/UI:N/S:U/RL:X/MAV:L/E:P");
+        assert_eq!(
+            v.to_string(),
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/E:P/MAV:L"
+        );
+        assert_eq!(cvss(&v.to_string()), v);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_vectors() {
+        let cases = [
+            (
+                "AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                CvssError::Malformed("AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H".into()),
+            ),
+            (
+                "CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                CvssError::UnsupportedVersion("CVSS:3.0".into()),
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H",
+                CvssError::MissingMetric("A".into()),
+            ),
+            (
+                "CVSS:3.1/AV:N/AV:L/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                CvssError::DuplicateMetric("AV".into()),
+            ),
+            (
+                "CVSS:3.1/AV:Z/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                CvssError::InvalidValue("AV".into(), "Z".into()),
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/FOO:B",
+                CvssError::UnknownMetric("FOO".into()),
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/",
+                CvssError::Malformed("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H/".into()),
+            ),
+            (
+                "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:X",
+                CvssError::InvalidValue("A".into(), "X".into()),
+            ),
+        ];
+
+        for (vector, expected) in cases {
+            assert_eq!(CvssV3::from_str(vector).unwrap_err(), expected, "{vector}");
+        }
+    }
+}
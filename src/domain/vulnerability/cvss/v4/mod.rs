@@ -0,0 +1,1039 @@
+mod lookup;
+
+use super::{CvssError, VectorMetrics};
+use crate::domain::vulnerability::classification::Severity;
+use lookup::MACROVECTOR_SCORES;
+use std::fmt;
+use std::str::FromStr;
+
+const PREFIX: &str = "CVSS:4.0";
+
+metric!(AttackVector {
+    Network => "N",
+    Adjacent => "A",
+    Local => "L",
+    Physical => "P",
+});
+
+metric!(AttackComplexity {
+    Low => "L",
+    High => "H",
+});
+
+metric!(AttackRequirements {
+    None => "N",
+    Present => "P",
+});
+
+metric!(PrivilegesRequired {
+    None => "N",
+    Low => "L",
+    High => "H",
+});
+
+metric!(UserInteraction {
+    None => "N",
+    Passive => "P",
+    Active => "A",
+});
+
+metric!(
+    /// Impact on the confidentiality, integrity or availability of a system.
+    Impact {
+        High => "H",
+        Low => "L",
+        None => "N",
+    }
+);
+
+metric!(
+    /// Modified subsequent system integrity or availability impact, which adds `Safety`.
+    SubsequentImpact {
+        Safety => "S",
+        High => "H",
+        Low => "L",
+        None => "N",
+    }
+);
+
+metric!(ExploitMaturity {
+    Attacked => "A",
+    ProofOfConcept => "P",
+    Unreported => "U",
+});
+
+metric!(
+    /// Security requirement (CR, IR, AR) of the affected asset.
+    Requirement {
+        High => "H",
+        Medium => "M",
+        Low => "L",
+    }
+);
+
+metric!(Safety {
+    Negligible => "N",
+    Present => "P",
+});
+
+metric!(Automatable {
+    No => "N",
+    Yes => "Y",
+});
+
+metric!(Recovery {
+    Automatic => "A",
+    User => "U",
+    Irrecoverable => "I",
+});
+
+metric!(ValueDensity {
+    Diffuse => "D",
+    Concentrated => "C",
+});
+
+metric!(ResponseEffort {
+    Low => "L",
+    Moderate => "M",
+    High => "H",
+});
+
+metric!(ProviderUrgency {
+    Clear => "Clear",
+    Green => "Green",
+    Amber => "Amber",
+    Red => "Red",
+});
+
+impl From<Impact> for SubsequentImpact {
+    fn from(impact: Impact) -> Self {
+        match impact {
+            Impact::High => Self::High,
+            Impact::Low => Self::Low,
+            Impact::None => Self::None,
+        }
+    }
+}
+
+/// Mandatory base metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BaseMetrics {
+    pub attack_vector: AttackVector,
+    pub attack_complexity: AttackComplexity,
+    pub attack_requirements: AttackRequirements,
+    pub privileges_required: PrivilegesRequired,
+    pub user_interaction: UserInteraction,
+    pub vulnerable_confidentiality: Impact,
+    pub vulnerable_integrity: Impact,
+    pub vulnerable_availability: Impact,
+    pub subsequent_confidentiality: Impact,
+    pub subsequent_integrity: Impact,
+    pub subsequent_availability: Impact,
+}
+
+/// Optional threat metrics. `None` stands for Not Defined (`X`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ThreatMetrics {
+    pub exploit_maturity: Option<ExploitMaturity>,
+}
+
+/// Optional environmental metrics. `None` stands for Not Defined (`X`).
+///
+/// Undefined modified base metrics fall back to their base counterpart when scoring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EnvironmentalMetrics {
+    pub confidentiality_requirement: Option<Requirement>,
+    pub integrity_requirement: Option<Requirement>,
+    pub availability_requirement: Option<Requirement>,
+    pub modified_attack_vector: Option<AttackVector>,
+    pub modified_attack_complexity: Option<AttackComplexity>,
+    pub modified_attack_requirements: Option<AttackRequirements>,
+    pub modified_privileges_required: Option<PrivilegesRequired>,
+    pub modified_user_interaction: Option<UserInteraction>,
+    pub modified_vulnerable_confidentiality: Option<Impact>,
+    pub modified_vulnerable_integrity: Option<Impact>,
+    pub modified_vulnerable_availability: Option<Impact>,
+    pub modified_subsequent_confidentiality: Option<Impact>,
+    pub modified_subsequent_integrity: Option<SubsequentImpact>,
+    pub modified_subsequent_availability: Option<SubsequentImpact>,
+}
+
+/// Optional supplemental metrics. They convey context but never alter the score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SupplementalMetrics {
+    pub safety: Option<Safety>,
+    pub automatable: Option<Automatable>,
+    pub recovery: Option<Recovery>,
+    pub value_density: Option<ValueDensity>,
+    pub response_effort: Option<ResponseEffort>,
+    pub provider_urgency: Option<ProviderUrgency>,
+}
+
+/// Name of a CVSS v4.0 score, depending on which metric groups contributed to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nomenclature {
+    /// Base metrics only.
+    CvssB,
+    /// Base and threat metrics.
+    CvssBT,
+    /// Base and environmental metrics.
+    CvssBE,
+    /// Base, threat and environmental metrics.
+    CvssBTE,
+}
+
+impl fmt::Display for Nomenclature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::CvssB => "CVSS-B",
+            Self::CvssBT => "CVSS-BT",
+            Self::CvssBE => "CVSS-BE",
+            Self::CvssBTE => "CVSS-BTE",
+        };
+        f.write_str(s)
+    }
+}
+
+/// CVSS v4.0 vector, scored with the MacroVector lookup and interpolation algorithm
+/// of the FIRST reference implementation.
+///
+/// Parsing is strict: the `CVSS:4.0/` prefix and every base metric are mandatory, and unknown,
+/// duplicated or invalid metrics are rejected. The canonical form (see [`fmt::Display`]) lists
+/// metrics in specification order and omits those that are Not Defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CvssV4 {
+    base: BaseMetrics,
+    threat: ThreatMetrics,
+    environmental: EnvironmentalMetrics,
+    supplemental: SupplementalMetrics,
+}
+
+impl CvssV4 {
+    #[must_use]
+    pub const fn new(
+        base: BaseMetrics,
+        threat: ThreatMetrics,
+        environmental: EnvironmentalMetrics,
+        supplemental: SupplementalMetrics,
+    ) -> Self {
+        Self {
+            base,
+            threat,
+            environmental,
+            supplemental,
+        }
+    }
+
+    #[must_use]
+    pub const fn base(&self) -> &BaseMetrics {
+        &self.base
+    }
+
+    #[must_use]
+    pub const fn threat(&self) -> &ThreatMetrics {
+        &self.threat
+    }
+
+    #[must_use]
+    pub const fn environmental(&self) -> &EnvironmentalMetrics {
+        &self.environmental
+    }
+
+    #[must_use]
+    pub const fn supplemental(&self) -> &SupplementalMetrics {
+        &self.supplemental
+    }
+
+    /// Which metric groups contribute to [`Self::score`].
+    #[must_use]
+    pub fn nomenclature(&self) -> Nomenclature {
+        let threat = self.threat != ThreatMetrics::default();
+        let environmental = self.environmental != EnvironmentalMetrics::default();
+        match (threat, environmental) {
+            (false, false) => Nomenclature::CvssB,
+            (true, false) => Nomenclature::CvssBT,
+            (false, true) => Nomenclature::CvssBE,
+            (true, true) => Nomenclature::CvssBTE,
+        }
+    }
+
+    /// Score from 0.0 to 10.0, taking every defined metric group into account.
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        self.effective().score()
+    }
+
+    /// Qualitative severity of [`Self::score`].
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        Severity::from_score(self.score())
+    }
+
+    /// Resolve the value of every scored metric, applying modified metrics and the
+    /// worst-case defaults for Not Defined threat and requirement metrics.
+    fn effective(&self) -> Effective {
+        let b = &self.base;
+        let e = &self.environmental;
+        Effective {
+            av: e.modified_attack_vector.unwrap_or(b.attack_vector),
+            ac: e.modified_attack_complexity.unwrap_or(b.attack_complexity),
+            at: e
+                .modified_attack_requirements
+                .unwrap_or(b.attack_requirements),
+            pr: e
+                .modified_privileges_required
+                .unwrap_or(b.privileges_required),
+            ui: e.modified_user_interaction.unwrap_or(b.user_interaction),
+            vc: e
+                .modified_vulnerable_confidentiality
+                .unwrap_or(b.vulnerable_confidentiality),
+            vi: e
+                .modified_vulnerable_integrity
+                .unwrap_or(b.vulnerable_integrity),
+            va: e
+                .modified_vulnerable_availability
+                .unwrap_or(b.vulnerable_availability),
+            sc: e
+                .modified_subsequent_confidentiality
+                .unwrap_or(b.subsequent_confidentiality),
+            si: e
+                .modified_subsequent_integrity
+                .unwrap_or_else(|| b.subsequent_integrity.into()),
+            sa: e
+                .modified_subsequent_availability
+                .unwrap_or_else(|| b.subsequent_availability.into()),
+            e: self
+                .threat
+                .exploit_maturity
+                .unwrap_or(ExploitMaturity::Attacked),
+            cr: e.confidentiality_requirement.unwrap_or(Requirement::High),
+            ir: e.integrity_requirement.unwrap_or(Requirement::High),
+            ar: e.availability_requirement.unwrap_or(Requirement::High),
+        }
+    }
+}
+
+/// Metric values actually used for scoring.
+struct Effective {
+    av: AttackVector,
+    ac: AttackComplexity,
+    at: AttackRequirements,
+    pr: PrivilegesRequired,
+    ui: UserInteraction,
+    vc: Impact,
+    vi: Impact,
+    va: Impact,
+    sc: Impact,
+    si: SubsequentImpact,
+    sa: SubsequentImpact,
+    e: ExploitMaturity,
+    cr: Requirement,
+    ir: Requirement,
+    ar: Requirement,
+}
+
+/// Highest severity vectors of each EQ1 level, as (AV, PR, UI).
+const EQ1_MAX: [&[(AttackVector, PrivilegesRequired, UserInteraction)]; 3] = [
+    &[(
+        AttackVector::Network,
+        PrivilegesRequired::None,
+        UserInteraction::None,
+    )],
+    &[
+        (
+            AttackVector::Adjacent,
+            PrivilegesRequired::None,
+            UserInteraction::None,
+        ),
+        (
+            AttackVector::Network,
+            PrivilegesRequired::Low,
+            UserInteraction::None,
+        ),
+        (
+            AttackVector::Network,
+            PrivilegesRequired::None,
+            UserInteraction::Passive,
+        ),
+    ],
+    &[
+        (
+            AttackVector::Physical,
+            PrivilegesRequired::None,
+            UserInteraction::None,
+        ),
+        (
+            AttackVector::Adjacent,
+            PrivilegesRequired::Low,
+            UserInteraction::Passive,
+        ),
+    ],
+];
+
+/// Highest severity vectors of each EQ2 level, as (AC, AT).
+const EQ2_MAX: [&[(AttackComplexity, AttackRequirements)]; 2] = [
+    &[(AttackComplexity::Low, AttackRequirements::None)],
+    &[
+        (AttackComplexity::High, AttackRequirements::None),
+        (AttackComplexity::Low, AttackRequirements::Present),
+    ],
+];
+
+/// Highest severity vectors of each EQ4 level, as (SC, SI, SA).
+const EQ4_MAX: [(Impact, SubsequentImpact, SubsequentImpact); 3] = [
+    (
+        Impact::High,
+        SubsequentImpact::Safety,
+        SubsequentImpact::Safety,
+    ),
+    (Impact::High, SubsequentImpact::High, SubsequentImpact::High),
+    (Impact::Low, SubsequentImpact::Low, SubsequentImpact::Low),
+];
+
+/// Depth of each EQ level, in tenths of severity distance.
+const EQ1_DEPTH: [u8; 3] = [1, 4, 5];
+const EQ2_DEPTH: [u8; 2] = [1, 2];
+const EQ4_DEPTH: [u8; 3] = [6, 5, 4];
+const EQ5_DEPTH: u8 = 1;
+
+type Eq3Eq6Vector = (
+    Impact,
+    Impact,
+    Impact,
+    Requirement,
+    Requirement,
+    Requirement,
+);
+
+/// Highest severity vectors of each joint EQ3/EQ6 level, as (VC, VI, VA, CR, IR, AR).
+fn eq3eq6_max(eq3: u8, eq6: u8) -> &'static [Eq3Eq6Vector] {
+    use Impact::{High as H, Low as L};
+    use Requirement::{High as RH, Medium as RM};
+    match (eq3, eq6) {
+        (0, 0) => &[(H, H, H, RH, RH, RH)],
+        (0, _) => &[(H, H, L, RM, RM, RH), (H, H, H, RM, RM, RM)],
+        (1, 0) => &[(L, H, H, RH, RH, RH), (H, L, H, RH, RH, RH)],
+        (1, _) => &[
+            (L, H, H, RH, RM, RM),
+            (L, H, L, RH, RM, RH),
+            (H, L, H, RM, RH, RM),
+            (H, L, L, RM, RH, RH),
+            (L, L, H, RH, RH, RM),
+        ],
+        _ => &[(L, L, L, RH, RH, RH)],
+    }
+}
+
+const fn eq3eq6_depth(eq3: u8, eq6: u8) -> u8 {
+    match (eq3, eq6) {
+        (0, 0) => 7,
+        (0, _) => 6,
+        (1, _) => 8,
+        _ => 10,
+    }
+}
+
+const fn av_level(v: AttackVector) -> i32 {
+    match v {
+        AttackVector::Network => 0,
+        AttackVector::Adjacent => 1,
+        AttackVector::Local => 2,
+        AttackVector::Physical => 3,
+    }
+}
+
+const fn pr_level(v: PrivilegesRequired) -> i32 {
+    match v {
+        PrivilegesRequired::None => 0,
+        PrivilegesRequired::Low => 1,
+        PrivilegesRequired::High => 2,
+    }
+}
+
+const fn ui_level(v: UserInteraction) -> i32 {
+    match v {
+        UserInteraction::None => 0,
+        UserInteraction::Passive => 1,
+        UserInteraction::Active => 2,
+    }
+}
+
+const fn ac_level(v: AttackComplexity) -> i32 {
+    match v {
+        AttackComplexity::Low => 0,
+        AttackComplexity::High => 1,
+    }
+}
+
+const fn at_level(v: AttackRequirements) -> i32 {
+    match v {
+        AttackRequirements::None => 0,
+        AttackRequirements::Present => 1,
+    }
+}
+
+const fn vulnerable_level(v: Impact) -> i32 {
+    match v {
+        Impact::High => 0,
+        Impact::Low => 1,
+        Impact::None => 2,
+    }
+}
+
+const fn subsequent_level(v: SubsequentImpact) -> i32 {
+    match v {
+        SubsequentImpact::Safety => 0,
+        SubsequentImpact::High => 1,
+        SubsequentImpact::Low => 2,
+        SubsequentImpact::None => 3,
+    }
+}
+
+const fn requirement_level(v: Requirement) -> i32 {
+    match v {
+        Requirement::High => 0,
+        Requirement::Medium => 1,
+        Requirement::Low => 2,
+    }
+}
+
+impl Effective {
+    /// Equivalence class levels `[EQ1, EQ2, EQ3, EQ4, EQ5, EQ6]`.
+    fn macrovector(&self) -> [u8; 6] {
+        let eq1 = match (self.av, self.pr, self.ui) {
+            (AttackVector::Network, PrivilegesRequired::None, UserInteraction::None) => 0,
+            (AttackVector::Physical, _, _) => 2,
+            (AttackVector::Network, _, _)
+            | (_, PrivilegesRequired::None, _)
+            | (_, _, UserInteraction::None) => 1,
+            _ => 2,
+        };
+
+        let eq2 = match (self.ac, self.at) {
+            (AttackComplexity::Low, AttackRequirements::None) => 0,
+            _ => 1,
+        };
+
+        let eq3 = match (self.vc, self.vi, self.va) {
+            (Impact::High, Impact::High, _) => 0,
+            (Impact::High, _, _) | (_, Impact::High, _) | (_, _, Impact::High) => 1,
+            _ => 2,
+        };
+
+        let eq4 = if self.si == SubsequentImpact::Safety || self.sa == SubsequentImpact::Safety {
+            0
+        } else if self.sc == Impact::High
+            || self.si == SubsequentImpact::High
+            || self.sa == SubsequentImpact::High
+        {
+            1
+        } else {
+            2
+        };
+
+        let eq5 = match self.e {
+            ExploitMaturity::Attacked => 0,
+            ExploitMaturity::ProofOfConcept => 1,
+            ExploitMaturity::Unreported => 2,
+        };
+
+        let eq6 = u8::from(
+            !((self.cr == Requirement::High && self.vc == Impact::High)
+                || (self.ir == Requirement::High && self.vi == Impact::High)
+                || (self.ar == Requirement::High && self.va == Impact::High)),
+        );
+
+        [eq1, eq2, eq3, eq4, eq5, eq6]
+    }
+
+    fn score(&self) -> f64 {
+        let no_impact = [self.vc, self.vi, self.va, self.sc]
+            .iter()
+            .all(|i| *i == Impact::None)
+            && self.si == SubsequentImpact::None
+            && self.sa == SubsequentImpact::None;
+        if no_impact {
+            return 0.0;
+        }
+
+        let eq = self.macrovector();
+        let [eq1, eq2, eq3, eq4, _, eq6] = eq;
+        let Some(value) = macrovector_score(eq) else {
+            return 0.0;
+        };
+
+        let lower = |steps: [u8; 6]| {
+            let mut next = eq;
+            for (level, step) in next.iter_mut().zip(steps) {
+                *level += step;
+            }
+            macrovector_score(next)
+        };
+        let eq3eq6_lower = match (eq3, eq6) {
+            (0, 0) => match (lower([0, 0, 0, 0, 0, 1]), lower([0, 0, 1, 0, 0, 0])) {
+                (Some(left), Some(right)) => Some(left.max(right)),
+                (left, right) => left.or(right),
+            },
+            (1, 0) => lower([0, 0, 0, 0, 0, 1]),
+            (_, 1) if eq3 < 2 => lower([0, 0, 1, 0, 0, 0]),
+            _ => lower([0, 0, 1, 0, 0, 1]),
+        };
+
+        // Severity distance (in tenths) between the vector and the highest severity vector
+        // of its MacroVector, for each equivalence class.
+        let eq1_distance = first_reachable(EQ1_MAX[usize::from(eq1)], |(av, pr, ui)| {
+            [
+                av_level(self.av) - av_level(*av),
+                pr_level(self.pr) - pr_level(*pr),
+                ui_level(self.ui) - ui_level(*ui),
+            ]
+        });
+        let eq2_distance = first_reachable(EQ2_MAX[usize::from(eq2)], |(ac, at)| {
+            [
+                ac_level(self.ac) - ac_level(*ac),
+                at_level(self.at) - at_level(*at),
+            ]
+        });
+        let eq3eq6_distance = first_reachable(eq3eq6_max(eq3, eq6), |(vc, vi, va, cr, ir, ar)| {
+            [
+                vulnerable_level(self.vc) - vulnerable_level(*vc),
+                vulnerable_level(self.vi) - vulnerable_level(*vi),
+                vulnerable_level(self.va) - vulnerable_level(*va),
+                requirement_level(self.cr) - requirement_level(*cr),
+                requirement_level(self.ir) - requirement_level(*ir),
+                requirement_level(self.ar) - requirement_level(*ar),
+            ]
+        });
+        let eq4_distance = first_reachable(
+            &EQ4_MAX[usize::from(eq4)..=usize::from(eq4)],
+            |(sc, si, sa)| {
+                [
+                    subsequent_level(self.sc.into()) - subsequent_level((*sc).into()),
+                    subsequent_level(self.si) - subsequent_level(*si),
+                    subsequent_level(self.sa) - subsequent_level(*sa),
+                ]
+            },
+        );
+
+        let classes = [
+            (
+                lower([1, 0, 0, 0, 0, 0]),
+                eq1_distance,
+                EQ1_DEPTH[usize::from(eq1)],
+            ),
+            (
+                lower([0, 1, 0, 0, 0, 0]),
+                eq2_distance,
+                EQ2_DEPTH[usize::from(eq2)],
+            ),
+            (eq3eq6_lower, eq3eq6_distance, eq3eq6_depth(eq3, eq6)),
+            (
+                lower([0, 0, 0, 1, 0, 0]),
+                eq4_distance,
+                EQ4_DEPTH[usize::from(eq4)],
+            ),
+            (lower([0, 0, 0, 0, 1, 0]), 0, EQ5_DEPTH),
+        ];
+
+        // Each class with a lower MacroVector moves the score towards it, proportionally to
+        // how far the vector is from the highest severity vector of its own MacroVector.
+        let (total, existing) = classes
+            .iter()
+            .filter_map(|(lower, distance, depth)| {
+                lower.map(|lower| (value - lower) * f64::from(*distance) / f64::from(*depth))
+            })
+            .fold((0.0, 0u8), |(total, n), normalized| {
+                (total + normalized, n + 1)
+            });
+
+        let mean_distance = if existing == 0 {
+            0.0
+        } else {
+            total / f64::from(existing)
+        };
+
+        round_to_one_decimal((value - mean_distance).clamp(0.0, 10.0))
+    }
+}
+
+fn macrovector_score(eq: [u8; 6]) -> Option<f64> {
+    let key: String = eq.iter().map(u8::to_string).collect();
+    MACROVECTOR_SCORES
+        .binary_search_by_key(&key.as_str(), |(k, _)| k)
+        .ok()
+        .map(|i| MACROVECTOR_SCORES[i].1)
+}
+
+/// Total severity distance to the first candidate vector that is not less severe than the
+/// scored vector, i.e. whose every per-metric distance is non-negative.
+fn first_reachable<T, const N: usize>(candidates: &[T], distances: impl Fn(&T) -> [i32; N]) -> i32 {
+    let all: Vec<[i32; N]> = candidates.iter().map(distances).collect();
+    all.iter()
+        .find(|d| d.iter().all(|v| *v >= 0))
+        .or_else(|| all.last())
+        .map_or(0, |d| d.iter().sum())
+}
+
+/// Round half up to one decimal, with the epsilon used by the reference implementation.
+fn round_to_one_decimal(value: f64) -> f64 {
+    ((value + 1e-6) * 10.0).round() / 10.0
+}
+
+impl FromStr for CvssV4 {
+    type Err = CvssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut m = VectorMetrics::parse(s, PREFIX)?;
+
+        let base = BaseMetrics {
+            attack_vector: m.required("AV", AttackVector::parse)?,
+            attack_complexity: m.required("AC", AttackComplexity::parse)?,
+            attack_requirements: m.required("AT", AttackRequirements::parse)?,
+            privileges_required: m.required("PR", PrivilegesRequired::parse)?,
+            user_interaction: m.required("UI", UserInteraction::parse)?,
+            vulnerable_confidentiality: m.required("VC", Impact::parse)?,
+            vulnerable_integrity: m.required("VI", Impact::parse)?,
+            vulnerable_availability: m.required("VA", Impact::parse)?,
+            subsequent_confidentiality: m.required("SC", Impact::parse)?,
+            subsequent_integrity: m.required("SI", Impact::parse)?,
+            subsequent_availability: m.required("SA", Impact::parse)?,
+        };
+
+        let threat = ThreatMetrics {
+            exploit_maturity: m.optional("E", ExploitMaturity::parse)?,
+        };
+
+        let environmental = EnvironmentalMetrics {
+            confidentiality_requirement: m.optional("CR", Requirement::parse)?,
+            integrity_requirement: m.optional("IR", Requirement::parse)?,
+            availability_requirement: m.optional("AR", Requirement::parse)?,
+            modified_attack_vector: m.optional("MAV", AttackVector::parse)?,
+            modified_attack_complexity: m.optional("MAC", AttackComplexity::parse)?,
+            modified_attack_requirements: m.optional("MAT", AttackRequirements::parse)?,
+            modified_privileges_required: m.optional("MPR", PrivilegesRequired::parse)?,
+            modified_user_interaction: m.optional("MUI", UserInteraction::parse)?,
+            modified_vulnerable_confidentiality: m.optional("MVC", Impact::parse)?,
+            modified_vulnerable_integrity: m.optional("MVI", Impact::parse)?,
+            modified_vulnerable_availability: m.optional("MVA", Impact::parse)?,
+            modified_subsequent_confidentiality: m.optional("MSC", Impact::parse)?,
+            modified_subsequent_integrity: m.optional("MSI", SubsequentImpact::parse)?,
+            modified_subsequent_availability: m.optional("MSA", SubsequentImpact::parse)?,
+        };
+
+        let supplemental = SupplementalMetrics {
+            safety: m.optional("S", Safety::parse)?,
+            automatable: m.optional("AU", Automatable::parse)?,
+            recovery: m.optional("R", Recovery::parse)?,
+            value_density: m.optional("V", ValueDensity::parse)?,
+            response_effort: m.optional("RE", ResponseEffort::parse)?,
+            provider_urgency: m.optional("U", ProviderUrgency::parse)?,
+        };
+
+        m.finish()?;
+        Ok(Self::new(base, threat, environmental, supplemental))
+    }
+}
+
+impl fmt::Display for CvssV4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = &self.base;
+        write!(
+            f,
+            "{PREFIX}/AV:{}/AC:{}/AT:{}/PR:{}/UI:{}/VC:{}/VI:{}/VA:{}/SC:{}/SI:{}/SA:{}",
+            b.attack_vector.abbreviation(),
+            b.attack_complexity.abbreviation(),
+            b.attack_requirements.abbreviation(),
+            b.privileges_required.abbreviation(),
+            b.user_interaction.abbreviation(),
+            b.vulnerable_confidentiality.abbreviation(),
+            b.vulnerable_integrity.abbreviation(),
+            b.vulnerable_availability.abbreviation(),
+            b.subsequent_confidentiality.abbreviation(),
+            b.subsequent_integrity.abbreviation(),
+            b.subsequent_availability.abbreviation(),
+        )?;
+
+        let e = &self.environmental;
+        let s = &self.supplemental;
+        let optional = [
+            (
+                "E",
+                self.threat
+                    .exploit_maturity
+                    .map(ExploitMaturity::abbreviation),
+            ),
+            (
+                "CR",
+                e.confidentiality_requirement.map(Requirement::abbreviation),
+            ),
+            ("IR", e.integrity_requirement.map(Requirement::abbreviation)),
+            (
+                "AR",
+                e.availability_requirement.map(Requirement::abbreviation),
+            ),
+            (
+                "MAV",
+                e.modified_attack_vector.map(AttackVector::abbreviation),
+            ),
+            (
+                "MAC",
+                e.modified_attack_complexity
+                    .map(AttackComplexity::abbreviation),
+            ),
+            (
+                "MAT",
+                e.modified_attack_requirements
+                    .map(AttackRequirements::abbreviation),
+            ),
+            (
+                "MPR",
+                e.modified_privileges_required
+                    .map(PrivilegesRequired::abbreviation),
+            ),
+            (
+                "MUI",
+                e.modified_user_interaction
+                    .map(UserInteraction::abbreviation),
+            ),
+            (
+                "MVC",
+                e.modified_vulnerable_confidentiality
+                    .map(Impact::abbreviation),
+            ),
+            (
+                "MVI",
+                e.modified_vulnerable_integrity.map(Impact::abbreviation),
+            ),
+            (
+                "MVA",
+                e.modified_vulnerable_availability.map(Impact::abbreviation),
+            ),
+            (
+                "MSC",
+                e.modified_subsequent_confidentiality
+                    .map(Impact::abbreviation),
+            ),
+            (
+                "MSI",
+                e.modified_subsequent_integrity
+                    .map(SubsequentImpact::abbreviation),
+            ),
+            (
+                "MSA",
+                e.modified_subsequent_availability
+                    .map(SubsequentImpact::abbreviation),
+            ),
+            ("S", s.safety.map(Safety::abbreviation)),
+            ("AU", s.automatable.map(Automatable::abbreviation)),
+            ("R", s.recovery.map(Recovery::abbreviation)),
+            ("V", s.value_density.map(ValueDensity::abbreviation)),
+            ("RE", s.response_effort.map(ResponseEffort::abbreviation)),
+            ("U", s.provider_urgency.map(ProviderUrgency::abbreviation)),
+        ];
+
+        for (key, value) in optional {
+            if let Some(value) = value {
+                write!(f, "/{key}:{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cvss(vector: &str) -> CvssV4 {
+        CvssV4::from_str(vector).unwrap()
+    }
+
+    #[test]
+    fn lookup_table_is_sorted_and_complete() {
+        assert!(MACROVECTOR_SCORES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(macrovector_score([0, 0, 0, 0, 0, 0]), Some(10.0));
+        assert_eq!(macrovector_score([2, 1, 2, 2, 2, 1]), Some(0.1));
+        assert_eq!(macrovector_score([0, 0, 2, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn scores_match_reference_calculator() {
+        let examples = [
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:H/SI:H/SA:H",
+                10.0,
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                9.3,
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:P/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                9.2,
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.7,
+            ),
+            (
+                "CVSS:4.0/AV:L/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N",
+                8.5,
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:N/VA:N/SC:N/SI:N/SA:N",
+                8.7,
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/E:U",
+                8.1,
+            ),
+        ];
+
+        for (vector, expected) in examples {
+            assert_eq!(cvss(vector).score(), expected, "{vector}");
+        }
+    }
+
+    #[test]
+    fn environmental_requirements_match_reference_calculator() {
+        let examples = [(
+            "CVSS:4.0/AV:N/AC:L/AT:P/PR:N/UI:P/VC:H/VI:L/VA:H/SC:L/SI:L/SA:L/CR:M/IR:M/AR:L",
+            5.0,
+        )];
+        for (vector, expected) in examples {
+            assert_eq!(cvss(vector).score(), expected, "{vector}");
+        }
+
+        // Undefined requirements default to High.
+        let base = "CVSS:4.0/AV:N/AC:L/AT:P/PR:N/UI:P/VC:H/VI:L/VA:H/SC:L/SI:L/SA:L";
+        for requirements in ["/CR:H/IR:H/AR:H", "/CR:X/IR:X/AR:X"] {
+            let vector = format!("{base}{requirements}");
+            assert_eq!(cvss(&vector).score(), cvss(base).score(), "{vector}");
+        }
+    }
+
+    #[test]
+    fn highest_vectors_of_each_eq3_eq6_level_belong_to_it() {
+        for (eq3, eq6) in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 1)] {
+            for (vc, vi, va, cr, ir, ar) in eq3eq6_max(eq3, eq6) {
+                let vector = format!(
+                    "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:{}/VI:{}/VA:{}/SC:H/SI:H/SA:H/CR:{}/IR:{}/AR:{}",
+                    vc.abbreviation(),
+                    vi.abbreviation(),
+                    va.abbreviation(),
+                    cr.abbreviation(),
+                    ir.abbreviation(),
+                    ar.abbreviation(),
+                );
+                let v = cvss(&vector);
+                let eq = v.effective().macrovector();
+                assert_eq!((eq[2], eq[5]), (eq3, eq6), "{vector}");
+                // A highest vector is at distance zero from its macrovector.
+                assert_eq!(Some(v.score()), macrovector_score(eq), "{vector}");
+            }
+        }
+    }
+
+    #[test]
+    fn no_impact_should_score_zero() {
+        let v = cvss("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:N/VI:N/VA:N/SC:N/SI:N/SA:N");
+        assert_eq!(v.score(), 0.0);
+        assert_eq!(v.severity(), Severity::None);
+    }
+
+    #[test]
+    fn environmental_metrics_override_base_metrics() {
+        let base = cvss("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N");
+        let local =
+            cvss("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/MAV:L/MPR:L");
+        let explicit = cvss("CVSS:4.0/AV:L/AC:L/AT:N/PR:L/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N");
+        assert!(local.score() < base.score());
+        assert_eq!(local.score(), explicit.score());
+
+        let safety = cvss("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/MSI:S");
+        assert_eq!(safety.score(), 10.0);
+    }
+
+    #[test]
+    fn supplemental_metrics_do_not_alter_score() {
+        let base = cvss("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N");
+        let supplemental = cvss(
+            "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/S:P/AU:Y/R:I/V:C/RE:H/U:Red",
+        );
+        assert_eq!(supplemental.score(), base.score());
+        assert_eq!(supplemental.nomenclature(), Nomenclature::CvssB);
+    }
+
+    #[test]
+    fn nomenclature_reflects_defined_groups() {
+        let base = "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N";
+        assert_eq!(cvss(base).nomenclature(), Nomenclature::CvssB);
+        assert_eq!(
+            cvss(&format!("{base}/E:P")).nomenclature(),
+            Nomenclature::CvssBT
+        );
+        assert_eq!(
+            cvss(&format!("{base}/CR:L")).nomenclature(),
+            Nomenclature::CvssBE
+        );
+        assert_eq!(
+            cvss(&format!("{base}/E:P/MAV:A")).nomenclature(),
+            Nomenclature::CvssBTE
+        );
+        assert_eq!(
+            cvss(&format!("{base}/E:X")).nomenclature(),
+            Nomenclature::CvssB
+        );
+        assert_eq!(Nomenclature::CvssBTE.to_string(), "CVSS-BTE");
+    }
+
+    #[test]
+    fn display_is_canonical_and_roundtrips() {
+        let v = cvss(
+            "CVSS:4.0/U:Amber/VC:H/VI:L/VA:N/SC:N/SI:N/SA:N/AV:A/AC:H/AT:P/PR:L/UI:A/MSI:S/E:P/CR:X",
+        );
+        let canonical =
+            "CVSS:4.0/AV:A/AC:H/AT:P/PR:L/UI:A/VC:H/VI:L/VA:N/SC:N/SI:N/SA:N/E:P/MSI:S/U:Amber";
+        assert_eq!(v.to_string(), canonical);
+        assert_eq!(cvss(canonical), v);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_vectors() {
+        let base = "AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N";
+        let cases = [
+            (
+                format!("CVSS:3.1/{base}"),
+                CvssError::UnsupportedVersion("CVSS:3.1".into()),
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N".to_string(),
+                CvssError::MissingMetric("SA".into()),
+            ),
+            (
+                format!("CVSS:4.0/{base}/E:A/E:P"),
+                CvssError::DuplicateMetric("E".into()),
+            ),
+            (
+                format!("CVSS:4.0/{base}/SI:S"),
+                CvssError::DuplicateMetric("SI".into()),
+            ),
+            (
+                "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:S/SA:N".to_string(),
+                CvssError::InvalidValue("SI".into(), "S".into()),
+            ),
+            (
+                format!("CVSS:4.0/{base}/U:red"),
+                CvssError::InvalidValue("U".into(), "red".into()),
+            ),
+            (
+                format!("CVSS:4.0/{base}/RL:O"),
+                CvssError::UnknownMetric("RL".into()),
+            ),
+        ];
+
+        for (vector, expected) in cases {
+            assert_eq!(CvssV4::from_str(&vector).unwrap_err(), expected, "{vector}");
+        }
+    }
+}
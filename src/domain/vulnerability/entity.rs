@@ -0,0 +1,398 @@
+use crate::domain::component::purl::PackageUrl;
+use crate::domain::vulnerability::cvss::Cvss;
+use crate::domain::vulnerability::event::VulnerabilityEvent;
+use crate::domain::vulnerability::id::VulnerabilityId;
+use crate::domain::vulnerability::version::{VersionError, VersionScheme};
+use std::cmp::Ordering;
+use std::fmt;
+use url::Url;
+
+/// A vulnerability as published by an external database, such as an OSV record.
+///
+/// Read-only in the domain: it is replaced as a whole whenever its source publishes new data,
+/// and [`Vulnerability::changes`] tells which event such a replacement amounts to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vulnerability {
+    id: VulnerabilityId,
+    summary: Option<String>,
+    published: Option<String>,
+    modified: Option<String>,
+    withdrawn: Option<String>,
+    cvss: Vec<Cvss>,
+    affected: Vec<AffectedPackage>,
+    references: Vec<Reference>,
+}
+
+impl Vulnerability {
+    #[must_use]
+    pub const fn new(id: VulnerabilityId) -> Self {
+        Self {
+            id,
+            summary: None,
+            published: None,
+            modified: None,
+            withdrawn: None,
+            cvss: Vec::new(),
+            affected: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Record the publication, last modification and withdrawal timestamps of the source,
+    /// kept as published (RFC 3339).
+    #[must_use]
+    pub fn with_dates(
+        mut self,
+        published: Option<String>,
+        modified: Option<String>,
+        withdrawn: Option<String>,
+    ) -> Self {
+        self.published = published;
+        self.modified = modified;
+        self.withdrawn = withdrawn;
+        self
+    }
+
+    /// Add CVSS vectors, ignoring those already known.
+    #[must_use]
+    pub fn with_cvss(mut self, cvss: impl IntoIterator<Item = Cvss>) -> Self {
+        extend_unique(&mut self.cvss, cvss);
+        self
+    }
+
+    #[must_use]
+    pub fn with_affected(mut self, affected: impl IntoIterator<Item = AffectedPackage>) -> Self {
+        extend_unique(&mut self.affected, affected);
+        self
+    }
+
+    #[must_use]
+    pub fn with_references(mut self, references: impl IntoIterator<Item = Reference>) -> Self {
+        extend_unique(&mut self.references, references);
+        self
+    }
+
+    /// Combine two records published for the same vulnerability, such as a GHSA and a PYSEC
+    /// advisory aliasing the same CVE.
+    ///
+    /// Identifiers, vectors, affected packages and references are united; the most recent
+    /// modification wins, and the vulnerability is withdrawn only if both records are.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        let withdrawn = match (self.withdrawn, other.withdrawn) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+        Self {
+            id: self.id.with_aliases(other.id.ids().cloned()),
+            summary: self.summary.or(other.summary),
+            published: min_date(self.published, other.published),
+            modified: self.modified.max(other.modified),
+            withdrawn,
+            cvss: self.cvss,
+            affected: self.affected,
+            references: self.references,
+        }
+        .with_cvss(other.cvss)
+        .with_affected(other.affected)
+        .with_references(other.references)
+    }
+
+    /// Event recording the replacement of `previous` by this data, if anything changed.
+    #[must_use]
+    pub fn changes(&self, previous: Option<&Self>) -> Option<VulnerabilityEvent> {
+        match previous {
+            None => Some(VulnerabilityEvent::VulnerabilityRegistered {
+                vulnerability_id: self.id.clone(),
+                cvss: self.cvss.clone(),
+            }),
+            Some(previous) if previous == self && previous.id.aliases() == self.id.aliases() => {
+                None
+            }
+            Some(previous) => Some(VulnerabilityEvent::VulnerabilityUpdated {
+                vulnerability_id: self.id.clone(),
+                previous_cvss: previous.cvss.clone(),
+                cvss: self.cvss.clone(),
+            }),
+        }
+    }
+
+    // Accessors
+
+    /// Identifier of the vulnerability, carrying the aliases it is published under.
+    #[must_use]
+    pub const fn id(&self) -> &VulnerabilityId {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+
+    #[must_use]
+    pub fn published(&self) -> Option<&str> {
+        self.published.as_deref()
+    }
+
+    #[must_use]
+    pub fn modified(&self) -> Option<&str> {
+        self.modified.as_deref()
+    }
+
+    /// Withdrawal timestamp, set when the source retracted the vulnerability.
+    #[must_use]
+    pub fn withdrawn(&self) -> Option<&str> {
+        self.withdrawn.as_deref()
+    }
+
+    #[must_use]
+    pub fn cvss(&self) -> &[Cvss] {
+        &self.cvss
+    }
+
+    #[must_use]
+    pub fn affected(&self) -> &[AffectedPackage] {
+        &self.affected
+    }
+
+    #[must_use]
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+}
+
+/// A package affected by a vulnerability, with the versions it affects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedPackage {
+    ecosystem: String,
+    name: String,
+    purl: Option<PackageUrl>,
+    ranges: Vec<AffectedRange>,
+    versions: Vec<String>,
+}
+
+impl AffectedPackage {
+    /// Package `name` of the given ecosystem, as named by OSV (e.g. `PyPI`, `Debian:12`).
+    #[must_use]
+    pub fn new(ecosystem: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            ecosystem: ecosystem.into(),
+            name: name.into(),
+            purl: None,
+            ranges: Vec::new(),
+            versions: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_purl(mut self, purl: PackageUrl) -> Self {
+        self.purl = Some(purl);
+        self
+    }
+
+    #[must_use]
+    pub fn with_ranges(mut self, ranges: impl IntoIterator<Item = AffectedRange>) -> Self {
+        self.ranges.extend(ranges);
+        self
+    }
+
+    /// Add versions listed as affected one by one, next to or instead of ranges.
+    #[must_use]
+    pub fn with_versions<I: IntoIterator<Item: Into<String>>>(mut self, versions: I) -> Self {
+        self.versions.extend(versions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether `version` of the package is affected, being listed or within one of its
+    /// `SEMVER` or `ECOSYSTEM` ranges. `GIT` ranges are ignored, as they name commits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VersionError::UnsupportedEcosystem`] if the package has `ECOSYSTEM` ranges
+    /// in an ecosystem without [`VersionScheme`], and [`VersionError::Invalid`] if a version
+    /// cannot be parsed.
+    pub fn affects(&self, version: &str) -> Result<bool, VersionError> {
+        let scheme = VersionScheme::for_ecosystem(&self.ecosystem);
+        let listed = self.versions.iter().any(|listed| {
+            listed == version
+                || scheme
+                    .is_some_and(|scheme| scheme.compare(listed, version) == Ok(Ordering::Equal))
+        });
+        if listed {
+            return Ok(true);
+        }
+
+        for range in &self.ranges {
+            if let Some(scheme) = VersionScheme::for_range(range.kind, &self.ecosystem)?
+                && scheme.contains(&range.events, version)?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    #[must_use]
+    pub fn ecosystem(&self) -> &str {
+        &self.ecosystem
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub const fn purl(&self) -> Option<&PackageUrl> {
+        self.purl.as_ref()
+    }
+
+    #[must_use]
+    pub fn ranges(&self) -> &[AffectedRange] {
+        &self.ranges
+    }
+
+    #[must_use]
+    pub fn versions(&self) -> &[String] {
+        &self.versions
+    }
+}
+
+/// How the versions of an [`AffectedRange`] are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeKind {
+    /// Semantic versions, whatever the ecosystem.
+    Semver,
+    /// Versions ordered by the rules of the ecosystem of the package.
+    Ecosystem,
+    /// Commit hashes of a git repository.
+    Git,
+}
+
+impl fmt::Display for RangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Semver => "SEMVER",
+            Self::Ecosystem => "ECOSYSTEM",
+            Self::Git => "GIT",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Boundary of an affected range, in the order given by the source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RangeEvent {
+    /// The vulnerability appears in this version; `0` stands for all earlier versions.
+    Introduced(String),
+    /// First version no longer affected.
+    Fixed(String),
+    /// Last version known to be affected, when no fix is known.
+    LastAffected(String),
+    /// Upper bound beyond which versions are out of the range, affected or not.
+    Limit(String),
+}
+
+impl RangeEvent {
+    /// Version the event occurs at.
+    #[must_use]
+    pub fn version(&self) -> &str {
+        match self {
+            Self::Introduced(version)
+            | Self::Fixed(version)
+            | Self::LastAffected(version)
+            | Self::Limit(version) => version,
+        }
+    }
+}
+
+/// Versions of a package affected by a vulnerability, delimited by a series of events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedRange {
+    kind: RangeKind,
+    repository: Option<String>,
+    events: Vec<RangeEvent>,
+}
+
+impl AffectedRange {
+    #[must_use]
+    pub fn new(kind: RangeKind, events: impl IntoIterator<Item = RangeEvent>) -> Self {
+        Self {
+            kind,
+            repository: None,
+            events: events.into_iter().collect(),
+        }
+    }
+
+    /// Record the repository the commits of a [`RangeKind::Git`] range belong to.
+    #[must_use]
+    pub fn with_repository(mut self, repository: impl Into<String>) -> Self {
+        self.repository = Some(repository.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn kind(&self) -> RangeKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn repository(&self) -> Option<&str> {
+        self.repository.as_deref()
+    }
+
+    #[must_use]
+    pub fn events(&self) -> &[RangeEvent] {
+        &self.events
+    }
+}
+
+/// Link to further information about a vulnerability.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reference {
+    kind: String,
+    url: Url,
+}
+
+impl Reference {
+    /// Reference of the given kind, as named by OSV (e.g. `ADVISORY`, `FIX`, `WEB`).
+    #[must_use]
+    pub fn new(kind: impl Into<String>, url: Url) -> Self {
+        Self {
+            kind: kind.into(),
+            url,
+        }
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    #[must_use]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+fn extend_unique<T: PartialEq>(items: &mut Vec<T>, new: impl IntoIterator<Item = T>) {
+    for item in new {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+}
+
+fn min_date(a: Option<String>, b: Option<String>) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
@@ -0,0 +1,84 @@
+use crate::domain::vulnerability::classification::Classification;
+use crate::domain::vulnerability::cvss::Cvss;
+use crate::domain::vulnerability::id::{ManagedVulnerabilityId, VulnerabilityId};
+
+/// Domain events emitted by the `ManagedVulnerability` aggregate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManagedVulnerabilityEvent {
+    /// A vulnerability has started being tracked for a component.
+    ManagedVulnerabilityRegistered { id: ManagedVulnerabilityId },
+
+    /// The vulnerability was classified based on the component context and CVSS data.
+    ///
+    /// Emitted both for the first classification and for reclassifications.
+    ManagedVulnerabilityClassified {
+        id: ManagedVulnerabilityId,
+        classification: Classification,
+    },
+
+    /// The vulnerability adopted the classification of a similar, already classified component.
+    ManagedVulnerabilityClassificationInherited {
+        id: ManagedVulnerabilityId,
+        classification: Classification,
+        source: ManagedVulnerabilityId,
+    },
+
+    /// The current classification is no longer valid due to a context or CVSS change.
+    ManagedVulnerabilityClassificationOutdated { id: ManagedVulnerabilityId },
+}
+
+impl ManagedVulnerabilityEvent {
+    #[must_use]
+    pub const fn id(&self) -> &ManagedVulnerabilityId {
+        match self {
+            Self::ManagedVulnerabilityRegistered { id }
+            | Self::ManagedVulnerabilityClassified { id, .. }
+            | Self::ManagedVulnerabilityClassificationInherited { id, .. }
+            | Self::ManagedVulnerabilityClassificationOutdated { id } => id,
+        }
+    }
+}
+
+/// Events emitted when vulnerability data is ingested from external feeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VulnerabilityEvent {
+    /// A new vulnerability was ingested from an external source.
+    VulnerabilityRegistered {
+        vulnerability_id: VulnerabilityId,
+        cvss: Vec<Cvss>,
+    },
+
+    /// An existing vulnerability was updated with new data.
+    VulnerabilityUpdated {
+        vulnerability_id: VulnerabilityId,
+        previous_cvss: Vec<Cvss>,
+        cvss: Vec<Cvss>,
+    },
+}
+
+impl VulnerabilityEvent {
+    #[must_use]
+    pub const fn vulnerability_id(&self) -> &VulnerabilityId {
+        match self {
+            Self::VulnerabilityRegistered {
+                vulnerability_id, ..
+            }
+            | Self::VulnerabilityUpdated {
+                vulnerability_id, ..
+            } => vulnerability_id,
+        }
+    }
+
+    /// Whether the event changes the CVSS vectors known for the vulnerability.
+    #[must_use]
+    pub fn cvss_changed(&self) -> bool {
+        match self {
+            Self::VulnerabilityRegistered { .. } => false,
+            Self::VulnerabilityUpdated {
+                previous_cvss,
+                cvss,
+                ..
+            } => previous_cvss != cvss,
+        }
+    }
+}
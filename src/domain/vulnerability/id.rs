@@ -0,0 +1,532 @@
+use crate::domain::component::id::ComponentId;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Authority or database that issued an advisory identifier.
+///
+/// Declared by preference: when a vulnerability is known under several identifiers,
+/// the one from the first namespace becomes its primary identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum VulnerabilityNamespace {
+    /// Common Vulnerabilities and Exposures, e.g. `CVE-2024-1234`.
+    Cve,
+    /// GitHub Security Advisory, e.g. `GHSA-xvch-5gv4-984h`.
+    Ghsa,
+    /// Python Packaging Advisory Database, e.g. `PYSEC-2024-12`.
+    Pysec,
+    /// Go Vulnerability Database, e.g. `GO-2024-2687`.
+    Go,
+    /// `RustSec` Advisory Database, e.g. `RUSTSEC-2024-0332`.
+    Rustsec,
+    /// Debian Security Advisory, e.g. `DSA-5678-1`.
+    Dsa,
+    /// Debian LTS Advisory, e.g. `DLA-3782-1`.
+    Dla,
+    /// Red Hat Security Advisory, e.g. `RHSA-2024:1234`.
+    Rhsa,
+    /// `AlmaLinux` Security Advisory, e.g. `ALSA-2024:1234`.
+    Alsa,
+    /// Ubuntu Security Notice, e.g. `USN-6543-1`.
+    Usn,
+}
+
+impl VulnerabilityNamespace {
+    /// Prefix of the identifiers issued in this namespace.
+    #[must_use]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Cve => "CVE",
+            Self::Ghsa => "GHSA",
+            Self::Pysec => "PYSEC",
+            Self::Go => "GO",
+            Self::Rustsec => "RUSTSEC",
+            Self::Dsa => "DSA",
+            Self::Dla => "DLA",
+            Self::Rhsa => "RHSA",
+            Self::Alsa => "ALSA",
+            Self::Usn => "USN",
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        [
+            Self::Cve,
+            Self::Ghsa,
+            Self::Pysec,
+            Self::Go,
+            Self::Rustsec,
+            Self::Dsa,
+            Self::Dla,
+            Self::Rhsa,
+            Self::Alsa,
+            Self::Usn,
+        ]
+        .into_iter()
+        .find(|ns| ns.prefix().eq_ignore_ascii_case(prefix))
+    }
+
+    /// Validate and normalize the part of an identifier following `<prefix>-`.
+    fn normalize(self, rest: &str) -> Option<String> {
+        let valid = match self {
+            Self::Cve | Self::Go => dashed_year(rest, 4, usize::MAX),
+            Self::Pysec => dashed_year(rest, 1, usize::MAX),
+            Self::Rustsec => dashed_year(rest, 4, 4),
+            Self::Dsa | Self::Dla | Self::Usn => numbered_revision(rest),
+            Self::Rhsa | Self::Alsa => rest
+                .split_once(':')
+                .is_some_and(|(year, seq)| digits(year, 4, 4) && digits(seq, 4, usize::MAX)),
+            Self::Ghsa => {
+                let parts: Vec<&str> = rest.split('-').collect();
+                parts.len() == 3
+                    && parts.iter().all(|p| {
+                        p.len() == 4
+                            && p.chars()
+                                .all(|c| GHSA_ALPHABET.contains(c.to_ascii_lowercase()))
+                    })
+            }
+        };
+        let rest = match self {
+            Self::Ghsa => rest.to_ascii_lowercase(),
+            _ => rest.to_string(),
+        };
+        valid.then(|| format!("{}-{rest}", self.prefix()))
+    }
+}
+
+impl fmt::Display for VulnerabilityNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.prefix())
+    }
+}
+
+/// Characters used by the three groups of a GHSA identifier.
+const GHSA_ALPHABET: &str = "23456789cfghjmpqrvwx";
+
+fn digits(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// `YYYY-N...`, with a sequence number of `min` to `max` digits.
+fn dashed_year(s: &str, min: usize, max: usize) -> bool {
+    s.split_once('-')
+        .is_some_and(|(year, seq)| digits(year, 4, 4) && digits(seq, min, max))
+}
+
+/// `N...` or `N...-R...`, an advisory number with an optional revision.
+fn numbered_revision(s: &str) -> bool {
+    match s.split_once('-') {
+        Some((number, revision)) => {
+            digits(number, 1, usize::MAX) && digits(revision, 1, usize::MAX)
+        }
+        None => digits(s, 1, usize::MAX),
+    }
+}
+
+/// A single advisory identifier, validated and normalized within its namespace.
+///
+/// Prefixes are upper-cased and GHSA groups lower-cased, following the spelling used by the
+/// issuing databases. Identifiers of a namespace are ordered by their numeric parts, so that
+/// `CVE-2024-9999` sorts before `CVE-2024-10000`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AdvisoryId {
+    namespace: VulnerabilityNamespace,
+    id: String,
+}
+
+impl AdvisoryId {
+    #[must_use]
+    pub const fn namespace(&self) -> VulnerabilityNamespace {
+        self.namespace
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    /// Segments following the prefix, numeric ones compared by value.
+    fn sort_key(&self) -> Vec<(u64, &str)> {
+        self.id[self.namespace.prefix().len() + 1..]
+            .split(['-', ':'])
+            .map(|part| part.parse().map_or((u64::MAX, part), |n| (n, "")))
+            .collect()
+    }
+}
+
+impl FromStr for AdvisoryId {
+    type Err = VulnerabilityIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(VulnerabilityIdError::Empty);
+        }
+
+        let (prefix, rest) = s
+            .split_once('-')
+            .ok_or_else(|| VulnerabilityIdError::UnknownNamespace(s.to_string()))?;
+        let namespace = VulnerabilityNamespace::from_prefix(prefix)
+            .ok_or_else(|| VulnerabilityIdError::UnknownNamespace(s.to_string()))?;
+        let id = namespace
+            .normalize(rest)
+            .ok_or_else(|| VulnerabilityIdError::InvalidFormat(namespace, s.to_string()))?;
+
+        Ok(Self { namespace, id })
+    }
+}
+
+impl Ord for AdvisoryId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.namespace
+            .cmp(&other.namespace)
+            .then_with(|| self.sort_key().cmp(&other.sort_key()))
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl PartialOrd for AdvisoryId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for AdvisoryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+/// Global vulnerability identifier (e.g. `CVE-2024-1234`), with the aliases it is known under.
+///
+/// The same flaw is often published under several identifiers, such as a CVE and a GHSA.
+/// The preferred one according to [`VulnerabilityNamespace`] becomes the primary identifier,
+/// which alone defines equality, hashing and ordering: a vulnerability reported under its CVE
+/// or under a GHSA aliasing that CVE is therefore tracked once per component.
+#[derive(Debug, Clone)]
+pub struct VulnerabilityId {
+    primary: AdvisoryId,
+    aliases: BTreeSet<AdvisoryId>,
+}
+
+impl VulnerabilityId {
+    /// Create a new `VulnerabilityId` from a string-like input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VulnerabilityIdError::Empty`] if the provided ID string is empty,
+    /// [`VulnerabilityIdError::UnknownNamespace`] if its prefix is not recognized, or
+    /// [`VulnerabilityIdError::InvalidFormat`] if it does not follow the format of its namespace.
+    pub fn new(id: impl Into<String>) -> Result<Self, VulnerabilityIdError> {
+        Ok(AdvisoryId::from_str(&id.into())?.into())
+    }
+
+    /// Add aliases, electing the preferred identifier among all of them as primary.
+    #[must_use]
+    pub fn with_aliases(self, aliases: impl IntoIterator<Item = AdvisoryId>) -> Self {
+        let mut ids = self.aliases;
+        ids.insert(self.primary);
+        ids.extend(aliases);
+        let primary = ids
+            .pop_first()
+            .expect("a vulnerability id holds at least one id");
+        Self {
+            primary,
+            aliases: ids,
+        }
+    }
+
+    #[must_use]
+    pub const fn primary(&self) -> &AdvisoryId {
+        &self.primary
+    }
+
+    #[must_use]
+    pub const fn namespace(&self) -> VulnerabilityNamespace {
+        self.primary.namespace()
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.primary.as_str()
+    }
+
+    /// Other identifiers of the same vulnerability, excluding the primary one.
+    #[must_use]
+    pub const fn aliases(&self) -> &BTreeSet<AdvisoryId> {
+        &self.aliases
+    }
+
+    /// All identifiers of the vulnerability, primary first.
+    pub fn ids(&self) -> impl Iterator<Item = &AdvisoryId> {
+        std::iter::once(&self.primary).chain(&self.aliases)
+    }
+
+    /// Whether the given identifier designates this vulnerability.
+    #[must_use]
+    pub fn is_known_as(&self, id: &AdvisoryId) -> bool {
+        &self.primary == id || self.aliases.contains(id)
+    }
+
+    /// Whether both ids share at least one identifier, even if their primaries differ.
+    #[must_use]
+    pub fn same_vulnerability(&self, other: &Self) -> bool {
+        other.ids().any(|id| self.is_known_as(id))
+    }
+}
+
+impl From<AdvisoryId> for VulnerabilityId {
+    fn from(primary: AdvisoryId) -> Self {
+        Self {
+            primary,
+            aliases: BTreeSet::new(),
+        }
+    }
+}
+
+impl FromStr for VulnerabilityId {
+    type Err = VulnerabilityIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl PartialEq for VulnerabilityId {
+    fn eq(&self, other: &Self) -> bool {
+        self.primary == other.primary
+    }
+}
+
+impl Eq for VulnerabilityId {}
+
+impl Hash for VulnerabilityId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.primary.hash(state);
+    }
+}
+
+impl Ord for VulnerabilityId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.primary.cmp(&other.primary)
+    }
+}
+
+impl PartialOrd for VulnerabilityId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for VulnerabilityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.primary.fmt(f)
+    }
+}
+
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum VulnerabilityIdError {
+    #[error("Vulnerability ID cannot be empty")]
+    Empty,
+
+    #[error("Unrecognized vulnerability ID namespace in `{0}`")]
+    UnknownNamespace(String),
+
+    #[error("Invalid {0} identifier `{1}`")]
+    InvalidFormat(VulnerabilityNamespace, String),
+}
+
+/// Identity of a `ManagedVulnerability`: a vulnerability scoped to the component it affects.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ManagedVulnerabilityId {
+    component_id: ComponentId,
+    vulnerability_id: VulnerabilityId,
+}
+
+impl ManagedVulnerabilityId {
+    #[must_use]
+    pub const fn new(component_id: ComponentId, vulnerability_id: VulnerabilityId) -> Self {
+        Self {
+            component_id,
+            vulnerability_id,
+        }
+    }
+
+    #[must_use]
+    pub const fn component_id(&self) -> &ComponentId {
+        &self.component_id
+    }
+
+    #[must_use]
+    pub const fn vulnerability_id(&self) -> &VulnerabilityId {
+        &self.vulnerability_id
+    }
+}
+
+impl fmt::Display for ManagedVulnerabilityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.vulnerability_id, self.component_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(s: &str) -> AdvisoryId {
+        AdvisoryId::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn known_formats_should_be_recognized_and_normalized() {
+        let cases = [
+            (
+                "cve-2024-1234",
+                VulnerabilityNamespace::Cve,
+                "CVE-2024-1234",
+            ),
+            (
+                " CVE-2021-44228 ",
+                VulnerabilityNamespace::Cve,
+                "CVE-2021-44228",
+            ),
+            (
+                "GHSA-XVCH-5GV4-984H",
+                VulnerabilityNamespace::Ghsa,
+                "GHSA-xvch-5gv4-984h",
+            ),
+            (
+                "ghsa-jfh8-c2jp-5v3q",
+                VulnerabilityNamespace::Ghsa,
+                "GHSA-jfh8-c2jp-5v3q",
+            ),
+            (
+                "PYSEC-2021-19",
+                VulnerabilityNamespace::Pysec,
+                "PYSEC-2021-19",
+            ),
+            ("go-2024-2687", VulnerabilityNamespace::Go, "GO-2024-2687"),
+            (
+                "RUSTSEC-2024-0332",
+                VulnerabilityNamespace::Rustsec,
+                "RUSTSEC-2024-0332",
+            ),
+            ("DSA-5678-1", VulnerabilityNamespace::Dsa, "DSA-5678-1"),
+            ("dla-3782-1", VulnerabilityNamespace::Dla, "DLA-3782-1"),
+            (
+                "RHSA-2024:1234",
+                VulnerabilityNamespace::Rhsa,
+                "RHSA-2024:1234",
+            ),
+            (
+                "alsa-2024:10219",
+                VulnerabilityNamespace::Alsa,
+                "ALSA-2024:10219",
+            ),
+            ("USN-6543-1", VulnerabilityNamespace::Usn, "USN-6543-1"),
+        ];
+
+        for (input, namespace, normalized) in cases {
+            let id = advisory(input);
+            assert_eq!(id.namespace(), namespace, "{input}");
+            assert_eq!(id.as_str(), normalized, "{input}");
+        }
+    }
+
+    #[test]
+    fn malformed_ids_should_be_rejected() {
+        assert_eq!(VulnerabilityId::new("  "), Err(VulnerabilityIdError::Empty));
+        assert_eq!(
+            VulnerabilityId::new("FOO-2024-1234"),
+            Err(VulnerabilityIdError::UnknownNamespace(
+                "FOO-2024-1234".to_string()
+            ))
+        );
+        assert_eq!(
+            VulnerabilityId::new("CVE2024-1234"),
+            Err(VulnerabilityIdError::UnknownNamespace(
+                "CVE2024-1234".to_string()
+            ))
+        );
+
+        for (input, namespace) in [
+            ("CVE-2024-123", VulnerabilityNamespace::Cve),
+            ("CVE-24-1234", VulnerabilityNamespace::Cve),
+            ("GHSA-xvch-5gv4", VulnerabilityNamespace::Ghsa),
+            ("GHSA-xvch-5gv4-984a", VulnerabilityNamespace::Ghsa),
+            ("RUSTSEC-2024-33", VulnerabilityNamespace::Rustsec),
+            ("RHSA-2024-1234", VulnerabilityNamespace::Rhsa),
+            ("USN-x-1", VulnerabilityNamespace::Usn),
+        ] {
+            assert_eq!(
+                VulnerabilityId::new(input),
+                Err(VulnerabilityIdError::InvalidFormat(
+                    namespace,
+                    input.to_string()
+                )),
+            );
+        }
+    }
+
+    #[test]
+    fn ordering_should_group_by_namespace_and_compare_numbers() {
+        let mut ids = [
+            advisory("GHSA-xvch-5gv4-984h"),
+            advisory("CVE-2024-10000"),
+            advisory("USN-6543-1"),
+            advisory("CVE-2024-9999"),
+            advisory("CVE-2023-50000"),
+        ];
+        ids.sort();
+
+        let sorted: Vec<&str> = ids.iter().map(AdvisoryId::as_str).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "CVE-2023-50000",
+                "CVE-2024-9999",
+                "CVE-2024-10000",
+                "GHSA-xvch-5gv4-984h",
+                "USN-6543-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn aliases_should_elect_preferred_primary() {
+        let ghsa = VulnerabilityId::new("GHSA-jfh8-c2jp-5v3q").unwrap();
+        let id = ghsa
+            .clone()
+            .with_aliases([advisory("CVE-2021-44228"), advisory("DSA-5022-1")]);
+
+        assert_eq!(id.as_str(), "CVE-2021-44228");
+        assert_eq!(id.namespace(), VulnerabilityNamespace::Cve);
+        assert_eq!(
+            id.aliases()
+                .iter()
+                .map(AdvisoryId::as_str)
+                .collect::<Vec<_>>(),
+            vec!["GHSA-jfh8-c2jp-5v3q", "DSA-5022-1"]
+        );
+        assert!(id.is_known_as(&advisory("ghsa-JFH8-c2jp-5v3q")));
+        assert!(id.same_vulnerability(&ghsa));
+        assert!(!id.same_vulnerability(&VulnerabilityId::new("CVE-2021-45046").unwrap()));
+    }
+
+    #[test]
+    fn equality_should_follow_the_primary_id() {
+        let cve = VulnerabilityId::new("CVE-2021-44228").unwrap();
+        let reported_as_ghsa = VulnerabilityId::new("GHSA-jfh8-c2jp-5v3q")
+            .unwrap()
+            .with_aliases([advisory("CVE-2021-44228")]);
+        assert_eq!(cve, reported_as_ghsa);
+
+        let component = ComponentId::from_str("registry.test/app:v1").unwrap();
+        assert_eq!(
+            ManagedVulnerabilityId::new(component.clone(), cve),
+            ManagedVulnerabilityId::new(component, reported_as_ghsa)
+        );
+    }
+}
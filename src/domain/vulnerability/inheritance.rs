@@ -0,0 +1,401 @@
+use crate::domain::component::context::ExecutionContext;
+use crate::domain::component::id::ComponentId;
+use crate::domain::vulnerability::classification::ClassificationState;
+use crate::domain::vulnerability::event::ManagedVulnerabilityEvent;
+use crate::domain::vulnerability::{ManagedVulnerability, ManagedVulnerabilityError};
+use std::fmt;
+
+/// What the inheritance service needs to know about a component to compare it with others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentProfile {
+    id: ComponentId,
+    base_image: Option<ComponentId>,
+    context: Option<ExecutionContext>,
+}
+
+impl ComponentProfile {
+    #[must_use]
+    pub const fn new(
+        id: ComponentId,
+        base_image: Option<ComponentId>,
+        context: Option<ExecutionContext>,
+    ) -> Self {
+        Self {
+            id,
+            base_image,
+            context,
+        }
+    }
+
+    #[must_use]
+    pub const fn id(&self) -> &ComponentId {
+        &self.id
+    }
+
+    #[must_use]
+    pub const fn base_image(&self) -> Option<&ComponentId> {
+        self.base_image.as_ref()
+    }
+
+    #[must_use]
+    pub const fn context(&self) -> Option<&ExecutionContext> {
+        self.context.as_ref()
+    }
+
+    /// How this component relates to another one, strongest relation first.
+    ///
+    /// Returns `None` when this component has no execution context to bind an inherited
+    /// classification to, or when both profiles describe the same component or are unrelated.
+    #[must_use]
+    pub fn similarity(&self, other: &Self) -> Option<Similarity> {
+        if self.id == other.id || self.context.is_none() {
+            return None;
+        }
+
+        let same_digest = matches!(
+            (self.id.digest(), other.id.digest()),
+            (Some(a), Some(b)) if a == b
+        );
+        let same_base_image = matches!(
+            (self.base_image(), other.base_image()),
+            (Some(a), Some(b)) if a == b
+        ) && matches!(
+            (self.context(), other.context()),
+            (Some(a), Some(b)) if a == b
+        );
+
+        if same_digest {
+            Some(Similarity::SameDigest)
+        } else if self.id.same_repository(&other.id) {
+            Some(Similarity::SameRepository)
+        } else if same_base_image {
+            Some(Similarity::SameBaseImage)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reason why two components may share the classification of a vulnerability.
+///
+/// Ordered from the strongest to the weakest relation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Similarity {
+    /// Both references resolve to the same image content.
+    SameDigest,
+    /// Another tag of the same repository.
+    SameRepository,
+    /// Built on the same base image and running in an equal execution context.
+    SameBaseImage,
+}
+
+impl fmt::Display for Similarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::SameDigest => "same-digest",
+            Self::SameRepository => "same-repository",
+            Self::SameBaseImage => "same-base-image",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A classified vulnerability on a similar component, from which a classification may be inherited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClassificationCandidate<'a> {
+    source: &'a ManagedVulnerability,
+    similarity: Similarity,
+}
+
+impl<'a> ClassificationCandidate<'a> {
+    #[must_use]
+    pub const fn source(&self) -> &'a ManagedVulnerability {
+        self.source
+    }
+
+    #[must_use]
+    pub const fn similarity(&self) -> Similarity {
+        self.similarity
+    }
+}
+
+/// Domain service finding classification sources for a (component, vulnerability) pair.
+///
+/// Spares analysts from triaging the same vulnerability again on every tag of an image.
+pub struct ClassificationInheritance;
+
+impl ClassificationInheritance {
+    /// List the vulnerabilities `target` could inherit a classification from, best first.
+    ///
+    /// Only direct classifications of the same vulnerability on a similar component qualify,
+    /// and only if the target component has an execution context.
+    /// Each source is given with the profile of the component it affects; candidates with
+    /// the same similarity are ordered by id so that the result is deterministic.
+    #[must_use]
+    pub fn candidates<'a>(
+        target: &ManagedVulnerability,
+        profile: &ComponentProfile,
+        sources: &'a [(ManagedVulnerability, ComponentProfile)],
+    ) -> Vec<ClassificationCandidate<'a>> {
+        let mut candidates: Vec<_> = sources
+            .iter()
+            .filter(|(source, source_profile)| {
+                source
+                    .id()
+                    .vulnerability_id()
+                    .same_vulnerability(target.id().vulnerability_id())
+                    && source.id().component_id() == source_profile.id()
+                    && matches!(source.state(), ClassificationState::Classified(_))
+            })
+            .filter_map(|(source, source_profile)| {
+                profile
+                    .similarity(source_profile)
+                    .map(|similarity| ClassificationCandidate { source, similarity })
+            })
+            .collect();
+
+        candidates.sort_by_cached_key(|c| (c.similarity, c.source.id().to_string()));
+        candidates
+    }
+
+    /// Emit the event inheriting the classification of the best candidate.
+    ///
+    /// The severity of the source is bound to the execution context of the target component.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagedVulnerabilityError::NoClassificationSource`] if no similar component
+    /// holds a classification of the same vulnerability, or any error raised by
+    /// [`ManagedVulnerability::inherit_classification`].
+    pub fn inherit(
+        target: &ManagedVulnerability,
+        profile: &ComponentProfile,
+        sources: &[(ManagedVulnerability, ComponentProfile)],
+    ) -> Result<ManagedVulnerabilityEvent, ManagedVulnerabilityError> {
+        let best = Self::candidates(target, profile, sources)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                ManagedVulnerabilityError::NoClassificationSource(Box::new(target.id().clone()))
+            })?;
+        target.inherit_classification(best.source(), profile.context())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::context::{DataSensitivity, Exposure, Isolation};
+    use crate::domain::shared::aggregate::EventSourcedAggregate;
+    use crate::domain::vulnerability::classification::Severity;
+    use crate::domain::vulnerability::id::{AdvisoryId, ManagedVulnerabilityId, VulnerabilityId};
+    use std::str::FromStr;
+
+    const CVE: &str = "CVE-2024-1234";
+
+    fn component(image: &str) -> ComponentId {
+        ComponentId::from_str(image).unwrap()
+    }
+
+    fn context(exposure: Exposure) -> ExecutionContext {
+        ExecutionContext::new(
+            exposure,
+            Isolation::Sandboxed,
+            DataSensitivity::Internal,
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+        )
+        .unwrap()
+    }
+
+    fn profile(image: &str, base: Option<&str>, exposure: Exposure) -> ComponentProfile {
+        ComponentProfile::new(
+            component(image),
+            base.map(component),
+            Some(context(exposure)),
+        )
+    }
+
+    fn registered(image: &str, cve: &str) -> ManagedVulnerability {
+        let id = ManagedVulnerabilityId::new(component(image), VulnerabilityId::new(cve).unwrap());
+        ManagedVulnerability::from_initial_event(&ManagedVulnerability::register(id)).unwrap()
+    }
+
+    fn classified(profile: &ComponentProfile, cve: &str) -> ManagedVulnerability {
+        let mut vuln = registered(&profile.id().to_string(), cve);
+        let event = vuln.classify(Severity::High, profile.context()).unwrap();
+        vuln.apply(&event).unwrap();
+        vuln
+    }
+
+    fn digest_ref(repository: &str) -> String {
+        format!("{repository}@sha256:{}", "a".repeat(64))
+    }
+
+    #[test]
+    fn similarity_should_rank_digest_then_repository_then_base_image() {
+        let target = profile(
+            &digest_ref("app/api"),
+            Some("debian:12"),
+            Exposure::Internal,
+        );
+
+        let same_digest = profile(&digest_ref("mirror/api"), None, Exposure::InternetFacing);
+        let other_tag = profile("app/api:1.1", None, Exposure::InternetFacing);
+        let same_base = profile("app/worker:1.0", Some("debian:12"), Exposure::Internal);
+        let other_context = profile("app/worker:1.0", Some("debian:12"), Exposure::Isolated);
+        let unrelated = profile("app/worker:1.0", Some("alpine:3"), Exposure::Internal);
+
+        assert_eq!(
+            target.similarity(&same_digest),
+            Some(Similarity::SameDigest)
+        );
+        assert_eq!(
+            target.similarity(&other_tag),
+            Some(Similarity::SameRepository)
+        );
+        assert_eq!(
+            target.similarity(&same_base),
+            Some(Similarity::SameBaseImage)
+        );
+        assert_eq!(target.similarity(&other_context), None);
+        assert_eq!(target.similarity(&unrelated), None);
+        assert_eq!(target.similarity(&target), None);
+    }
+
+    #[test]
+    fn candidates_should_keep_classified_sources_of_the_same_vulnerability() {
+        let target_profile = profile("app/api:1.2", None, Exposure::Internal);
+        let target = registered("app/api:1.2", CVE);
+
+        let v10 = profile("app/api:1.0", None, Exposure::Internal);
+        let v11 = profile("app/api:1.1", None, Exposure::Internal);
+        let base = profile("app/worker:1.0", None, Exposure::Internal);
+        let sources = vec![
+            (classified(&v11, CVE), v11.clone()),
+            (classified(&v10, "CVE-2024-9999"), v10.clone()),
+            (registered("app/api:1.0", CVE), v10.clone()),
+            (classified(&base, CVE), base),
+            (classified(&v10, CVE), v10.clone()),
+        ];
+
+        let candidates = ClassificationInheritance::candidates(&target, &target_profile, &sources);
+        let ids: Vec<_> = candidates
+            .iter()
+            .map(|c| c.source().id().component_id().clone())
+            .collect();
+        assert_eq!(ids, vec![v10.id().clone(), v11.id().clone()]);
+        assert!(
+            candidates
+                .iter()
+                .all(|c| c.similarity() == Similarity::SameRepository)
+        );
+    }
+
+    #[test]
+    fn sources_tracked_under_an_alias_should_qualify() {
+        const GHSA: &str = "GHSA-xvch-5gv4-984h";
+        let target_profile = profile("app/api:1.2", None, Exposure::Internal);
+        let id = ManagedVulnerabilityId::new(
+            component("app/api:1.2"),
+            VulnerabilityId::new(CVE)
+                .unwrap()
+                .with_aliases([AdvisoryId::from_str(GHSA).unwrap()]),
+        );
+        let target =
+            ManagedVulnerability::from_initial_event(&ManagedVulnerability::register(id)).unwrap();
+        let other_tag = profile("app/api:1.1", None, Exposure::Internal);
+        let sources = vec![(classified(&other_tag, GHSA), other_tag.clone())];
+
+        let candidates = ClassificationInheritance::candidates(&target, &target_profile, &sources);
+        assert_eq!(candidates.len(), 1);
+        let event = ClassificationInheritance::inherit(&target, &target_profile, &sources).unwrap();
+        match event {
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationInherited {
+                source,
+                ..
+            } => assert_eq!(source.component_id(), other_tag.id()),
+            other => panic!("Expected ManagedVulnerabilityClassificationInherited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn inherit_should_reference_best_source() {
+        let target_profile = profile(
+            &digest_ref("app/api"),
+            Some("debian:12"),
+            Exposure::Internal,
+        );
+        let target = registered(&digest_ref("app/api"), CVE);
+
+        let same_base = profile("app/worker:1.0", Some("debian:12"), Exposure::Internal);
+        let same_digest = profile(&digest_ref("mirror/api"), None, Exposure::Internal);
+        let sources = vec![
+            (classified(&same_base, CVE), same_base),
+            (classified(&same_digest, CVE), same_digest.clone()),
+        ];
+
+        let event = ClassificationInheritance::inherit(&target, &target_profile, &sources).unwrap();
+        match event {
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationInherited {
+                source,
+                ..
+            } => assert_eq!(source.component_id(), same_digest.id()),
+            other => panic!("Expected ManagedVulnerabilityClassificationInherited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn inherit_should_bind_the_classification_to_the_target_context() {
+        let target_profile = profile("app/api:1.2", None, Exposure::Internal);
+        let target = registered("app/api:1.2", CVE);
+        let other_tag = profile("app/api:1.1", None, Exposure::InternetFacing);
+        let sources = vec![(classified(&other_tag, CVE), other_tag)];
+
+        let event = ClassificationInheritance::inherit(&target, &target_profile, &sources).unwrap();
+        match event {
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationInherited {
+                classification,
+                ..
+            } => {
+                assert_eq!(classification.severity(), Severity::High);
+                assert_eq!(Some(classification.context()), target_profile.context());
+            }
+            other => panic!("Expected ManagedVulnerabilityClassificationInherited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn targets_without_context_should_not_inherit() {
+        let target_profile = ComponentProfile::new(component(&digest_ref("app/api")), None, None);
+        let target = registered(&digest_ref("app/api"), CVE);
+        let same_digest = profile(&digest_ref("mirror/api"), None, Exposure::Internal);
+        let other_tag = profile("app/api:1.1", None, Exposure::Internal);
+        let sources = vec![
+            (classified(&same_digest, CVE), same_digest),
+            (classified(&other_tag, CVE), other_tag),
+        ];
+
+        assert!(
+            ClassificationInheritance::candidates(&target, &target_profile, &sources).is_empty()
+        );
+        let err =
+            ClassificationInheritance::inherit(&target, &target_profile, &sources).unwrap_err();
+        assert_eq!(
+            err,
+            ManagedVulnerabilityError::NoClassificationSource(Box::new(target.id().clone()))
+        );
+    }
+
+    #[test]
+    fn inherit_without_candidate_should_fail() {
+        let target_profile = profile("app/api:1.2", None, Exposure::Internal);
+        let target = registered("app/api:1.2", CVE);
+
+        let err = ClassificationInheritance::inherit(&target, &target_profile, &[]).unwrap_err();
+        assert_eq!(
+            err,
+            ManagedVulnerabilityError::NoClassificationSource(Box::new(target.id().clone()))
+        );
+    }
+}
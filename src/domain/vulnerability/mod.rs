@@ -0,0 +1,608 @@
+pub mod classification;
+pub mod cvss;
+pub mod entity;
+pub mod event;
+pub mod id;
+pub mod inheritance;
+pub mod scoring;
+pub mod version;
+
+use crate::domain::component::context::ExecutionContext;
+use crate::domain::shared::aggregate::EventSourcedAggregate;
+use crate::domain::vulnerability::classification::{Classification, ClassificationState, Severity};
+use crate::domain::vulnerability::event::ManagedVulnerabilityEvent;
+use crate::domain::vulnerability::id::ManagedVulnerabilityId;
+use std::convert::TryFrom;
+use thiserror::Error;
+
+/// A tracked vulnerability affecting a specific component.
+///
+/// Uniquely identified by its [`ManagedVulnerabilityId`] (component + vulnerability) and
+/// responsible for the classification lifecycle of the vulnerability on that component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagedVulnerability {
+    id: ManagedVulnerabilityId,
+    state: ClassificationState,
+}
+
+impl ManagedVulnerability {
+    #[must_use]
+    pub const fn new(id: ManagedVulnerabilityId) -> Self {
+        Self {
+            id,
+            state: ClassificationState::Unclassified,
+        }
+    }
+
+    /// Emit event for registering a new managed vulnerability
+    #[must_use]
+    pub const fn register(id: ManagedVulnerabilityId) -> ManagedVulnerabilityEvent {
+        ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered { id }
+    }
+
+    /// Emit an event to classify a registered vulnerability for the first time.
+    ///
+    /// The classification is bound to the execution context of the affected component,
+    /// which must have been assigned beforehand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagedVulnerabilityError::AlreadyClassified`] if the vulnerability already
+    /// holds a classification, or [`ManagedVulnerabilityError::ExecutionContextRequired`] if
+    /// the component has no execution context.
+    pub fn classify(
+        &self,
+        severity: Severity,
+        context: Option<&ExecutionContext>,
+    ) -> Result<ManagedVulnerabilityEvent, ManagedVulnerabilityError> {
+        self.ensure_unclassified()?;
+        let context = self.require_context(context)?;
+        Ok(ManagedVulnerabilityEvent::ManagedVulnerabilityClassified {
+            id: self.id.clone(),
+            classification: Classification::new(severity, context),
+        })
+    }
+
+    /// Emit an event to adopt the classification of the same vulnerability on a similar component.
+    ///
+    /// Only the severity is adopted: the inherited classification is bound to `context`, the
+    /// execution context of this component, so that its own context changes outdate it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagedVulnerabilityError::AlreadyClassified`] if the vulnerability already
+    /// holds a classification, [`ManagedVulnerabilityError::InvalidClassificationSource`] if
+    /// the source tracks another vulnerability, is this very aggregate, or is not directly
+    /// classified, or [`ManagedVulnerabilityError::ExecutionContextRequired`] if the component
+    /// has no execution context.
+    pub fn inherit_classification(
+        &self,
+        source: &Self,
+        context: Option<&ExecutionContext>,
+    ) -> Result<ManagedVulnerabilityEvent, ManagedVulnerabilityError> {
+        self.ensure_unclassified()?;
+
+        let same_vulnerability = source
+            .id
+            .vulnerability_id()
+            .same_vulnerability(self.id.vulnerability_id());
+        let other_component = source.id.component_id() != self.id.component_id();
+
+        match &source.state {
+            ClassificationState::Classified(classification)
+                if same_vulnerability && other_component =>
+            {
+                let context = self.require_context(context)?;
+                Ok(
+                    ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationInherited {
+                        id: self.id.clone(),
+                        classification: Classification::new(classification.severity(), context),
+                        source: source.id.clone(),
+                    },
+                )
+            }
+            _ => Err(ManagedVulnerabilityError::InvalidClassificationSource(
+                Box::new(source.id.clone()),
+            )),
+        }
+    }
+
+    /// Emit an event to flag the current classification, direct or inherited, as outdated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagedVulnerabilityError::NotClassified`] if the vulnerability is not
+    /// currently holding a direct or inherited classification.
+    pub fn mark_outdated(&self) -> Result<ManagedVulnerabilityEvent, ManagedVulnerabilityError> {
+        self.ensure_classified()?;
+        Ok(
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated {
+                id: self.id.clone(),
+            },
+        )
+    }
+
+    /// Emit an event to classify an outdated vulnerability again.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagedVulnerabilityError::NotOutdated`] if the vulnerability is not outdated,
+    /// or [`ManagedVulnerabilityError::ExecutionContextRequired`] if the component has no
+    /// execution context.
+    pub fn reclassify(
+        &self,
+        severity: Severity,
+        context: Option<&ExecutionContext>,
+    ) -> Result<ManagedVulnerabilityEvent, ManagedVulnerabilityError> {
+        self.ensure_outdated()?;
+        let context = self.require_context(context)?;
+        Ok(ManagedVulnerabilityEvent::ManagedVulnerabilityClassified {
+            id: self.id.clone(),
+            classification: Classification::new(severity, context),
+        })
+    }
+
+    fn ensure_unclassified(&self) -> Result<(), ManagedVulnerabilityError> {
+        match self.state {
+            ClassificationState::Unclassified => Ok(()),
+            _ => Err(ManagedVulnerabilityError::AlreadyClassified(Box::new(
+                self.id.clone(),
+            ))),
+        }
+    }
+
+    fn ensure_classified(&self) -> Result<(), ManagedVulnerabilityError> {
+        match self.state {
+            ClassificationState::Classified(_) | ClassificationState::Inherited { .. } => Ok(()),
+            _ => Err(ManagedVulnerabilityError::NotClassified(Box::new(
+                self.id.clone(),
+            ))),
+        }
+    }
+
+    fn ensure_outdated(&self) -> Result<(), ManagedVulnerabilityError> {
+        match self.state {
+            ClassificationState::Outdated(_) => Ok(()),
+            _ => Err(ManagedVulnerabilityError::NotOutdated(Box::new(
+                self.id.clone(),
+            ))),
+        }
+    }
+
+    fn require_context(
+        &self,
+        context: Option<&ExecutionContext>,
+    ) -> Result<ExecutionContext, ManagedVulnerabilityError> {
+        context.cloned().ok_or_else(|| {
+            ManagedVulnerabilityError::ExecutionContextRequired(Box::new(self.id.clone()))
+        })
+    }
+
+    // Accessors
+
+    #[must_use]
+    pub const fn id(&self) -> &ManagedVulnerabilityId {
+        &self.id
+    }
+
+    #[must_use]
+    pub const fn state(&self) -> &ClassificationState {
+        &self.state
+    }
+
+    #[must_use]
+    pub const fn classification(&self) -> Option<&Classification> {
+        self.state.classification()
+    }
+}
+
+impl TryFrom<&ManagedVulnerabilityEvent> for ManagedVulnerability {
+    type Error = ManagedVulnerabilityError;
+
+    fn try_from(event: &ManagedVulnerabilityEvent) -> Result<Self, Self::Error> {
+        match event {
+            ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered { id } => {
+                Ok(Self::new(id.clone()))
+            }
+            _ => Err(ManagedVulnerabilityError::InvalidInitialEvent),
+        }
+    }
+}
+
+impl EventSourcedAggregate<ManagedVulnerabilityEvent, ManagedVulnerabilityError>
+    for ManagedVulnerability
+{
+    fn from_initial_event(
+        event: &ManagedVulnerabilityEvent,
+    ) -> Result<Self, ManagedVulnerabilityError> {
+        Self::try_from(event)
+    }
+
+    fn apply(
+        &mut self,
+        event: &ManagedVulnerabilityEvent,
+    ) -> Result<(), ManagedVulnerabilityError> {
+        if event.id() != &self.id {
+            return Err(ManagedVulnerabilityError::InconsistentIds(
+                event.id().to_string(),
+                self.id.to_string(),
+            ));
+        }
+
+        self.state = match event {
+            ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered { .. } => {
+                return Err(ManagedVulnerabilityError::RegisteredEventNotAllowed);
+            }
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassified {
+                classification, ..
+            } => {
+                if !matches!(self.state, ClassificationState::Outdated(_)) {
+                    self.ensure_unclassified()?;
+                }
+                ClassificationState::Classified(classification.clone())
+            }
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationInherited {
+                classification,
+                source,
+                ..
+            } => {
+                self.ensure_unclassified()?;
+                ClassificationState::Inherited {
+                    classification: classification.clone(),
+                    source: source.clone(),
+                }
+            }
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated { .. } => {
+                match &self.state {
+                    ClassificationState::Classified(c)
+                    | ClassificationState::Inherited {
+                        classification: c, ..
+                    } => ClassificationState::Outdated(c.clone()),
+                    _ => {
+                        return Err(ManagedVulnerabilityError::NotClassified(Box::new(
+                            self.id.clone(),
+                        )));
+                    }
+                }
+            }
+        };
+
+        Ok(())
+    }
+
+    fn invalid_initial_event() -> ManagedVulnerabilityError {
+        ManagedVulnerabilityError::InvalidInitialEvent
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ManagedVulnerabilityError {
+    #[error("Managed vulnerability `{0}` is already registered")]
+    AlreadyRegistered(Box<ManagedVulnerabilityId>),
+
+    #[error("Managed vulnerability `{0}` is not registered")]
+    NotRegistered(Box<ManagedVulnerabilityId>),
+
+    #[error("Managed vulnerability `{0}` is already classified")]
+    AlreadyClassified(Box<ManagedVulnerabilityId>),
+
+    #[error("Managed vulnerability `{0}` has no current classification")]
+    NotClassified(Box<ManagedVulnerabilityId>),
+
+    #[error("Managed vulnerability `{0}` is not outdated")]
+    NotOutdated(Box<ManagedVulnerabilityId>),
+
+    #[error("Managed vulnerability `{0}` cannot be classified without an execution context")]
+    ExecutionContextRequired(Box<ManagedVulnerabilityId>),
+
+    #[error("`{0}` is not a valid classification source")]
+    InvalidClassificationSource(Box<ManagedVulnerabilityId>),
+
+    #[error("No similar component holds a classification for `{0}`")]
+    NoClassificationSource(Box<ManagedVulnerabilityId>),
+
+    #[error("Event `ManagedVulnerabilityRegistered` cannot be applied to an existing aggregate")]
+    RegisteredEventNotAllowed,
+
+    #[error(
+        "Only `ManagedVulnerabilityRegistered` can be used to initialize a ManagedVulnerability"
+    )]
+    InvalidInitialEvent,
+
+    #[error("Inconsistent ids: `{0}` != `{1}`")]
+    InconsistentIds(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::context::{DataSensitivity, Exposure, Isolation};
+    use crate::domain::component::id::ComponentId;
+    use crate::domain::vulnerability::id::VulnerabilityId;
+    use std::str::FromStr;
+
+    fn managed_id(image: &str, cve: &str) -> ManagedVulnerabilityId {
+        ManagedVulnerabilityId::new(
+            ComponentId::from_str(image).unwrap(),
+            VulnerabilityId::new(cve).unwrap(),
+        )
+    }
+
+    fn dummy_id() -> ManagedVulnerabilityId {
+        managed_id("registry.test/namespace/image:v0", "CVE-2024-1234")
+    }
+
+    fn dummy_context() -> ExecutionContext {
+        ExecutionContext::new(
+            Exposure::InternetFacing,
+            Isolation::Sandboxed,
+            DataSensitivity::Confidential,
+            ["pci-dss"],
+            ["payments"],
+        )
+        .unwrap()
+    }
+
+    fn registered(id: ManagedVulnerabilityId) -> ManagedVulnerability {
+        ManagedVulnerability::from_initial_event(&ManagedVulnerability::register(id)).unwrap()
+    }
+
+    fn classified(id: ManagedVulnerabilityId) -> ManagedVulnerability {
+        let mut vuln = registered(id);
+        let event = vuln
+            .classify(Severity::High, Some(&dummy_context()))
+            .unwrap();
+        vuln.apply(&event).unwrap();
+        vuln
+    }
+
+    #[test]
+    fn register_should_build_unclassified_vulnerability() {
+        let id = dummy_id();
+        let vuln = registered(id.clone());
+
+        assert_eq!(vuln.id(), &id);
+        assert_eq!(vuln.state(), &ClassificationState::Unclassified);
+        assert!(vuln.classification().is_none());
+    }
+
+    #[test]
+    fn classify_should_bind_severity_and_context() {
+        let vuln = classified(dummy_id());
+
+        let classification = vuln.classification().unwrap();
+        assert_eq!(classification.severity(), Severity::High);
+        assert_eq!(classification.context(), &dummy_context());
+        assert!(matches!(vuln.state(), ClassificationState::Classified(_)));
+    }
+
+    #[test]
+    fn classify_without_context_should_fail() {
+        let vuln = registered(dummy_id());
+
+        let err = vuln.classify(Severity::Low, None).unwrap_err();
+        assert_eq!(
+            err,
+            ManagedVulnerabilityError::ExecutionContextRequired(Box::new(dummy_id()))
+        );
+    }
+
+    #[test]
+    fn classify_twice_should_fail() {
+        let vuln = classified(dummy_id());
+
+        let err = vuln
+            .classify(Severity::Low, Some(&dummy_context()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ManagedVulnerabilityError::AlreadyClassified(_)
+        ));
+    }
+
+    #[test]
+    fn outdated_then_reclassify_should_return_to_classified() {
+        let mut vuln = classified(dummy_id());
+
+        let outdated = vuln.mark_outdated().unwrap();
+        vuln.apply(&outdated).unwrap();
+        assert!(matches!(vuln.state(), ClassificationState::Outdated(_)));
+        assert_eq!(vuln.classification().unwrap().severity(), Severity::High);
+
+        let reclassified = vuln
+            .reclassify(Severity::Medium, Some(&dummy_context()))
+            .unwrap();
+        vuln.apply(&reclassified).unwrap();
+        assert_eq!(vuln.classification().unwrap().severity(), Severity::Medium);
+    }
+
+    #[test]
+    fn reclassify_requires_outdated_state() {
+        let vuln = classified(dummy_id());
+
+        let err = vuln
+            .reclassify(Severity::Low, Some(&dummy_context()))
+            .unwrap_err();
+        assert!(matches!(err, ManagedVulnerabilityError::NotOutdated(_)));
+    }
+
+    #[test]
+    fn mark_outdated_requires_current_classification() {
+        let vuln = registered(dummy_id());
+
+        let err = vuln.mark_outdated().unwrap_err();
+        assert!(matches!(err, ManagedVulnerabilityError::NotClassified(_)));
+    }
+
+    #[test]
+    fn inherited_classification_can_be_outdated() {
+        let source = classified(managed_id(
+            "registry.test/namespace/image:v1",
+            "CVE-2024-1234",
+        ));
+        let mut vuln = registered(dummy_id());
+        let inherited = vuln
+            .inherit_classification(&source, Some(&dummy_context()))
+            .unwrap();
+        vuln.apply(&inherited).unwrap();
+
+        let outdated = vuln.mark_outdated().unwrap();
+        vuln.apply(&outdated).unwrap();
+        assert!(matches!(vuln.state(), ClassificationState::Outdated(_)));
+        assert_eq!(vuln.classification().unwrap().severity(), Severity::High);
+    }
+
+    #[test]
+    fn inherit_should_reference_classified_source() {
+        let source = classified(managed_id(
+            "registry.test/namespace/image:v1",
+            "CVE-2024-1234",
+        ));
+        let mut vuln = registered(dummy_id());
+        let context = ExecutionContext::new(
+            Exposure::Internal,
+            Isolation::Sandboxed,
+            DataSensitivity::Public,
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+        )
+        .unwrap();
+
+        let event = vuln
+            .inherit_classification(&source, Some(&context))
+            .unwrap();
+        vuln.apply(&event).unwrap();
+
+        match vuln.state() {
+            ClassificationState::Inherited {
+                classification,
+                source: from,
+            } => {
+                assert_eq!(from, source.id());
+                assert_eq!(classification.severity(), Severity::High);
+                assert_eq!(classification.context(), &context);
+            }
+            other => panic!("Expected Inherited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn inherit_requires_context() {
+        let source = classified(managed_id(
+            "registry.test/namespace/image:v1",
+            "CVE-2024-1234",
+        ));
+        let vuln = registered(dummy_id());
+
+        let err = vuln.inherit_classification(&source, None).unwrap_err();
+        assert!(matches!(
+            err,
+            ManagedVulnerabilityError::ExecutionContextRequired(_)
+        ));
+    }
+
+    #[test]
+    fn inherit_from_invalid_source_should_fail() {
+        let vuln = registered(dummy_id());
+
+        let unclassified = registered(managed_id(
+            "registry.test/namespace/image:v1",
+            "CVE-2024-1234",
+        ));
+        let other_cve = classified(managed_id(
+            "registry.test/namespace/image:v1",
+            "CVE-2024-9999",
+        ));
+        let itself = classified(dummy_id());
+
+        for source in [unclassified, other_cve, itself] {
+            let err = vuln
+                .inherit_classification(&source, Some(&dummy_context()))
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ManagedVulnerabilityError::InvalidClassificationSource(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn apply_registered_event_should_fail_on_existing_aggregate() {
+        let id = dummy_id();
+        let mut vuln = registered(id.clone());
+
+        let result = vuln.apply(&ManagedVulnerability::register(id));
+        assert_eq!(
+            result.unwrap_err(),
+            ManagedVulnerabilityError::RegisteredEventNotAllowed
+        );
+    }
+
+    #[test]
+    fn apply_event_of_another_aggregate_should_fail() {
+        let mut vuln = registered(dummy_id());
+        let other = managed_id("registry.test/namespace/image:v1", "CVE-2024-1234");
+
+        let result = vuln.apply(
+            &ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated { id: other },
+        );
+        assert!(matches!(
+            result,
+            Err(ManagedVulnerabilityError::InconsistentIds(_, _))
+        ));
+    }
+
+    #[test]
+    fn rehydrate_should_reject_illegal_transitions() {
+        let id = dummy_id();
+        let events = vec![
+            ManagedVulnerability::register(id.clone()),
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated { id },
+        ];
+
+        let result = ManagedVulnerability::rehydrate(&events);
+        assert!(matches!(
+            result,
+            Err(ManagedVulnerabilityError::NotClassified(_))
+        ));
+    }
+
+    #[test]
+    fn rehydrate_should_rebuild_complete_state() {
+        let id = dummy_id();
+        let first = Classification::new(Severity::Critical, dummy_context());
+        let second = Classification::new(Severity::Low, dummy_context());
+
+        let events = vec![
+            ManagedVulnerability::register(id.clone()),
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassified {
+                id: id.clone(),
+                classification: first,
+            },
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated {
+                id: id.clone(),
+            },
+            ManagedVulnerabilityEvent::ManagedVulnerabilityClassified {
+                id: id.clone(),
+                classification: second.clone(),
+            },
+        ];
+
+        let vuln = ManagedVulnerability::rehydrate(&events).unwrap();
+        assert_eq!(vuln.id(), &id);
+        assert_eq!(vuln.state(), &ClassificationState::Classified(second));
+    }
+
+    #[test]
+    fn try_from_non_initial_event_should_fail() {
+        let event = ManagedVulnerabilityEvent::ManagedVulnerabilityClassificationOutdated {
+            id: dummy_id(),
+        };
+
+        let result = ManagedVulnerability::try_from(&event);
+        assert!(matches!(
+            result,
+            Err(ManagedVulnerabilityError::InvalidInitialEvent)
+        ));
+    }
+}
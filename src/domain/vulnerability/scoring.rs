@@ -0,0 +1,462 @@
+use crate::domain::component::context::{DataSensitivity, ExecutionContext, Exposure, Isolation};
+use crate::domain::vulnerability::classification::Severity;
+use crate::domain::vulnerability::cvss::{Cvss, v3, v4};
+use crate::domain::vulnerability::id::ManagedVulnerabilityId;
+use std::fmt;
+
+/// Attribute of an execution context that can adjust a CVSS metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContextAttribute {
+    Exposure(Exposure),
+    Isolation(Isolation),
+    DataSensitivity(DataSensitivity),
+}
+
+impl fmt::Display for ContextAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exposure(v) => write!(f, "exposure={v}"),
+            Self::Isolation(v) => write!(f, "isolation={v}"),
+            Self::DataSensitivity(v) => write!(f, "data-sensitivity={v}"),
+        }
+    }
+}
+
+/// One step of the explanation trace: a context attribute that changed a metric.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Adjustment {
+    attribute: ContextAttribute,
+    metric: &'static str,
+    from: &'static str,
+    to: &'static str,
+}
+
+impl Adjustment {
+    #[must_use]
+    pub const fn attribute(&self) -> ContextAttribute {
+        self.attribute
+    }
+
+    /// Abbreviation of the environmental metric that was set (e.g. `MAV`).
+    #[must_use]
+    pub const fn metric(&self) -> &'static str {
+        self.metric
+    }
+
+    /// Value the metric effectively had before the adjustment.
+    #[must_use]
+    pub const fn from(&self) -> &'static str {
+        self.from
+    }
+
+    #[must_use]
+    pub const fn to(&self) -> &'static str {
+        self.to
+    }
+}
+
+impl fmt::Display for Adjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} {} -> {}",
+            self.attribute, self.metric, self.from, self.to
+        )
+    }
+}
+
+/// Score of a vulnerability in the execution context of a given component.
+///
+/// The context is mapped onto environmental metrics that the vector leaves Not Defined:
+///
+/// - exposure lowers the attack vector (`internal` to Adjacent, `isolated` to Local);
+/// - a sandboxed component contains the impact on subsequent systems (v3.1 scope becomes
+///   Unchanged, v4.0 high subsequent impacts become Low);
+/// - data sensitivity sets the confidentiality requirement, and restricted data also
+///   raises the integrity requirement.
+///
+/// Environmental metrics already present in the vector are assessments made by an analyst,
+/// so they take precedence and are never overridden. Every metric actually changed is
+/// recorded in [`Self::trace`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextualScore {
+    id: ManagedVulnerabilityId,
+    original: Cvss,
+    contextual: Cvss,
+    trace: Vec<Adjustment>,
+}
+
+impl ContextualScore {
+    /// Score the vulnerability identified by `id`, described by `cvss`, in `context`.
+    #[must_use]
+    pub fn evaluate(id: ManagedVulnerabilityId, cvss: &Cvss, context: &ExecutionContext) -> Self {
+        let mut trace = Vec::new();
+        let contextual = match cvss {
+            Cvss::V3(cvss) => Cvss::V3(adjust_v3(cvss, context, &mut trace)),
+            Cvss::V4(cvss) => Cvss::V4(adjust_v4(cvss, context, &mut trace)),
+        };
+        Self {
+            id,
+            original: *cvss,
+            contextual,
+            trace,
+        }
+    }
+
+    #[must_use]
+    pub const fn id(&self) -> &ManagedVulnerabilityId {
+        &self.id
+    }
+
+    /// Vector as published, without context adjustments.
+    #[must_use]
+    pub const fn original(&self) -> &Cvss {
+        &self.original
+    }
+
+    /// Vector with the environmental metrics derived from the execution context.
+    #[must_use]
+    pub const fn contextual(&self) -> &Cvss {
+        &self.contextual
+    }
+
+    #[must_use]
+    pub fn original_score(&self) -> f64 {
+        self.original.score()
+    }
+
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        self.contextual.score()
+    }
+
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.contextual.severity()
+    }
+
+    /// Metrics changed by the context, in the order they were applied.
+    #[must_use]
+    pub fn trace(&self) -> &[Adjustment] {
+        &self.trace
+    }
+}
+
+/// Set an undefined environmental metric to `to` if it differs from its effective value.
+fn adjust<T: Copy + PartialEq>(
+    slot: &mut Option<T>,
+    effective: T,
+    to: T,
+    abbreviation: fn(T) -> &'static str,
+    attribute: ContextAttribute,
+    metric: &'static str,
+    trace: &mut Vec<Adjustment>,
+) {
+    if slot.is_none() && effective != to {
+        *slot = Some(to);
+        trace.push(Adjustment {
+            attribute,
+            metric,
+            from: abbreviation(effective),
+            to: abbreviation(to),
+        });
+    }
+}
+
+fn adjust_v3(
+    cvss: &v3::CvssV3,
+    context: &ExecutionContext,
+    trace: &mut Vec<Adjustment>,
+) -> v3::CvssV3 {
+    use v3::{AttackVector, Requirement, Scope};
+
+    let base = cvss.base();
+    let mut env = *cvss.environmental();
+
+    let exposure = ContextAttribute::Exposure(context.exposure());
+    let attack_vector = match (context.exposure(), base.attack_vector) {
+        (Exposure::Internal, AttackVector::Network) => Some(AttackVector::Adjacent),
+        (Exposure::Isolated, AttackVector::Network | AttackVector::Adjacent) => {
+            Some(AttackVector::Local)
+        }
+        _ => None,
+    };
+    if let Some(to) = attack_vector {
+        adjust(
+            &mut env.modified_attack_vector,
+            base.attack_vector,
+            to,
+            AttackVector::abbreviation,
+            exposure,
+            "MAV",
+            trace,
+        );
+    }
+
+    if context.isolation() == Isolation::Sandboxed {
+        adjust(
+            &mut env.modified_scope,
+            base.scope,
+            Scope::Unchanged,
+            Scope::abbreviation,
+            ContextAttribute::Isolation(context.isolation()),
+            "MS",
+            trace,
+        );
+    }
+
+    // Not Defined requirements weigh as Medium in CVSS v3.1.
+    let sensitivity = ContextAttribute::DataSensitivity(context.data_sensitivity());
+    let (confidentiality, integrity) = match context.data_sensitivity() {
+        DataSensitivity::Public => (Requirement::Low, None),
+        DataSensitivity::Internal => (Requirement::Medium, None),
+        DataSensitivity::Confidential => (Requirement::High, None),
+        DataSensitivity::Restricted => (Requirement::High, Some(Requirement::High)),
+    };
+    adjust(
+        &mut env.confidentiality_requirement,
+        Requirement::Medium,
+        confidentiality,
+        Requirement::abbreviation,
+        sensitivity,
+        "CR",
+        trace,
+    );
+    if let Some(to) = integrity {
+        adjust(
+            &mut env.integrity_requirement,
+            Requirement::Medium,
+            to,
+            Requirement::abbreviation,
+            sensitivity,
+            "IR",
+            trace,
+        );
+    }
+
+    v3::CvssV3::new(*base, *cvss.temporal(), env)
+}
+
+fn adjust_v4(
+    cvss: &v4::CvssV4,
+    context: &ExecutionContext,
+    trace: &mut Vec<Adjustment>,
+) -> v4::CvssV4 {
+    use v4::{AttackVector, Impact, Requirement, SubsequentImpact};
+
+    let base = cvss.base();
+    let mut env = *cvss.environmental();
+
+    let exposure = ContextAttribute::Exposure(context.exposure());
+    let attack_vector = match (context.exposure(), base.attack_vector) {
+        (Exposure::Internal, AttackVector::Network) => Some(AttackVector::Adjacent),
+        (Exposure::Isolated, AttackVector::Network | AttackVector::Adjacent) => {
+            Some(AttackVector::Local)
+        }
+        _ => None,
+    };
+    if let Some(to) = attack_vector {
+        adjust(
+            &mut env.modified_attack_vector,
+            base.attack_vector,
+            to,
+            AttackVector::abbreviation,
+            exposure,
+            "MAV",
+            trace,
+        );
+    }
+
+    if context.isolation() == Isolation::Sandboxed {
+        let isolation = ContextAttribute::Isolation(context.isolation());
+        if base.subsequent_confidentiality == Impact::High {
+            adjust(
+                &mut env.modified_subsequent_confidentiality,
+                Impact::High,
+                Impact::Low,
+                Impact::abbreviation,
+                isolation,
+                "MSC",
+                trace,
+            );
+        }
+        if base.subsequent_integrity == Impact::High {
+            adjust(
+                &mut env.modified_subsequent_integrity,
+                SubsequentImpact::High,
+                SubsequentImpact::Low,
+                SubsequentImpact::abbreviation,
+                isolation,
+                "MSI",
+                trace,
+            );
+        }
+        if base.subsequent_availability == Impact::High {
+            adjust(
+                &mut env.modified_subsequent_availability,
+                SubsequentImpact::High,
+                SubsequentImpact::Low,
+                SubsequentImpact::abbreviation,
+                isolation,
+                "MSA",
+                trace,
+            );
+        }
+    }
+
+    // Not Defined requirements weigh as High in CVSS v4.0.
+    let sensitivity = ContextAttribute::DataSensitivity(context.data_sensitivity());
+    let confidentiality = match context.data_sensitivity() {
+        DataSensitivity::Public => Requirement::Low,
+        DataSensitivity::Internal => Requirement::Medium,
+        DataSensitivity::Confidential | DataSensitivity::Restricted => Requirement::High,
+    };
+    adjust(
+        &mut env.confidentiality_requirement,
+        Requirement::High,
+        confidentiality,
+        Requirement::abbreviation,
+        sensitivity,
+        "CR",
+        trace,
+    );
+
+    v4::CvssV4::new(*base, *cvss.threat(), env, *cvss.supplemental())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::id::ComponentId;
+    use crate::domain::vulnerability::id::VulnerabilityId;
+    use std::str::FromStr;
+
+    fn dummy_id() -> ManagedVulnerabilityId {
+        ManagedVulnerabilityId::new(
+            ComponentId::from_str("registry.test/namespace/image:v0").unwrap(),
+            VulnerabilityId::new("CVE-2024-1234").unwrap(),
+        )
+    }
+
+    fn context(
+        exposure: Exposure,
+        isolation: Isolation,
+        sensitivity: DataSensitivity,
+    ) -> ExecutionContext {
+        ExecutionContext::new(
+            exposure,
+            isolation,
+            sensitivity,
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+        )
+        .unwrap()
+    }
+
+    fn evaluate(vector: &str, context: &ExecutionContext) -> ContextualScore {
+        ContextualScore::evaluate(dummy_id(), &Cvss::from_str(vector).unwrap(), context)
+    }
+
+    fn trace(score: &ContextualScore) -> Vec<String> {
+        score.trace().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn worst_case_context_should_keep_published_score() {
+        let ctx = context(
+            Exposure::InternetFacing,
+            Isolation::Root,
+            DataSensitivity::Internal,
+        );
+        let score = evaluate("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", &ctx);
+
+        assert!(score.trace().is_empty());
+        assert_eq!(score.contextual(), score.original());
+        assert_eq!(score.score(), 9.8);
+    }
+
+    #[test]
+    fn isolated_public_component_should_downgrade_v3() {
+        let ctx = context(
+            Exposure::Isolated,
+            Isolation::Sandboxed,
+            DataSensitivity::Public,
+        );
+        let score = evaluate("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H", &ctx);
+
+        assert_eq!(
+            trace(&score),
+            vec![
+                "exposure=isolated: MAV N -> L",
+                "isolation=sandboxed: MS C -> U",
+                "data-sensitivity=public: CR M -> L",
+            ]
+        );
+        assert_eq!(score.original_score(), 10.0);
+        assert_eq!(
+            score.contextual().to_string(),
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H/CR:L/MAV:L/MS:U"
+        );
+        assert_eq!(score.score(), 8.1);
+        assert_eq!(score.severity(), Severity::High);
+    }
+
+    #[test]
+    fn restricted_data_should_raise_requirements_v3() {
+        let ctx = context(
+            Exposure::InternetFacing,
+            Isolation::Privileged,
+            DataSensitivity::Restricted,
+        );
+        let score = evaluate("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:U/C:L/I:L/A:N", &ctx);
+
+        assert_eq!(
+            trace(&score),
+            vec![
+                "data-sensitivity=restricted: CR M -> H",
+                "data-sensitivity=restricted: IR M -> H",
+            ]
+        );
+        assert!(score.score() > score.original_score());
+    }
+
+    #[test]
+    fn internal_sandboxed_component_should_downgrade_v4() {
+        let ctx = context(
+            Exposure::Internal,
+            Isolation::Sandboxed,
+            DataSensitivity::Internal,
+        );
+        let score = evaluate(
+            "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:H/SI:H/SA:N",
+            &ctx,
+        );
+
+        assert_eq!(
+            trace(&score),
+            vec![
+                "exposure=internal: MAV N -> A",
+                "isolation=sandboxed: MSC H -> L",
+                "isolation=sandboxed: MSI H -> L",
+                "data-sensitivity=internal: CR H -> M",
+            ]
+        );
+        assert!(score.score() < score.original_score());
+    }
+
+    #[test]
+    fn analyst_metrics_should_take_precedence() {
+        let ctx = context(
+            Exposure::Isolated,
+            Isolation::Sandboxed,
+            DataSensitivity::Public,
+        );
+        let score = evaluate(
+            "CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N/CR:H/MAV:N",
+            &ctx,
+        );
+
+        assert!(score.trace().is_empty());
+        assert_eq!(score.score(), score.original_score());
+    }
+}
@@ -0,0 +1,148 @@
+use super::semver::SemVer;
+use super::{VersionError, VersionScheme};
+use std::fmt;
+use std::str::FromStr;
+
+const TIMESTAMP_LENGTH: usize = 14;
+const REVISION_LENGTH: usize = 12;
+
+/// Version of a Go module: a semantic version, with or without its `v` prefix.
+///
+/// [Pseudo-versions](https://go.dev/ref/mod#pseudo-versions) such as
+/// `v0.0.0-20191109021931-daa7c04131f5` are pre-releases of the version following their
+/// base, so they sort between both by commit time. The `+incompatible` suffix of modules
+/// predating Go modules is ignored by comparisons.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GoVersion {
+    version: SemVer,
+}
+
+impl GoVersion {
+    /// Whether this is a pseudo-version, pointing to a commit rather than a tag.
+    #[must_use]
+    pub fn is_pseudo(&self) -> bool {
+        self.pseudo().is_some()
+    }
+
+    /// Commit time of a pseudo-version, as `yyyymmddhhmmss` in UTC.
+    #[must_use]
+    pub fn timestamp(&self) -> Option<String> {
+        self.pseudo().map(|(timestamp, _)| timestamp)
+    }
+
+    /// Abbreviated commit hash of a pseudo-version.
+    #[must_use]
+    pub fn revision(&self) -> Option<String> {
+        self.pseudo().map(|(_, revision)| revision)
+    }
+
+    /// Timestamp and revision, read from the last pre-release identifier. It stands alone
+    /// for `vX.0.0-` pseudo-versions, and follows a `0` identifier otherwise.
+    fn pseudo(&self) -> Option<(String, String)> {
+        let pre: Vec<String> = self.version.pre_release().collect();
+        let (last, base) = pre.split_last()?;
+        let standalone = base.is_empty() && self.version.minor() == 0 && self.version.patch() == 0;
+        if !standalone && base.last().is_none_or(|identifier| identifier != "0") {
+            return None;
+        }
+
+        let (timestamp, revision) = last.split_once('-')?;
+        let valid = timestamp.len() == TIMESTAMP_LENGTH
+            && timestamp.bytes().all(|b| b.is_ascii_digit())
+            && revision.len() == REVISION_LENGTH
+            && revision
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        valid.then(|| (timestamp.to_string(), revision.to_string()))
+    }
+}
+
+impl FromStr for GoVersion {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = SemVer::parse(s.strip_prefix('v').unwrap_or(s), VersionScheme::Go)
+            .map_err(|_| VersionError::Invalid(VersionScheme::Go, s.to_string()))?;
+        Ok(Self { version })
+    }
+}
+
+impl fmt::Display for GoVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> GoVersion {
+        GoVersion::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn pseudo_versions_sort_between_their_base_and_the_next_version() {
+        let ascending = [
+            "v0.0.0-20191109021931-daa7c04131f5",
+            "v0.0.0-20200101000000-0123456789ab",
+            "v0.0.1",
+            "v1.2.3",
+            "v1.2.4-0.20191109021931-daa7c04131f5",
+            "v1.2.4-0.20210101000000-0123456789ab",
+            "v1.2.4-pre",
+            "v1.2.4-pre.0.20191109021931-daa7c04131f5",
+            "v1.2.4",
+            "v2.0.0+incompatible",
+            "v2.0.1",
+        ];
+
+        for pair in ascending.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{pair:?}");
+        }
+        assert_eq!(version("v2.0.0+incompatible"), version("2.0.0"));
+    }
+
+    #[test]
+    fn pseudo_versions_expose_their_commit() {
+        let cases = [
+            (
+                "v0.0.0-20191109021931-daa7c04131f5",
+                Some("20191109021931"),
+                Some("daa7c04131f5"),
+            ),
+            (
+                "v1.2.4-0.20191109021931-daa7c04131f5",
+                Some("20191109021931"),
+                Some("daa7c04131f5"),
+            ),
+            (
+                "v1.2.4-rc.1.0.20191109021931-daa7c04131f5",
+                Some("20191109021931"),
+                Some("daa7c04131f5"),
+            ),
+            ("v1.2.4-20191109021931-daa7c04131f5", None, None),
+            ("v0.0.0-2019-daa7c04131f5", None, None),
+            ("v0.0.0-20191109021931-DAA7C04131F5", None, None),
+            ("v1.2.4", None, None),
+        ];
+
+        for (s, timestamp, revision) in cases {
+            let version = version(s);
+            assert_eq!(version.timestamp().as_deref(), timestamp, "{s}");
+            assert_eq!(version.revision().as_deref(), revision, "{s}");
+            assert_eq!(version.is_pseudo(), timestamp.is_some(), "{s}");
+        }
+    }
+
+    #[test]
+    fn invalid_versions_are_rejected() {
+        for s in ["", "v1", "v1.2", "vv1.2.3", "1.2.3.4", "latest"] {
+            assert_eq!(
+                GoVersion::from_str(s).unwrap_err(),
+                VersionError::Invalid(VersionScheme::Go, s.to_string()),
+                "{s}"
+            );
+        }
+    }
+}
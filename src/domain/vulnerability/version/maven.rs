@@ -0,0 +1,332 @@
+use super::{VersionError, VersionScheme};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Qualifiers known to Maven, in ascending order; the empty one stands for the release.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// Version of a Maven artifact, ordered as by Maven's `ComparableVersion`.
+///
+/// Any string is a valid Maven version: it is split into numbers and qualifiers on `.`, `-`
+/// and transitions between digits and letters, trailing zeros and release qualifiers
+/// (`ga`, `final`, `release`) are dropped, and qualifiers sort as
+/// `alpha < beta < milestone < rc = cr < snapshot < release < sp < unknown qualifiers`.
+#[derive(Debug, Clone)]
+pub struct MavenVersion {
+    value: String,
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    /// Digits, without leading zeros so that numbers of any size compare by length first.
+    Number(String),
+    Qualifier(String),
+    /// Items following a `-` or a transition between digits and letters.
+    List(Vec<Item>),
+}
+
+impl Item {
+    fn number(digits: &str) -> Self {
+        Self::Number(digits.trim_start_matches('0').to_string())
+    }
+
+    /// Qualifier, expanding the `a`, `b` and `m` shorthands when directly followed by digits.
+    fn qualifier(value: &str, followed_by_digit: bool) -> Self {
+        let value = match value {
+            "a" if followed_by_digit => "alpha",
+            "b" if followed_by_digit => "beta",
+            "m" if followed_by_digit => "milestone",
+            "ga" | "final" | "release" => "",
+            "cr" => "rc",
+            value => value,
+        };
+        Self::Qualifier(value.to_string())
+    }
+
+    fn parse(value: &str, is_digit: bool) -> Self {
+        if is_digit {
+            Self::number(value)
+        } else {
+            Self::qualifier(value, false)
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            Self::Number(digits) => digits.is_empty(),
+            Self::Qualifier(value) => value.is_empty(),
+            Self::List(items) => items.is_empty(),
+        }
+    }
+
+    /// Compare with a missing item, as when the other version is shorter.
+    fn compare_to_missing(&self) -> Ordering {
+        match self {
+            Self::Number(digits) if digits.is_empty() => Ordering::Equal,
+            Self::Number(_) => Ordering::Greater,
+            Self::Qualifier(value) => qualifier_rank(value).cmp(&qualifier_rank("")),
+            Self::List(items) => items
+                .first()
+                .map_or(Ordering::Equal, Self::compare_to_missing),
+        }
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+            (Self::Qualifier(a), Self::Qualifier(b)) => qualifier_rank(a).cmp(&qualifier_rank(b)),
+            (Self::List(a), Self::List(b)) => compare_items(a, b),
+            (Self::Number(_), _) | (Self::List(_), Self::Qualifier(_)) => Ordering::Greater,
+            (Self::Qualifier(_), _) | (Self::List(_), Self::Number(_)) => Ordering::Less,
+        }
+    }
+}
+
+/// Rank of a qualifier: its position among the known ones, unknown ones last and sorted
+/// alphabetically.
+fn qualifier_rank(value: &str) -> (usize, &str) {
+    QUALIFIERS
+        .iter()
+        .position(|known| *known == value)
+        .map_or((QUALIFIERS.len(), value), |position| (position, ""))
+}
+
+fn compare_items(a: &[Item], b: &[Item]) -> Ordering {
+    let mut a = a.iter();
+    let mut b = b.iter();
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(a), None) => a.compare_to_missing(),
+            (None, Some(b)) => b.compare_to_missing().reverse(),
+            (Some(a), Some(b)) => a.compare(b),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+/// Drop the trailing null items of a list, up to its last number or qualifier.
+fn normalize(items: &mut Vec<Item>) {
+    for i in (0..items.len()).rev() {
+        if items[i].is_null() {
+            items.remove(i);
+        } else if !matches!(items[i], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+impl FromStr for MavenVersion {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_lowercase();
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            return Err(VersionError::Invalid(VersionScheme::Maven, s.to_string()));
+        }
+
+        // Lists being filled, innermost last; each one is attached to its parent at the end.
+        let mut lists: Vec<Vec<Item>> = vec![Vec::new()];
+        let mut is_digit = false;
+        let mut start = 0;
+        for (i, c) in value.char_indices() {
+            let list = lists.last_mut().expect("the root list is never closed");
+            match c {
+                '.' | '-' => {
+                    list.push(if i == start {
+                        Item::number("0")
+                    } else {
+                        Item::parse(&value[start..i], is_digit)
+                    });
+                    start = i + 1;
+                    if c == '-' {
+                        lists.push(Vec::new());
+                    }
+                }
+                c if c.is_ascii_digit() => {
+                    if !is_digit && i > start {
+                        list.push(Item::qualifier(&value[start..i], true));
+                        start = i;
+                        lists.push(Vec::new());
+                    }
+                    is_digit = true;
+                }
+                _ => {
+                    if is_digit && i > start {
+                        list.push(Item::number(&value[start..i]));
+                        start = i;
+                        lists.push(Vec::new());
+                    }
+                    is_digit = false;
+                }
+            }
+        }
+        if value.len() > start {
+            // A trailing qualifier after a `.` is read as if it followed a `-`.
+            if !is_digit && lists.last().is_some_and(|list| !list.is_empty()) {
+                lists.push(Vec::new());
+            }
+            let item = Item::parse(&value[start..], is_digit);
+            lists
+                .last_mut()
+                .expect("the root list is never closed")
+                .push(item);
+        }
+
+        let mut items = lists.pop().unwrap_or_default();
+        normalize(&mut items);
+        while let Some(mut parent) = lists.pop() {
+            parent.push(Item::List(items));
+            normalize(&mut parent);
+            items = parent;
+        }
+
+        Ok(Self {
+            value: s.trim().to_string(),
+            items,
+        })
+    }
+}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_items(&self.items, &other.items)
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl fmt::Display for MavenVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> MavenVersion {
+        MavenVersion::from_str(s).unwrap()
+    }
+
+    fn assert_ascending(versions: &[&str]) {
+        for pair in versions.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{pair:?}");
+            assert!(version(pair[1]) > version(pair[0]), "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn qualifiers_sort_around_the_release() {
+        assert_ascending(&[
+            "1-alpha2snapshot",
+            "1-alpha2",
+            "1-alpha-123",
+            "1-beta-2",
+            "1-beta123",
+            "1-m2",
+            "1-m11",
+            "1-rc",
+            "1-cr2",
+            "1-rc123",
+            "1-SNAPSHOT",
+            "1",
+            "1-sp",
+            "1-sp2",
+            "1-sp123",
+            "1-abc",
+            "1-def",
+            "1-pom-1",
+            "1-1-snapshot",
+            "1-1",
+            "1-2",
+            "1-123",
+        ]);
+    }
+
+    #[test]
+    fn numbers_sort_numerically() {
+        assert_ascending(&[
+            "2.0",
+            "2.0.a",
+            "2-1",
+            "2.0.2",
+            "2.0.123",
+            "2.1.0",
+            "2.1-a",
+            "2.1b",
+            "2.1-c",
+            "2.1-1",
+            "2.1.0.1",
+            "2.2",
+            "2.123",
+            "11.a2",
+            "11.a11",
+            "11.b2",
+            "11.b11",
+            "11.m2",
+            "11.m11",
+            "11",
+            "11.a",
+            "11b",
+            "11c",
+            "11m",
+            "99999999999999999999999",
+            "100000000000000000000000",
+        ]);
+    }
+
+    #[test]
+    fn equivalent_spellings_are_equal() {
+        let cases = [
+            ("1", "1.0.0"),
+            ("1", "1-0"),
+            ("1", "1.0-0"),
+            ("1", "1-ga"),
+            ("1", "1.0.FINAL"),
+            ("1", "1-release"),
+            ("1.0.0.Final", "1"),
+            ("1a1", "1-alpha-1"),
+            ("1b2", "1-beta-2"),
+            ("1m3", "1-milestone-3"),
+            ("1.0-RC1", "1.0-rc-1"),
+            ("1cr", "1rc"),
+            ("1x", "1-x"),
+            ("1.1", "1.01"),
+        ];
+
+        for (a, b) in cases {
+            assert_eq!(version(a), version(b), "{a} = {b}");
+        }
+        assert_ne!(version("1a"), version("1-alpha"));
+        assert_eq!(version("2.3.1.Final").to_string(), "2.3.1.Final");
+    }
+
+    #[test]
+    fn blank_versions_are_rejected() {
+        for s in ["", " ", "1 0"] {
+            assert_eq!(
+                MavenVersion::from_str(s).unwrap_err(),
+                VersionError::Invalid(VersionScheme::Maven, s.to_string()),
+                "{s:?}"
+            );
+        }
+    }
+}
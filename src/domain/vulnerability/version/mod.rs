@@ -0,0 +1,483 @@
+pub mod go;
+pub mod maven;
+pub mod pep440;
+pub mod semver;
+
+use crate::domain::vulnerability::entity::{RangeEvent, RangeKind};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Version introduced by OSV ranges affecting every version up to their next event.
+const EARLIEST: &str = "0";
+
+/// Rules ordering the versions of packages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionScheme {
+    /// [`semver::SemVer`], for npm and Cargo packages.
+    Semver,
+    /// [`pep440::Pep440Version`], for Python packages.
+    Pep440,
+    /// [`maven::MavenVersion`], for Maven artifacts.
+    Maven,
+    /// [`go::GoVersion`], for Go modules.
+    Go,
+}
+
+impl VersionScheme {
+    /// Scheme of an OSV ecosystem (e.g. `PyPI`), if its versions can be compared.
+    #[must_use]
+    pub fn for_ecosystem(ecosystem: &str) -> Option<Self> {
+        match ecosystem {
+            "npm" | "crates.io" => Some(Self::Semver),
+            "PyPI" => Some(Self::Pep440),
+            "Maven" => Some(Self::Maven),
+            "Go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    /// Scheme ordering the versions of a range of `kind` on a package of `ecosystem`, or
+    /// `None` for [`RangeKind::Git`] ranges, whose commits have no order of their own.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VersionError::UnsupportedEcosystem`] for a [`RangeKind::Ecosystem`] range of
+    /// an ecosystem without scheme.
+    pub fn for_range(kind: RangeKind, ecosystem: &str) -> Result<Option<Self>, VersionError> {
+        match (kind, Self::for_ecosystem(ecosystem)) {
+            // Go modules are semantic versions, written with or without their `v` prefix.
+            (RangeKind::Semver, Some(Self::Go)) => Ok(Some(Self::Go)),
+            (RangeKind::Semver, _) => Ok(Some(Self::Semver)),
+            (RangeKind::Ecosystem, Some(scheme)) => Ok(Some(scheme)),
+            (RangeKind::Ecosystem, None) => {
+                Err(VersionError::UnsupportedEcosystem(ecosystem.to_string()))
+            }
+            (RangeKind::Git, _) => Ok(None),
+        }
+    }
+
+    /// Compare two versions of this scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VersionError::Invalid`] if either version is not valid in this scheme.
+    pub fn compare(self, a: &str, b: &str) -> Result<Ordering, VersionError> {
+        match self {
+            Self::Semver => compare::<semver::SemVer>(a, b),
+            Self::Pep440 => compare::<pep440::Pep440Version>(a, b),
+            Self::Maven => compare::<maven::MavenVersion>(a, b),
+            Self::Go => compare::<go::GoVersion>(a, b),
+        }
+    }
+
+    /// Whether `version` lies within the range delimited by `events`.
+    ///
+    /// Events are sorted by version, `0` first, and the last one reached by `version` wins:
+    /// `introduced` enters the range, `fixed` and `limit` leave it at their version, and
+    /// `last_affected` leaves it right after its version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VersionError::Invalid`] if `version` or the version of an event is not
+    /// valid in this scheme.
+    pub fn contains(self, events: &[RangeEvent], version: &str) -> Result<bool, VersionError> {
+        match self {
+            Self::Semver => contains::<semver::SemVer>(events, version),
+            Self::Pep440 => contains::<pep440::Pep440Version>(events, version),
+            Self::Maven => contains::<maven::MavenVersion>(events, version),
+            Self::Go => contains::<go::GoVersion>(events, version),
+        }
+    }
+}
+
+impl fmt::Display for VersionScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Semver => "semver",
+            Self::Pep440 => "PEP 440",
+            Self::Maven => "Maven",
+            Self::Go => "Go",
+        };
+        f.write_str(s)
+    }
+}
+
+fn compare<V>(a: &str, b: &str) -> Result<Ordering, VersionError>
+where
+    V: Ord + FromStr<Err = VersionError>,
+{
+    Ok(a.parse::<V>()?.cmp(&b.parse::<V>()?))
+}
+
+fn contains<V>(events: &[RangeEvent], version: &str) -> Result<bool, VersionError>
+where
+    V: Ord + FromStr<Err = VersionError>,
+{
+    let version: V = version.parse()?;
+    let mut bounds = events
+        .iter()
+        .map(|event| match event {
+            RangeEvent::Introduced(v) if v == EARLIEST => Ok((None, event)),
+            _ => Ok((Some(event.version().parse::<V>()?), event)),
+        })
+        .collect::<Result<Vec<_>, VersionError>>()?;
+    bounds.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut affected = false;
+    for (bound, event) in bounds {
+        let reached = bound.as_ref().is_none_or(|bound| version >= *bound);
+        match event {
+            RangeEvent::Introduced(_) if reached => affected = true,
+            RangeEvent::Fixed(_) | RangeEvent::Limit(_) if reached => affected = false,
+            RangeEvent::LastAffected(_) if bound.is_some_and(|bound| version > bound) => {
+                affected = false;
+            }
+            _ => {}
+        }
+    }
+    Ok(affected)
+}
+
+/// Errors raised while comparing versions.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum VersionError {
+    #[error("Invalid {0} version `{1}`")]
+    Invalid(VersionScheme, String),
+
+    #[error("Versions of ecosystem `{0}` cannot be compared")]
+    UnsupportedEcosystem(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::vulnerability::entity::{AffectedPackage, AffectedRange};
+
+    /// Events as `(kind, version)` pairs, named as in OSV records.
+    type Events<'a> = &'a [(&'a str, &'a str)];
+
+    fn events(events: Events<'_>) -> Vec<RangeEvent> {
+        events
+            .iter()
+            .map(|(kind, version)| {
+                let version = (*version).to_string();
+                match *kind {
+                    "introduced" => RangeEvent::Introduced(version),
+                    "fixed" => RangeEvent::Fixed(version),
+                    "last_affected" => RangeEvent::LastAffected(version),
+                    "limit" => RangeEvent::Limit(version),
+                    kind => panic!("Unknown event {kind}"),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ranges_should_contain_versions_between_their_events() {
+        use VersionScheme::{Go, Maven, Pep440, Semver};
+
+        let cases: &[(VersionScheme, Events<'_>, &str, bool)] = &[
+            // Everything before the fix, pre-releases of the fix included.
+            (
+                Semver,
+                &[("introduced", "0"), ("fixed", "1.2.4")],
+                "0.0.1",
+                true,
+            ),
+            (
+                Semver,
+                &[("introduced", "0"), ("fixed", "1.2.4")],
+                "1.2.4-rc.1",
+                true,
+            ),
+            (
+                Semver,
+                &[("introduced", "0"), ("fixed", "1.2.4")],
+                "1.2.4",
+                false,
+            ),
+            (
+                Semver,
+                &[("introduced", "0"), ("fixed", "1.2.4")],
+                "1.2.4+build.5",
+                false,
+            ),
+            (
+                Semver,
+                &[("introduced", "1.0.0"), ("fixed", "1.2.4")],
+                "1.0.0-beta",
+                false,
+            ),
+            (
+                Semver,
+                &[("introduced", "1.0.0-beta.2"), ("fixed", "1.2.4")],
+                "1.0.0-beta.11",
+                true,
+            ),
+            // Several introduced/fixed pairs, in any order.
+            (
+                Semver,
+                &[
+                    ("fixed", "2.1.3"),
+                    ("introduced", "2.0.0"),
+                    ("introduced", "1.0.0"),
+                    ("fixed", "1.4.2"),
+                ],
+                "1.5.0",
+                false,
+            ),
+            (
+                Semver,
+                &[
+                    ("fixed", "2.1.3"),
+                    ("introduced", "2.0.0"),
+                    ("introduced", "1.0.0"),
+                    ("fixed", "1.4.2"),
+                ],
+                "2.1.0",
+                true,
+            ),
+            // The last affected version is included, unlike a fix.
+            (
+                Semver,
+                &[("introduced", "1.0.0"), ("last_affected", "1.2.5")],
+                "1.2.5",
+                true,
+            ),
+            (
+                Semver,
+                &[("introduced", "1.0.0"), ("last_affected", "1.2.5")],
+                "1.2.6-alpha",
+                false,
+            ),
+            (Semver, &[("introduced", "1.0.0")], "99.0.0", true),
+            (
+                Semver,
+                &[("introduced", "1.0.0"), ("limit", "2.0.0")],
+                "2.0.0",
+                false,
+            ),
+            (Semver, &[], "1.0.0", false),
+            // Development and pre-releases precede the release; post-releases follow it.
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "2.0")],
+                "2.0.dev1",
+                true,
+            ),
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "2.0")],
+                "2.0rc1",
+                true,
+            ),
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "2.0")],
+                "2.0.0",
+                false,
+            ),
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "2.0")],
+                "2.0.post1",
+                false,
+            ),
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "2.0.post1")],
+                "2.0",
+                true,
+            ),
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "2.0.post1")],
+                "2.0+local.7",
+                true,
+            ),
+            (
+                Pep440,
+                &[("introduced", "1.0"), ("last_affected", "1.4")],
+                "1.4.post2",
+                false,
+            ),
+            (
+                Pep440,
+                &[("introduced", "0"), ("fixed", "1.0")],
+                "1!0.5",
+                false,
+            ),
+            // Snapshots and milestones precede the release, service packs follow it.
+            (
+                Maven,
+                &[("introduced", "2.0"), ("fixed", "2.3.1")],
+                "2.3.1-SNAPSHOT",
+                true,
+            ),
+            (
+                Maven,
+                &[("introduced", "2.0"), ("fixed", "2.3.1")],
+                "2.3.1.Final",
+                false,
+            ),
+            (
+                Maven,
+                &[("introduced", "2.0"), ("fixed", "2.3.1")],
+                "2.0-M1",
+                false,
+            ),
+            (
+                Maven,
+                &[("introduced", "2.0"), ("fixed", "2.3.1")],
+                "2.3.0.sp1",
+                true,
+            ),
+            (
+                Maven,
+                &[("introduced", "0"), ("fixed", "9.4.51.v20230217")],
+                "9.4.50.v20221201",
+                true,
+            ),
+            (
+                Maven,
+                &[("introduced", "0"), ("last_affected", "1.0")],
+                "1.0-sp1",
+                false,
+            ),
+            // Pseudo-versions sort by commit time between the tags around them.
+            (
+                Go,
+                &[("introduced", "0"), ("fixed", "0.17.0")],
+                "v0.0.0-20220525230936-793ad666bf5e",
+                true,
+            ),
+            (
+                Go,
+                &[("introduced", "0"), ("fixed", "0.17.0")],
+                "v0.17.1-0.20230105000000-0123456789ab",
+                false,
+            ),
+            (
+                Go,
+                &[("introduced", "0"), ("fixed", "0.17.0")],
+                "v0.17.0-0.20230105000000-0123456789ab",
+                true,
+            ),
+            (
+                Go,
+                &[("introduced", "0"), ("fixed", "1.20.3")],
+                "1.20.3",
+                false,
+            ),
+            (
+                Go,
+                &[("introduced", "2.0.0+incompatible")],
+                "v2.1.0+incompatible",
+                true,
+            ),
+        ];
+
+        for (scheme, range, version, expected) in cases {
+            assert_eq!(
+                scheme.contains(&events(range), version),
+                Ok(*expected),
+                "{scheme} {version} in {range:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_versions_should_fail_the_evaluation() {
+        let range = events(&[("introduced", "0"), ("fixed", "1.2.4")]);
+        assert_eq!(
+            VersionScheme::Semver.contains(&range, "latest"),
+            Err(VersionError::Invalid(
+                VersionScheme::Semver,
+                "latest".to_string()
+            ))
+        );
+
+        let range = events(&[("introduced", "1.0"), ("fixed", "not-a-version")]);
+        assert_eq!(
+            VersionScheme::Pep440.contains(&range, "1.0"),
+            Err(VersionError::Invalid(
+                VersionScheme::Pep440,
+                "not-a-version".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn packages_should_be_affected_through_their_ranges_and_versions() {
+        let ecosystem = |introduced: &str, fixed: &str| {
+            AffectedRange::new(
+                RangeKind::Ecosystem,
+                events(&[("introduced", introduced), ("fixed", fixed)]),
+            )
+        };
+        let semver = AffectedRange::new(
+            RangeKind::Semver,
+            events(&[("introduced", "0"), ("fixed", "1.2.0")]),
+        );
+        let git = AffectedRange::new(
+            RangeKind::Git,
+            events(&[("introduced", "0"), ("fixed", "deadbeef")]),
+        )
+        .with_repository("https://github.com/org/repo");
+
+        let cases = [
+            (
+                AffectedPackage::new("PyPI", "django").with_ranges([ecosystem("4.0", "4.0.2")]),
+                "4.0.1",
+                Ok(true),
+            ),
+            (
+                AffectedPackage::new("PyPI", "django").with_ranges([ecosystem("4.0", "4.0.2")]),
+                "4.0.2",
+                Ok(false),
+            ),
+            (
+                AffectedPackage::new("PyPI", "django").with_versions(["4.1"]),
+                "4.1.0",
+                Ok(true),
+            ),
+            (
+                AffectedPackage::new("Go", "golang.org/x/net").with_ranges([semver.clone()]),
+                "v1.1.9",
+                Ok(true),
+            ),
+            (
+                AffectedPackage::new("npm", "minimist").with_ranges([semver.clone(), git.clone()]),
+                "1.2.0",
+                Ok(false),
+            ),
+            (
+                AffectedPackage::new("Debian:12", "openssl").with_versions(["3.0.11-1~deb12u1"]),
+                "3.0.11-1~deb12u1",
+                Ok(true),
+            ),
+            (
+                AffectedPackage::new("Debian:12", "openssl").with_ranges([git]),
+                "3.0.11",
+                Ok(false),
+            ),
+            (
+                AffectedPackage::new("Debian:12", "openssl")
+                    .with_ranges([ecosystem("0", "3.0.11-1~deb12u2")]),
+                "3.0.11-1~deb12u1",
+                Err(VersionError::UnsupportedEcosystem("Debian:12".to_string())),
+            ),
+        ];
+
+        for (package, version, expected) in cases {
+            assert_eq!(
+                package.affects(version),
+                expected,
+                "{} {version}",
+                package.name()
+            );
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use super::{VersionError, VersionScheme};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+const SEPARATORS: [char; 3] = ['.', '-', '_'];
+
+/// Version of a Python package, as specified by
+/// [PEP 440](https://packaging.python.org/en/latest/specifications/version-specifiers/).
+///
+/// Parsing is case-insensitive and accepts the spellings normalized by the specification,
+/// such as `1.0-ALPHA.1` for `1.0a1` or `1.0-1` for `1.0.post1`.
+#[derive(Debug, Clone)]
+pub struct Pep440Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(PreRelease, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    local: Vec<LocalSegment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum PreRelease {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+}
+
+/// Segment of a local version label; numeric segments sort after alphanumeric ones.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalSegment {
+    Alphanumeric(String),
+    Numeric(u64),
+}
+
+/// Sort key of an optional segment, which may sort before or after every present value.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Slot<T> {
+    Lowest,
+    Value(T),
+    Highest,
+}
+
+impl Pep440Version {
+    #[must_use]
+    pub const fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    #[must_use]
+    pub fn release(&self) -> &[u64] {
+        &self.release
+    }
+
+    /// Whether this is an alpha, beta, release candidate or development release.
+    #[must_use]
+    pub const fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    #[must_use]
+    pub const fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// Sort key: a development release precedes the pre-releases of its release, which
+    /// precede the release, itself followed by its local variants and post-releases.
+    fn key(&self) -> impl Ord + '_ {
+        let release_len = self
+            .release
+            .iter()
+            .rposition(|&n| n != 0)
+            .map_or(0, |last| last + 1);
+        let pre = match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => Slot::Lowest,
+            (None, _, _) => Slot::Highest,
+            (Some(pre), _, _) => Slot::Value(pre),
+        };
+        let post = self.post.map_or(Slot::Lowest, Slot::Value);
+        let dev = self.dev.map_or(Slot::Highest, Slot::Value);
+        (
+            self.epoch,
+            &self.release[..release_len],
+            pre,
+            post,
+            dev,
+            &self.local,
+        )
+    }
+}
+
+/// Cursor over the part of a version following its release segment.
+struct Segments<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Segments<'a> {
+    /// Consume one of `words`, optionally preceded by a separator, and return it.
+    fn keyword(&mut self, words: &[&'a str]) -> Option<&'a str> {
+        let rest = self.rest.strip_prefix(SEPARATORS).unwrap_or(self.rest);
+        let word = words.iter().find(|word| rest.starts_with(**word))?;
+        self.rest = &rest[word.len()..];
+        Some(word)
+    }
+
+    /// Consume the number of a segment, optionally preceded by a separator; `0` if absent.
+    fn number(&mut self) -> Option<u64> {
+        let rest = self.rest.strip_prefix(SEPARATORS).unwrap_or(self.rest);
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Some(0);
+        }
+        self.rest = &rest[digits..];
+        rest[..digits].parse().ok()
+    }
+
+    /// Consume an implicit post-release such as the `-1` of `1.0-1`.
+    fn implicit_post(&mut self) -> Option<u64> {
+        let rest = self.rest.strip_prefix('-')?;
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        self.rest = &rest[digits..];
+        rest[..digits].parse().ok()
+    }
+}
+
+impl FromStr for Pep440Version {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || VersionError::Invalid(VersionScheme::Pep440, s.to_string());
+        let normalized = s.trim().to_ascii_lowercase();
+        let version = normalized.strip_prefix('v').unwrap_or(&normalized);
+
+        let (version, local) = match version.split_once('+') {
+            Some((version, local)) => (version, Some(local)),
+            None => (version, None),
+        };
+        let local = local
+            .map(|local| {
+                local
+                    .split(SEPARATORS)
+                    .map(|segment| match segment.parse() {
+                        Ok(n) => Some(LocalSegment::Numeric(n)),
+                        Err(_)
+                            if !segment.is_empty()
+                                && segment.bytes().all(|b| b.is_ascii_alphanumeric()) =>
+                        {
+                            Some(LocalSegment::Alphanumeric(segment.to_string()))
+                        }
+                        Err(_) => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(invalid)
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let (epoch, version) = match version.split_once('!') {
+            Some((epoch, version)) => (epoch.parse().map_err(|_| invalid())?, version),
+            None => (0, version),
+        };
+
+        let release_end = version
+            .char_indices()
+            .find(|&(i, c)| {
+                !(c.is_ascii_digit()
+                    || c == '.' && version[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+            })
+            .map_or(version.len(), |(i, _)| i);
+        let release = version[..release_end]
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| invalid())?;
+
+        let mut segments = Segments {
+            rest: &version[release_end..],
+        };
+        let pre = match segments.keyword(&["alpha", "a", "beta", "b", "preview", "pre", "rc", "c"])
+        {
+            Some(kind) => {
+                let kind = match kind {
+                    "alpha" | "a" => PreRelease::Alpha,
+                    "beta" | "b" => PreRelease::Beta,
+                    _ => PreRelease::ReleaseCandidate,
+                };
+                Some((kind, segments.number().ok_or_else(invalid)?))
+            }
+            None => None,
+        };
+        let post = match segments.implicit_post() {
+            Some(post) => Some(post),
+            None => match segments.keyword(&["post", "rev", "r"]) {
+                Some(_) => Some(segments.number().ok_or_else(invalid)?),
+                None => None,
+            },
+        };
+        let dev = match segments.keyword(&["dev"]) {
+            Some(_) => Some(segments.number().ok_or_else(invalid)?),
+            None => None,
+        };
+        if !segments.rest.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440Version {}
+
+/// Normalized form, e.g. `1!2.0rc1.post2.dev3+ubuntu.1`.
+impl fmt::Display for Pep440Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release: Vec<String> = self.release.iter().map(ToString::to_string).collect();
+        f.write_str(&release.join("."))?;
+        if let Some((kind, n)) = self.pre {
+            let kind = match kind {
+                PreRelease::Alpha => "a",
+                PreRelease::Beta => "b",
+                PreRelease::ReleaseCandidate => "rc",
+            };
+            write!(f, "{kind}{n}")?;
+        }
+        if let Some(post) = self.post {
+            write!(f, ".post{post}")?;
+        }
+        if let Some(dev) = self.dev {
+            write!(f, ".dev{dev}")?;
+        }
+        if !self.local.is_empty() {
+            let local: Vec<String> = self
+                .local
+                .iter()
+                .map(|segment| match segment {
+                    LocalSegment::Alphanumeric(s) => s.clone(),
+                    LocalSegment::Numeric(n) => n.to_string(),
+                })
+                .collect();
+            write!(f, "+{}", local.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> Pep440Version {
+        Pep440Version::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn ordering_follows_the_specification() {
+        let ascending = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.0.15",
+            "1.1.dev1",
+            "1!0.1",
+        ];
+
+        for pair in ascending.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn alternative_spellings_are_normalized() {
+        let cases = [
+            ("1.0", "1.0.0", "1.0"),
+            ("v1.0", "1.0", "1.0"),
+            ("1.0-ALPHA.1", "1.0a1", "1.0a1"),
+            ("1.0.beta_2", "1.0b2", "1.0b2"),
+            ("1.0c1", "1.0rc1", "1.0rc1"),
+            ("1.0-preview-3", "1.0rc3", "1.0rc3"),
+            ("1.0a", "1.0a0", "1.0a0"),
+            ("1.0-1", "1.0.post1", "1.0.post1"),
+            ("1.0-r2", "1.0.post2", "1.0.post2"),
+            ("1.0.rev", "1.0.post0", "1.0.post0"),
+            ("1.0-dev", "1.0.dev0", "1.0.dev0"),
+            ("0!1.0+Ubuntu-1", "1.0+ubuntu.1", "1.0+ubuntu.1"),
+            (
+                "2!1.0rc1.post2.dev3",
+                "2!1.0rc1.post2.dev3",
+                "2!1.0rc1.post2.dev3",
+            ),
+        ];
+
+        for (spelling, normalized, display) in cases {
+            assert_eq!(version(spelling), version(normalized), "{spelling}");
+            assert_eq!(version(spelling).to_string(), display, "{spelling}");
+        }
+    }
+
+    #[test]
+    fn invalid_versions_are_rejected() {
+        let invalid = [
+            "",
+            "1.0.",
+            ".1",
+            "abc",
+            "1.0+",
+            "1.0+a..b",
+            "1.0a1a2",
+            "1.0.post1a1",
+            "1.0-",
+            "a!1.0",
+            "1.0 1",
+        ];
+
+        for s in invalid {
+            assert_eq!(
+                Pep440Version::from_str(s).unwrap_err(),
+                VersionError::Invalid(VersionScheme::Pep440, s.to_string()),
+                "{s}"
+            );
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use super::{VersionError, VersionScheme};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// [Semantic version](https://semver.org/spec/v2.0.0.html), as used by npm and Cargo.
+///
+/// Build metadata is kept for display but ignored by comparisons, so that `1.0.0+a` and
+/// `1.0.0+b` are equal.
+#[derive(Debug, Clone)]
+pub struct SemVer {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<Identifier>,
+    build: Option<String>,
+}
+
+/// Pre-release identifier; numeric identifiers have a lower precedence than alphanumeric ones.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Identifier {
+    Numeric(u64),
+    Alphanumeric(String),
+}
+
+impl SemVer {
+    #[must_use]
+    pub const fn major(&self) -> u64 {
+        self.major
+    }
+
+    #[must_use]
+    pub const fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    #[must_use]
+    pub const fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    #[must_use]
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Pre-release identifiers, as written after the first `-`.
+    pub fn pre_release(&self) -> impl Iterator<Item = String> + '_ {
+        self.pre.iter().map(ToString::to_string)
+    }
+
+    /// Parse a version on behalf of `scheme`, which is reported in errors.
+    pub(super) fn parse(s: &str, scheme: VersionScheme) -> Result<Self, VersionError> {
+        let invalid = || VersionError::Invalid(scheme, s.to_string());
+
+        let (rest, build) = match s.split_once('+') {
+            Some((rest, build)) if build.split('.').all(is_identifier) => {
+                (rest, Some(build.to_string()))
+            }
+            Some(_) => return Err(invalid()),
+            None => (s, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+
+        let mut numbers = core.split('.').map(numeric);
+        let (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) = (
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+        ) else {
+            return Err(invalid());
+        };
+
+        let pre = pre
+            .map(|pre| {
+                pre.split('.')
+                    .map(|identifier| match numeric(identifier) {
+                        Some(n) => Some(Identifier::Numeric(n)),
+                        None if is_identifier(identifier)
+                            && !identifier.bytes().all(|b| b.is_ascii_digit()) =>
+                        {
+                            Some(Identifier::Alphanumeric(identifier.to_string()))
+                        }
+                        None => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(invalid)
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+}
+
+/// Numeric identifier, without leading zeros.
+fn numeric(s: &str) -> Option<u64> {
+    let valid =
+        !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) && (s == "0" || !s.starts_with('0'));
+    valid.then(|| s.parse().ok()).flatten()
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+impl FromStr for SemVer {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, VersionScheme::Semver)
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A pre-release precedes the release it leads to.
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SemVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SemVer {}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numeric(n) => n.fmt(f),
+            Self::Alphanumeric(s) => f.write_str(s),
+        }
+    }
+}
+
+impl fmt::Display for SemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            f.write_str("-")?;
+            f.write_str(&self.pre_release().collect::<Vec<_>>().join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{build}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(s: &str) -> SemVer {
+        SemVer::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn precedence_follows_the_specification() {
+        let ascending = [
+            "0.9.9",
+            "1.0.0-0.3.7",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1-x-y-z.--",
+            "1.0.1",
+            "1.2.0",
+            "1.10.0",
+            "2.0.0",
+            "10.0.0",
+        ];
+
+        for pair in ascending.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn build_metadata_is_ignored_by_comparisons() {
+        let cases = [
+            ("1.0.0+20130313144700", "1.0.0"),
+            ("1.0.0-beta+exp.sha.5114f85", "1.0.0-beta"),
+            ("1.0.0+a", "1.0.0+b"),
+        ];
+
+        for (a, b) in cases {
+            assert_eq!(version(a), version(b), "{a} = {b}");
+        }
+        assert_eq!(
+            version("1.0.0-beta.11+exp.sha").to_string(),
+            "1.0.0-beta.11+exp.sha"
+        );
+    }
+
+    #[test]
+    fn invalid_versions_are_rejected() {
+        let invalid = [
+            "",
+            "1",
+            "1.0",
+            "1.0.0.0",
+            "01.0.0",
+            "1.0.0-",
+            "1.0.0-01",
+            "1.0.0-alpha..1",
+            "1.0.0+",
+            "1.0.0+a_b",
+            "v1.0.0",
+            "1.0.x",
+        ];
+
+        for s in invalid {
+            assert_eq!(
+                SemVer::from_str(s).unwrap_err(),
+                VersionError::Invalid(VersionScheme::Semver, s.to_string()),
+                "{s}"
+            );
+        }
+    }
+}
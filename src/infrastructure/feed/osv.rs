@@ -0,0 +1,497 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::Deserialize;
+use url::Url;
+use zip::ZipArchive;
+
+use crate::application::service::vulnerability_ingestion::{
+    FeedRecords, VulnerabilityFeed, VulnerabilityFeedError,
+};
+use crate::domain::component::purl::PackageUrl;
+use crate::domain::vulnerability::cvss::Cvss;
+use crate::domain::vulnerability::entity::{
+    AffectedPackage, AffectedRange, RangeEvent, RangeKind, Reference, Vulnerability,
+};
+use crate::domain::vulnerability::id::{AdvisoryId, VulnerabilityId};
+
+const RECORD_EXTENSION: &str = "json";
+const CVSS_V3_0_PREFIX: &str = "CVSS:3.0/";
+const CVSS_V3_1_PREFIX: &str = "CVSS:3.1/";
+
+/// Local export of an [OSV](https://ossf.github.io/osv-schema/) database: a directory tree
+/// of JSON records, or a zip archive of them such as the `all.zip` files published per
+/// ecosystem.
+///
+/// CVSS v3.0 vectors are scored as v3.1 ones, which share their base metrics. Other severities
+/// than CVSS v3 and v4.0 vectors, invalid purls and invalid reference URLs are skipped;
+/// records without any recognized identifier or with malformed ranges are rejected.
+pub struct OsvFeed {
+    path: PathBuf,
+}
+
+impl OsvFeed {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn read_directory(&self, records: &mut FeedRecords) -> Result<(), VulnerabilityFeedError> {
+        let mut files = Vec::new();
+        collect_records(&self.path, &mut files)?;
+        files.sort();
+
+        for file in files {
+            let source = display(&file);
+            match fs::read(&file) {
+                Ok(content) => add_record(records, &source, &content),
+                Err(e) => records
+                    .rejected
+                    .push(VulnerabilityFeedError::Unavailable(source, e.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_archive(&self, records: &mut FeedRecords) -> Result<(), VulnerabilityFeedError> {
+        let unavailable = |e: &dyn std::fmt::Display| {
+            VulnerabilityFeedError::Unavailable(display(&self.path), e.to_string())
+        };
+        let file = fs::File::open(&self.path).map_err(|e| unavailable(&e))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| unavailable(&e))?;
+
+        let mut names: Vec<String> = archive
+            .file_names()
+            .filter(|name| is_record(Path::new(name)))
+            .map(str::to_string)
+            .collect();
+        names.sort();
+
+        for name in names {
+            let source = format!("{}!{name}", display(&self.path));
+            let mut content = Vec::new();
+            let read = archive
+                .by_name(&name)
+                .map_err(|e| e.to_string())
+                .and_then(|mut entry| entry.read_to_end(&mut content).map_err(|e| e.to_string()));
+            match read {
+                Ok(_) => add_record(records, &source, &content),
+                Err(e) => records
+                    .rejected
+                    .push(VulnerabilityFeedError::Unavailable(source, e)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl VulnerabilityFeed for OsvFeed {
+    fn read(&self) -> Result<FeedRecords, VulnerabilityFeedError> {
+        let mut records = FeedRecords::default();
+        if self.path.is_dir() {
+            self.read_directory(&mut records)?;
+        } else {
+            self.read_archive(&mut records)?;
+        }
+        Ok(records)
+    }
+}
+
+fn collect_records(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), VulnerabilityFeedError> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| VulnerabilityFeedError::Unavailable(display(dir), e.to_string()))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| VulnerabilityFeedError::Unavailable(display(dir), e.to_string()))?
+            .path();
+        if path.is_dir() {
+            collect_records(&path, files)?;
+        } else if is_record(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_record(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(RECORD_EXTENSION))
+}
+
+fn add_record(records: &mut FeedRecords, source: &str, content: &[u8]) {
+    let parsed = serde_json::from_slice::<OsvRecord>(content)
+        .map_err(|e| VulnerabilityFeedError::Malformed(source.to_string(), e.to_string()))
+        .and_then(|record| record.into_vulnerability(source));
+    match parsed {
+        Ok(vulnerability) => records.vulnerabilities.push(vulnerability),
+        Err(e) => records.rejected.push(e),
+    }
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[derive(Deserialize)]
+struct OsvRecord {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    summary: Option<String>,
+    published: Option<String>,
+    modified: Option<String>,
+    withdrawn: Option<String>,
+    #[serde(default)]
+    severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+    #[serde(default)]
+    references: Vec<OsvReference>,
+}
+
+impl OsvRecord {
+    fn into_vulnerability(self, source: &str) -> Result<Vulnerability, VulnerabilityFeedError> {
+        let candidates: Vec<String> = std::iter::once(self.id).chain(self.aliases).collect();
+        let mut ids = candidates
+            .iter()
+            .filter_map(|candidate| AdvisoryId::from_str(candidate).ok());
+        let Some(first) = ids.next() else {
+            return Err(VulnerabilityFeedError::Unidentified(
+                source.to_string(),
+                candidates,
+            ));
+        };
+        let id = VulnerabilityId::from(first).with_aliases(ids);
+
+        let cvss = self
+            .severity
+            .iter()
+            .chain(self.affected.iter().flat_map(|a| &a.severity))
+            .filter_map(OsvSeverity::cvss)
+            .collect::<Vec<_>>();
+        let affected = self
+            .affected
+            .into_iter()
+            .filter_map(|affected| affected.into_package(source).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+        let references = self.references.into_iter().filter_map(|reference| {
+            let url = Url::parse(&reference.url).ok()?;
+            Some(Reference::new(reference.kind, url))
+        });
+
+        let mut vulnerability = Vulnerability::new(id)
+            .with_dates(self.published, self.modified, self.withdrawn)
+            .with_cvss(cvss)
+            .with_affected(affected)
+            .with_references(references);
+        if let Some(summary) = self.summary {
+            vulnerability = vulnerability.with_summary(summary);
+        }
+        Ok(vulnerability)
+    }
+}
+
+#[derive(Deserialize)]
+struct OsvSeverity {
+    #[serde(rename = "type")]
+    kind: String,
+    score: String,
+}
+
+impl OsvSeverity {
+    /// CVSS vector of the severity, if it is one in a supported version.
+    fn cvss(&self) -> Option<Cvss> {
+        if !matches!(self.kind.as_str(), "CVSS_V3" | "CVSS_V4") {
+            return None;
+        }
+        let vector = match self.score.strip_prefix(CVSS_V3_0_PREFIX) {
+            Some(metrics) => format!("{CVSS_V3_1_PREFIX}{metrics}"),
+            None => self.score.clone(),
+        };
+        Cvss::from_str(&vector)
+            .inspect_err(|e| tracing::warn!("Ignoring OSV severity {}: {e}", self.score))
+            .ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct OsvAffected {
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+impl OsvAffected {
+    /// The affected package, or `None` for entries naming no package (e.g. git-only ranges).
+    fn into_package(self, source: &str) -> Result<Option<AffectedPackage>, VulnerabilityFeedError> {
+        let Some(package) = self.package else {
+            return Ok(None);
+        };
+        let ranges = self
+            .ranges
+            .into_iter()
+            .map(|range| range.into_range(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut affected = AffectedPackage::new(package.ecosystem, package.name)
+            .with_ranges(ranges)
+            .with_versions(self.versions);
+        if let Some(purl) = package
+            .purl
+            .and_then(|purl| PackageUrl::from_str(&purl).ok())
+        {
+            affected = affected.with_purl(purl);
+        }
+        Ok(Some(affected))
+    }
+}
+
+#[derive(Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+    purl: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    repo: Option<String>,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+impl OsvRange {
+    fn into_range(self, source: &str) -> Result<AffectedRange, VulnerabilityFeedError> {
+        let malformed =
+            |reason: String| VulnerabilityFeedError::Malformed(source.to_string(), reason);
+
+        let kind = match self.kind.as_str() {
+            "SEMVER" => RangeKind::Semver,
+            "ECOSYSTEM" => RangeKind::Ecosystem,
+            "GIT" => RangeKind::Git,
+            other => return Err(malformed(format!("unknown range type `{other}`"))),
+        };
+        let events = self
+            .events
+            .into_iter()
+            .map(|event| {
+                event
+                    .into_event()
+                    .ok_or_else(|| malformed("range event without version".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let range = AffectedRange::new(kind, events);
+        Ok(match self.repo {
+            Some(repo) => range.with_repository(repo),
+            None => range,
+        })
+    }
+}
+
+/// One of the four range event kinds, each holding a single version.
+#[derive(Deserialize)]
+struct OsvEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+    limit: Option<String>,
+}
+
+impl OsvEvent {
+    fn into_event(self) -> Option<RangeEvent> {
+        self.introduced
+            .map(RangeEvent::Introduced)
+            .or_else(|| self.fixed.map(RangeEvent::Fixed))
+            .or_else(|| self.last_affected.map(RangeEvent::LastAffected))
+            .or_else(|| self.limit.map(RangeEvent::Limit))
+    }
+}
+
+#[derive(Deserialize)]
+struct OsvReference {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+    use tempfile::TempDir;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    const CVSS_V3: &str = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H";
+
+    fn ghsa_record() -> serde_json::Value {
+        json!({
+            "schema_version": "1.6.0",
+            "id": "GHSA-xvch-5gv4-984h",
+            "aliases": ["CVE-2021-44906", "MAL-2021-1"],
+            "summary": "Prototype pollution in minimist",
+            "published": "2022-03-18T00:01:09Z",
+            "modified": "2024-02-01T10:00:00Z",
+            "severity": [
+                { "type": "CVSS_V3", "score": CVSS_V3 },
+                { "type": "CVSS_V2", "score": "AV:N/AC:L/Au:N/C:P/I:P/A:P" }
+            ],
+            "affected": [{
+                "package": { "ecosystem": "npm", "name": "minimist", "purl": "pkg:npm/minimist" },
+                "ranges": [
+                    { "type": "SEMVER", "events": [{ "introduced": "0" }, { "fixed": "0.2.4" }] },
+                    { "type": "SEMVER", "events": [{ "introduced": "1.0.0" }, { "last_affected": "1.2.5" }] }
+                ],
+                "versions": ["1.2.5"]
+            }],
+            "references": [
+                { "type": "ADVISORY", "url": "https://nvd.nist.gov/vuln/detail/CVE-2021-44906" },
+                { "type": "WEB", "url": "not a url" }
+            ]
+        })
+    }
+
+    fn feed_dir(files: &[(&str, String)]) -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, content) in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn records_should_bring_identifiers_severities_ranges_and_references() {
+        let dir = feed_dir(&[("npm/GHSA.json", ghsa_record().to_string())]);
+        let records = OsvFeed::new(dir.path()).read().unwrap();
+        assert!(records.rejected.is_empty(), "{:?}", records.rejected);
+
+        let [vulnerability] = &records.vulnerabilities[..] else {
+            panic!(
+                "Expected one vulnerability, got {:?}",
+                records.vulnerabilities
+            );
+        };
+        assert_eq!(vulnerability.id().as_str(), "CVE-2021-44906");
+        assert!(
+            vulnerability
+                .id()
+                .is_known_as(&"GHSA-xvch-5gv4-984h".parse().unwrap())
+        );
+        assert_eq!(
+            vulnerability.summary(),
+            Some("Prototype pollution in minimist")
+        );
+        assert_eq!(vulnerability.modified(), Some("2024-02-01T10:00:00Z"));
+        assert_eq!(vulnerability.cvss(), &[Cvss::from_str(CVSS_V3).unwrap()]);
+        assert_eq!(vulnerability.references().len(), 1);
+        assert_eq!(vulnerability.references()[0].kind(), "ADVISORY");
+
+        let [minimist] = vulnerability.affected() else {
+            panic!("Expected one affected package");
+        };
+        assert_eq!((minimist.ecosystem(), minimist.name()), ("npm", "minimist"));
+        assert_eq!(minimist.purl().unwrap().to_string(), "pkg:npm/minimist");
+        assert_eq!(minimist.versions(), ["1.2.5"]);
+        assert_eq!(minimist.ranges()[0].kind(), RangeKind::Semver);
+        assert_eq!(
+            minimist.ranges()[1].events(),
+            [
+                RangeEvent::Introduced("1.0.0".to_string()),
+                RangeEvent::LastAffected("1.2.5".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn cvss_v3_0_vectors_should_be_scored_as_v3_1() {
+        let mut record = ghsa_record();
+        record["severity"] = json!([
+            { "type": "CVSS_V3", "score": "CVSS:3.0/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" },
+            { "type": "CVSS_V3", "score": "CVSS:3.0/AV:N" }
+        ]);
+        let dir = feed_dir(&[("GHSA.json", record.to_string())]);
+
+        let records = OsvFeed::new(dir.path()).read().unwrap();
+        let [vulnerability] = &records.vulnerabilities[..] else {
+            panic!(
+                "Expected one vulnerability, got {:?}",
+                records.vulnerabilities
+            );
+        };
+        assert_eq!(vulnerability.cvss(), &[Cvss::from_str(CVSS_V3).unwrap()]);
+    }
+
+    #[test]
+    fn invalid_records_should_be_rejected_without_failing_the_feed() {
+        let mut unknown_range = ghsa_record();
+        unknown_range["affected"][0]["ranges"][0]["type"] = json!("CALVER");
+        let dir = feed_dir(&[
+            ("a.json", ghsa_record().to_string()),
+            ("b.json", "{ not json".to_string()),
+            (
+                "c.json",
+                json!({ "id": "MAL-2024-1", "aliases": ["OSV-1"] }).to_string(),
+            ),
+            ("d.json", unknown_range.to_string()),
+            ("README.md", "not a record".to_string()),
+        ]);
+
+        let records = OsvFeed::new(dir.path()).read().unwrap();
+        assert_eq!(records.vulnerabilities.len(), 1);
+        assert!(
+            matches!(
+                &records.rejected[..],
+                [
+                    VulnerabilityFeedError::Malformed(b, _),
+                    VulnerabilityFeedError::Unidentified(c, ids),
+                    VulnerabilityFeedError::Malformed(d, reason),
+                ] if b.ends_with("b.json")
+                    && c.ends_with("c.json")
+                    && ids == &["MAL-2024-1", "OSV-1"]
+                    && d.ends_with("d.json")
+                    && reason.contains("CALVER")
+            ),
+            "{:?}",
+            records.rejected
+        );
+    }
+
+    #[test]
+    fn zip_exports_should_be_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("all.zip");
+        let mut zip = ZipWriter::new(fs::File::create(&path).unwrap());
+        zip.start_file("GHSA-xvch-5gv4-984h.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(ghsa_record().to_string().as_bytes()).unwrap();
+        zip.start_file("PYSEC-2024-12.json", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(json!({ "id": "PYSEC-2024-12" }).to_string().as_bytes())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let records = OsvFeed::new(&path).read().unwrap();
+        let ids: Vec<_> = records
+            .vulnerabilities
+            .iter()
+            .map(|v| v.id().to_string())
+            .collect();
+        assert_eq!(ids, vec!["CVE-2021-44906", "PYSEC-2024-12"]);
+
+        let missing = OsvFeed::new(dir.path().join("missing.zip"));
+        assert!(matches!(
+            missing.read().unwrap_err(),
+            VulnerabilityFeedError::Unavailable(..)
+        ));
+    }
+}
@@ -0,0 +1,535 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::{CONTENT_LENGTH, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use url::Url;
+
+use crate::application::service::sbom_fetcher::{FetchedSbom, SbomFetcher, SbomFetcherError};
+use crate::domain::component::id::{Digest, DigestAlgorithm};
+use crate::domain::component::sbom::{Sbom, SbomLocation};
+use crate::infrastructure::blocking;
+use crate::infrastructure::parser::detect_spec;
+use crate::infrastructure::store::filesystem::write_atomically;
+
+const LATEST_FILE: &str = "latest.json";
+const TMP_DIR: &str = "tmp";
+
+/// Fetches `http(s)://` and `file://` SBOMs through a cache on the local filesystem.
+///
+/// Each URL gets a directory named after its SHA-256 digest, holding the documents fetched
+/// from it as `<algorithm>-<hex>` files and a `latest.json` file recording the digest of the
+/// last one along with the `ETag` and `Last-Modified` validators the server sent for it.
+/// Files are written to `tmp/` first.
+///
+/// SBOMs carrying a digest are served from the cache without any request once fetched. Other
+/// HTTP SBOMs are revalidated with a conditional request, and only downloaded again when the
+/// server reports a change.
+pub struct CachingSbomFetcher {
+    cache: Cache,
+    client: Client,
+    timeout: Duration,
+    max_size: u64,
+}
+
+/// Cache directory, accessed with blocking I/O.
+#[derive(Debug, Clone)]
+struct Cache {
+    root: PathBuf,
+}
+
+/// Contents of `latest.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Latest {
+    url: String,
+    digest: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// Document retrieved from a location, or confirmation that the cached one is still current.
+enum Retrieved {
+    NotModified,
+    Document {
+        content: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+impl Cache {
+    fn url_dir(&self, url: &Url) -> PathBuf {
+        let key = Digest::compute(DigestAlgorithm::Sha256, url.as_str().as_bytes());
+        self.root.join(key.hex())
+    }
+
+    fn blob_path(&self, url: &Url, digest: &Digest) -> PathBuf {
+        self.url_dir(url)
+            .join(format!("{}-{}", digest.algorithm(), digest.hex()))
+    }
+
+    /// Cached document of the URL with the given digest, if any.
+    fn cached(&self, url: &Url, digest: &Digest) -> Result<Option<Vec<u8>>, SbomFetcherError> {
+        let path = self.blob_path(url, digest);
+        match fs::read(&path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(cache_unavailable(&path, &e)),
+        }
+    }
+
+    /// Document last fetched from the URL, if still in the cache.
+    fn latest(&self, url: &Url) -> Option<(Latest, Digest, Vec<u8>)> {
+        let entry = fs::read(self.url_dir(url).join(LATEST_FILE)).ok()?;
+        let latest: Latest = serde_json::from_slice(&entry).ok()?;
+        let digest = Digest::from_str(&latest.digest).ok()?;
+        let content = self.cached(url, &digest).ok()??;
+        Some((latest, digest, content))
+    }
+
+    fn write_atomically(&self, path: &Path, content: &[u8]) -> Result<(), SbomFetcherError> {
+        write_atomically(&self.root.join(TMP_DIR), path, content)
+            .map_err(|e| cache_unavailable(path, &e))
+    }
+
+    fn store(
+        &self,
+        url: &Url,
+        content: &[u8],
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Result<Digest, SbomFetcherError> {
+        let digest = Digest::compute(DigestAlgorithm::Sha256, content);
+        let blob = self.blob_path(url, &digest);
+        if !blob.exists() {
+            self.write_atomically(&blob, content)?;
+        }
+
+        let latest = Latest {
+            url: url.to_string(),
+            digest: digest.to_string(),
+            etag,
+            last_modified,
+        };
+        let latest = serde_json::to_vec_pretty(&latest)
+            .map_err(|e| SbomFetcherError::CacheUnavailable(display(&blob), e.to_string()))?;
+        self.write_atomically(&self.url_dir(url).join(LATEST_FILE), &latest)?;
+        Ok(digest)
+    }
+}
+
+impl CachingSbomFetcher {
+    /// Open the cache rooted at the given directory, creating it if needed.
+    ///
+    /// Requests are abandoned after `timeout`, and documents larger than `max_size` bytes are
+    /// refused.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomFetcherError::CacheUnavailable`] if the directory cannot be created, or
+    /// [`SbomFetcherError::Unreachable`] if the HTTP client cannot be initialized.
+    pub fn new(
+        root: impl Into<PathBuf>,
+        timeout: Duration,
+        max_size: u64,
+    ) -> Result<Self, SbomFetcherError> {
+        let root = root.into();
+        let tmp = root.join(TMP_DIR);
+        fs::create_dir_all(&tmp).map_err(|e| cache_unavailable(&tmp, &e))?;
+
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| SbomFetcherError::Unreachable(display(&root), e.to_string()))?;
+        Ok(Self {
+            cache: Cache { root },
+            client,
+            timeout,
+            max_size,
+        })
+    }
+
+    /// Access the cache through [`blocking`].
+    async fn with_cache<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&Cache) -> Result<T, SbomFetcherError> + Send + 'static,
+    ) -> Result<T, SbomFetcherError> {
+        let cache = self.cache.clone();
+        blocking(move || f(&cache))
+            .await
+            .map_err(|e| SbomFetcherError::CacheUnavailable(display(&self.cache.root), e))?
+    }
+
+    async fn retrieve(
+        &self,
+        url: &Url,
+        latest: Option<&Latest>,
+    ) -> Result<Retrieved, SbomFetcherError> {
+        match url.scheme() {
+            "http" | "https" => self.retrieve_http(url, latest).await,
+            "file" => {
+                let path = url
+                    .to_file_path()
+                    .map_err(|()| SbomFetcherError::UnsupportedLocation(url.to_string()))?;
+                let file = tokio::fs::File::open(&path)
+                    .await
+                    .map_err(|e| SbomFetcherError::Unreachable(url.to_string(), e.to_string()))?;
+                let content = self.read_limited(url, file).await?;
+                Ok(Retrieved::Document {
+                    content,
+                    etag: None,
+                    last_modified: None,
+                })
+            }
+            _ => Err(SbomFetcherError::UnsupportedLocation(url.to_string())),
+        }
+    }
+
+    async fn retrieve_http(
+        &self,
+        url: &Url,
+        latest: Option<&Latest>,
+    ) -> Result<Retrieved, SbomFetcherError> {
+        let mut request = self.client.get(url.clone());
+        if let Some(latest) = latest {
+            if let Some(etag) = &latest.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &latest.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| self.request_failed(url, &e))?;
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED && latest.is_some() {
+            return Ok(Retrieved::NotModified);
+        }
+        if !status.is_success() {
+            return Err(SbomFetcherError::Unreachable(
+                url.to_string(),
+                format!("server responded with {status}"),
+            ));
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        if let Some(length) = header(CONTENT_LENGTH).and_then(|l| l.parse::<u64>().ok())
+            && length > self.max_size
+        {
+            return Err(SbomFetcherError::TooLarge(url.to_string(), self.max_size));
+        }
+
+        // Read chunk by chunk, refusing the document as soon as it exceeds the size limit.
+        let mut content = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| self.request_failed(url, &e))?
+        {
+            content.extend_from_slice(&chunk);
+            if content.len() as u64 > self.max_size {
+                return Err(SbomFetcherError::TooLarge(url.to_string(), self.max_size));
+            }
+        }
+        Ok(Retrieved::Document {
+            content,
+            etag,
+            last_modified,
+        })
+    }
+
+    /// Read a whole document, refusing it as soon as it exceeds the size limit.
+    async fn read_limited(
+        &self,
+        url: &Url,
+        reader: impl AsyncRead + Unpin,
+    ) -> Result<Vec<u8>, SbomFetcherError> {
+        let mut content = Vec::new();
+        reader
+            .take(self.max_size.saturating_add(1))
+            .read_to_end(&mut content)
+            .await
+            .map_err(|e| SbomFetcherError::Unreachable(url.to_string(), e.to_string()))?;
+        if content.len() as u64 > self.max_size {
+            return Err(SbomFetcherError::TooLarge(url.to_string(), self.max_size));
+        }
+        Ok(content)
+    }
+
+    fn request_failed(&self, url: &Url, e: &reqwest::Error) -> SbomFetcherError {
+        if e.is_timeout() {
+            SbomFetcherError::TimedOut(url.to_string(), self.timeout)
+        } else {
+            SbomFetcherError::Unreachable(url.to_string(), e.to_string())
+        }
+    }
+}
+
+#[async_trait]
+impl SbomFetcher for CachingSbomFetcher {
+    async fn fetch(&self, sbom: &Sbom) -> Result<FetchedSbom, SbomFetcherError> {
+        let SbomLocation::Remote(url) = sbom.location() else {
+            return Err(SbomFetcherError::UnsupportedLocation(sbom.to_string()));
+        };
+
+        // A pinned document never changes: once cached, it is served without any request.
+        if let Some(digest) = sbom.digest() {
+            let (url, digest) = (url.clone(), digest.clone());
+            if let Some(content) = self
+                .with_cache(move |cache| cache.cached(&url, &digest))
+                .await?
+            {
+                sbom.verify(&content)?;
+                return Ok(fetched(sbom.clone(), content));
+            }
+        }
+
+        let latest = {
+            let url = url.clone();
+            self.with_cache(move |cache| Ok(cache.latest(&url))).await?
+        };
+        let validators = latest.as_ref().map(|(latest, _, _)| latest);
+        let (digest, content) = match (self.retrieve(url, validators).await?, latest) {
+            (Retrieved::NotModified, Some((_, digest, content))) => (digest, content),
+            (Retrieved::NotModified, None) => {
+                return Err(SbomFetcherError::Unreachable(
+                    url.to_string(),
+                    "server reported an unknown document as not modified".to_string(),
+                ));
+            }
+            (
+                Retrieved::Document {
+                    content,
+                    etag,
+                    last_modified,
+                },
+                _,
+            ) => {
+                let url = url.clone();
+                self.with_cache(move |cache| {
+                    let digest = cache.store(&url, &content, etag, last_modified)?;
+                    Ok((digest, content))
+                })
+                .await?
+            }
+        };
+
+        let sbom = match sbom.digest() {
+            Some(_) => {
+                sbom.verify(&content)?;
+                sbom.clone()
+            }
+            None => sbom.clone().with_digest(digest),
+        };
+        Ok(fetched(sbom, content))
+    }
+}
+
+/// Complete the specification of a fetched SBOM from its contents when unknown.
+fn fetched(sbom: Sbom, content: Vec<u8>) -> FetchedSbom {
+    let spec = match sbom.spec() {
+        Some(_) => None,
+        None => std::str::from_utf8(&content).ok().and_then(detect_spec),
+    };
+    let sbom = match spec {
+        Some(spec) => sbom.with_spec(spec),
+        None => sbom,
+    };
+    FetchedSbom { sbom, content }
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn cache_unavailable(path: &Path, e: &io::Error) -> SbomFetcherError {
+    SbomFetcherError::CacheUnavailable(display(path), e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::{self, JoinHandle};
+    use tempfile::TempDir;
+    use tiny_http::{Header, Response, Server};
+
+    const CYCLONEDX: &str = r#"{"bomFormat": "CycloneDX", "specVersion": "1.6"}"#;
+    const ETAG_V1: &str = "\"v1\"";
+    const LAST_MODIFIED_V1: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+    /// `If-None-Match` and `If-Modified-Since` headers of a request.
+    type Validators = (Option<String>, Option<String>);
+
+    /// Fetcher with a cache under a new temporary directory; keep the directory alive.
+    fn fetcher(max_size: u64) -> (TempDir, CachingSbomFetcher) {
+        let root = tempfile::tempdir().unwrap();
+        let fetcher = CachingSbomFetcher::new(
+            root.path().join("cache"),
+            Duration::from_millis(500),
+            max_size,
+        )
+        .unwrap();
+        (root, fetcher)
+    }
+
+    /// Stub server answering `requests` requests, returning `304 Not Modified` whenever the
+    /// client presents the `v1` validators and the document otherwise.
+    fn serve(requests: usize, content: &'static str) -> (Url, JoinHandle<Vec<Validators>>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = thread::spawn(move || {
+            let mut received = Vec::new();
+            for request in server.incoming_requests().take(requests) {
+                let header = |name: &str| {
+                    request
+                        .headers()
+                        .iter()
+                        .find(|h| h.field.to_string().eq_ignore_ascii_case(name))
+                        .map(|h| h.value.to_string())
+                };
+                let validators = (header("If-None-Match"), header("If-Modified-Since"));
+                let response = if validators.0.as_deref() == Some(ETAG_V1) {
+                    Response::from_data(Vec::new()).with_status_code(304)
+                } else {
+                    Response::from_data(content.as_bytes().to_vec())
+                        .with_header(Header::from_bytes("ETag", ETAG_V1).unwrap())
+                        .with_header(Header::from_bytes("Last-Modified", LAST_MODIFIED_V1).unwrap())
+                };
+                received.push(validators);
+                request.respond(response).unwrap();
+            }
+            received
+        });
+        let url = Url::parse(&format!("http://127.0.0.1:{port}/vendor/nginx.cdx.json")).unwrap();
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn http_sboms_should_be_revalidated_with_conditional_requests() {
+        let (_root, fetcher) = fetcher(1024);
+        let (url, server) = serve(2, CYCLONEDX);
+        let remote = Sbom::from(url);
+
+        let first = fetcher.fetch(&remote).await.unwrap();
+        let second = fetcher.fetch(&remote).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.content, CYCLONEDX.as_bytes());
+        assert_eq!(
+            first.sbom.digest(),
+            Some(&Digest::compute(
+                DigestAlgorithm::Sha256,
+                CYCLONEDX.as_bytes()
+            ))
+        );
+        assert!(first.sbom.spec().is_some());
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                (None, None),
+                (
+                    Some(ETAG_V1.to_string()),
+                    Some(LAST_MODIFIED_V1.to_string())
+                )
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pinned_sboms_should_be_served_from_the_cache() {
+        let (_root, fetcher) = fetcher(1024);
+        let (url, server) = serve(1, CYCLONEDX);
+
+        let fetched = fetcher.fetch(&Sbom::from(url)).await.unwrap();
+        server.join().unwrap();
+
+        // The server is gone: only the cache can answer.
+        assert_eq!(fetcher.fetch(&fetched.sbom).await.unwrap(), fetched);
+    }
+
+    #[tokio::test]
+    async fn contents_should_match_the_pinned_digest() {
+        let (_root, fetcher) = fetcher(1024);
+        let (url, server) = serve(1, CYCLONEDX);
+
+        let pinned = Sbom::from(url).sealed(b"{}");
+        let err = fetcher.fetch(&pinned).await.unwrap_err();
+        assert!(matches!(err, SbomFetcherError::Integrity(_)), "{err:?}");
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn oversized_documents_should_be_refused() {
+        let (root, fetcher) = fetcher(8);
+        let (url, server) = serve(1, CYCLONEDX);
+        let err = fetcher.fetch(&Sbom::from(url)).await.unwrap_err();
+        assert!(matches!(err, SbomFetcherError::TooLarge(_, 8)), "{err:?}");
+        server.join().unwrap();
+
+        let path = root.path().join("oversized.cdx.json");
+        fs::write(&path, CYCLONEDX).unwrap();
+        let err = fetcher
+            .fetch(&Sbom::from(Url::from_file_path(&path).unwrap()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SbomFetcherError::TooLarge(_, 8)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn slow_servers_should_time_out() {
+        let (_root, fetcher) = fetcher(1024);
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = thread::spawn(move || {
+            let request = server.recv().unwrap();
+            thread::sleep(Duration::from_secs(1));
+            let _ = request.respond(Response::from_string(CYCLONEDX));
+        });
+
+        let url = Url::parse(&format!("http://127.0.0.1:{port}/slow")).unwrap();
+        let err = fetcher.fetch(&Sbom::from(url)).await.unwrap_err();
+        assert!(matches!(err, SbomFetcherError::TimedOut(..)), "{err:?}");
+        handle.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn file_sboms_should_be_fetched_and_other_schemes_refused() {
+        let (root, fetcher) = fetcher(1024);
+        let path = root.path().join("vendor.cdx.json");
+        fs::write(&path, CYCLONEDX).unwrap();
+
+        let fetched = fetcher
+            .fetch(&Sbom::from(Url::from_file_path(&path).unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(fetched.content, CYCLONEDX.as_bytes());
+        assert!(fetched.sbom.verify(CYCLONEDX.as_bytes()).is_ok());
+
+        let s3 = Sbom::from_url_str("s3://vendor/nginx.cdx.json").unwrap();
+        assert!(matches!(
+            fetcher.fetch(&s3).await.unwrap_err(),
+            SbomFetcherError::UnsupportedLocation(_)
+        ));
+        assert!(matches!(
+            fetcher.fetch(&Sbom::from(path)).await.unwrap_err(),
+            SbomFetcherError::UnsupportedLocation(_)
+        ));
+    }
+}
@@ -1 +1,51 @@
+pub mod offline;
+pub mod process;
 pub mod syft;
+pub mod trivy;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::application::service::sbom_generator::{SbomGenerator, SbomGeneratorError};
+use crate::application::service::sbom_store::SbomStore;
+use crate::config::{SbomGeneratorKind, VenomConfig};
+use crate::domain::component::{id::ComponentId, sbom::Sbom};
+use crate::infrastructure::blocking;
+use crate::infrastructure::generator::{
+    offline::OfflineSbomGenerator, syft::SyftSbomGenerator, trivy::TrivySbomGenerator,
+};
+
+/// Build the generator selected in the configuration.
+///
+/// # Errors
+///
+/// Returns [`SbomGeneratorError::ToolUnavailable`] if the selected tool cannot be run.
+pub fn from_config(
+    config: &VenomConfig,
+    store: Arc<dyn SbomStore>,
+) -> Result<Arc<dyn SbomGenerator>, SbomGeneratorError> {
+    Ok(match config.sbom_generator {
+        SbomGeneratorKind::Syft => Arc::new(SyftSbomGenerator::new(store)?),
+        SbomGeneratorKind::Trivy => Arc::new(TrivySbomGenerator::new(store)?),
+        SbomGeneratorKind::Offline => Arc::new(OfflineSbomGenerator::new(
+            config
+                .offline_sbom_sources
+                .iter()
+                .map(PathBuf::from)
+                .collect(),
+            store,
+        )),
+    })
+}
+
+/// Hand a generated document over to the store.
+async fn store(
+    store: &Arc<dyn SbomStore>,
+    component: &ComponentId,
+    content: Vec<u8>,
+) -> Result<Sbom, SbomGeneratorError> {
+    let (store, target) = (store.clone(), component.clone());
+    Ok(blocking(move || store.put(&target, &content))
+        .await
+        .map_err(|e| SbomGeneratorError::GenerationFailed(component.to_string(), e))??)
+}
@@ -0,0 +1,608 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::info;
+
+use crate::application::service::sbom_generator::{SbomGenerator, SbomGeneratorError};
+use crate::application::service::sbom_store::SbomStore;
+use crate::domain::component::{
+    id::{ComponentId, Digest},
+    sbom::Sbom,
+};
+use crate::infrastructure::{blocking, generator};
+
+/// Extensions tried, in order, for documents of a directory tree.
+const TREE_EXTENSIONS: [&str; 4] = ["cdx.json", "spdx.json", "spdx", "json"];
+
+const OCI_LAYOUT_FILE: &str = "oci-layout";
+const OCI_INDEX_FILE: &str = "index.json";
+const INDEX_MEDIA_TYPES: [&str; 2] = [
+    "application/vnd.oci.image.index.v1+json",
+    "application/vnd.docker.distribution.manifest.list.v2+json",
+];
+const SBOM_MEDIA_TYPES: [&str; 3] = [
+    "application/vnd.cyclonedx+json",
+    "application/spdx+json",
+    "text/spdx",
+];
+
+const IMAGE_NAME_ANNOTATION: &str = "io.containerd.image.name";
+const REF_NAME_ANNOTATION: &str = "org.opencontainers.image.ref.name";
+const REFERENCE_TYPE_ANNOTATION: &str = "vnd.docker.reference.type";
+const REFERENCE_DIGEST_ANNOTATION: &str = "vnd.docker.reference.digest";
+const ATTESTATION_MANIFEST: &str = "attestation-manifest";
+const PREDICATE_TYPE_ANNOTATION: &str = "in-toto.io/predicate-type";
+const SBOM_PREDICATE_TYPES: [&str; 2] = ["https://spdx.dev/Document", "https://cyclonedx.org/bom"];
+
+/// Resolves components to pre-built SBOMs on disk, without any network access.
+///
+/// Sources are searched in order. Each one is either:
+/// - an OCI image layout directory (it holds an `oci-layout` file);
+/// - a tarball produced by `docker save`, which holds an OCI image layout since Docker 25;
+/// - a directory tree of documents named `<registry>/<namespace>/<name>/<version>.<ext>`,
+///   where the version is the tag or `<algorithm>-<hex>` for the digest (preferred), and
+///   the extension one of `cdx.json`, `spdx.json`, `spdx` or `json`.
+///
+/// Within image layouts, SBOMs are found as OCI referrers of the image (artifacts whose
+/// `subject` is the image manifest) or as BuildKit attestations (in-toto statements with an
+/// SPDX or CycloneDX predicate). Found documents are handed to the [`SbomStore`].
+pub struct OfflineSbomGenerator {
+    sources: Vec<PathBuf>,
+    store: Arc<dyn SbomStore>,
+}
+
+impl OfflineSbomGenerator {
+    #[must_use]
+    pub fn new(sources: Vec<PathBuf>, store: Arc<dyn SbomStore>) -> Self {
+        Self { sources, store }
+    }
+}
+
+#[async_trait]
+impl SbomGenerator for OfflineSbomGenerator {
+    async fn generate(&self, component: &ComponentId) -> Result<Sbom, SbomGeneratorError> {
+        let failed =
+            |reason: String| SbomGeneratorError::GenerationFailed(component.to_string(), reason);
+
+        let (sources, target) = (self.sources.clone(), component.clone());
+        let content = blocking(move || find(&sources, &target))
+            .await
+            .map_err(failed)?
+            .map_err(failed)?
+            .ok_or_else(|| {
+                let sources = self
+                    .sources
+                    .iter()
+                    .map(|s| s.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                SbomGeneratorError::NotFound(
+                    component.to_string(),
+                    format!("no pre-built SBOM found in [{sources}]"),
+                )
+            })?;
+
+        generator::store(&self.store, component, content).await
+    }
+}
+
+fn find(sources: &[PathBuf], component: &ComponentId) -> Result<Option<Vec<u8>>, String> {
+    for source in sources {
+        let found = if source.is_dir() && !source.join(OCI_LAYOUT_FILE).is_file() {
+            find_in_tree(source, component)
+        } else if source.is_dir() {
+            ImageLayout::Directory(source.clone()).find(component)
+        } else if source.is_file() {
+            ImageLayout::open_archive(source)?.find(component)
+        } else {
+            continue;
+        }
+        .map_err(|e| format!("{}: {e}", source.display()))?;
+
+        if found.is_some() {
+            info!("Found sbom for {component} in {}", source.display());
+            return Ok(found);
+        }
+    }
+    Ok(None)
+}
+
+fn find_in_tree(root: &Path, component: &ComponentId) -> Result<Option<Vec<u8>>, String> {
+    let mut dir = root.join(component.registry());
+    if let Some(namespace) = component.namespace() {
+        dir = dir.join(namespace);
+    }
+    let dir = dir.join(component.name());
+
+    let digest = component
+        .digest()
+        .map(|d| format!("{}-{}", d.algorithm(), d.hex()));
+    let versions = digest.as_deref().into_iter().chain(component.tag());
+    for version in versions {
+        for extension in TREE_EXTENSIONS {
+            let path = dir.join(format!("{version}.{extension}"));
+            if path.is_file() {
+                return fs::read(&path)
+                    .map(Some)
+                    .map_err(|e| format!("cannot read {}: {e}", path.display()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// OCI image layout, either unpacked in a directory or held in a tarball.
+enum ImageLayout {
+    Directory(PathBuf),
+    /// Regular files of the tarball by normalized path, as offset and size in the tarball.
+    /// Image layers can be large: files are only read when needed.
+    Archive(PathBuf, HashMap<String, (u64, u64)>),
+}
+
+impl ImageLayout {
+    fn open_archive(path: &Path) -> Result<Self, String> {
+        let unreadable = |e: io::Error| format!("cannot read {}: {e}", path.display());
+
+        let file = fs::File::open(path).map_err(unreadable)?;
+        let mut archive = tar::Archive::new(file);
+        let mut files = HashMap::new();
+        for entry in archive.entries().map_err(unreadable)? {
+            let entry = entry.map_err(unreadable)?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry
+                .path()
+                .map_err(unreadable)?
+                .to_string_lossy()
+                .to_string();
+            files.insert(
+                name.trim_start_matches("./").to_string(),
+                (entry.raw_file_position(), entry.size()),
+            );
+        }
+        Ok(Self::Archive(path.to_path_buf(), files))
+    }
+
+    fn file(&self, name: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Directory(root) => fs::read(root.join(name)).ok(),
+            Self::Archive(path, files) => {
+                let &(offset, size) = files.get(name)?;
+                let mut file = fs::File::open(path).ok()?;
+                file.seek(SeekFrom::Start(offset)).ok()?;
+                let mut content = Vec::new();
+                file.take(size).read_to_end(&mut content).ok()?;
+                Some(content)
+            }
+        }
+    }
+
+    /// Read a blob, checking it against the digest it is addressed by.
+    fn blob(&self, digest: &str) -> Result<Vec<u8>, String> {
+        let expected = Digest::from_str(digest).map_err(|e| e.to_string())?;
+        let content = self
+            .file(&format!(
+                "blobs/{}/{}",
+                expected.algorithm(),
+                expected.hex()
+            ))
+            .ok_or_else(|| format!("blob {digest} is missing"))?;
+        if Digest::compute(expected.algorithm(), &content) != expected {
+            return Err(format!("blob {digest} does not match its digest"));
+        }
+        Ok(content)
+    }
+
+    fn json<T: for<'de> Deserialize<'de>>(content: &[u8], what: &str) -> Result<T, String> {
+        serde_json::from_slice(content).map_err(|e| format!("malformed {what}: {e}"))
+    }
+
+    fn find(&self, component: &ComponentId) -> Result<Option<Vec<u8>>, String> {
+        if self.file(OCI_LAYOUT_FILE).is_none() {
+            return Ok(None);
+        }
+        let index: ImageIndex = Self::json(
+            &self
+                .file(OCI_INDEX_FILE)
+                .ok_or_else(|| format!("{OCI_INDEX_FILE} is missing"))?,
+            OCI_INDEX_FILE,
+        )?;
+        let images: HashSet<&str> = index
+            .manifests
+            .iter()
+            .filter(|d| d.is_named())
+            .map(|d| d.digest.as_str())
+            .collect();
+        let single_image = images.len() == 1;
+        let Some(image) = index
+            .manifests
+            .iter()
+            .find(|d| d.names(component, single_image))
+        else {
+            return Ok(None);
+        };
+
+        // SBOMs may be attached to the image index or to any of its platform manifests,
+        // and listed next to the image in the layout index or inside the image index.
+        let mut subjects = HashSet::from([image.digest.clone()]);
+        let mut candidates = index.manifests.clone();
+        if image.is_index() {
+            let nested: ImageIndex = Self::json(&self.blob(&image.digest)?, &image.digest)?;
+            subjects.extend(nested.manifests.iter().map(|d| d.digest.clone()));
+            candidates.extend(nested.manifests);
+        }
+
+        for descriptor in candidates.iter().filter(|d| !d.is_index()) {
+            let attestation = descriptor.attests(&subjects);
+            if !attestation && descriptor.digest == image.digest {
+                continue;
+            }
+            let manifest: Manifest =
+                Self::json(&self.blob(&descriptor.digest)?, &descriptor.digest)?;
+            let referrer = manifest
+                .subject
+                .as_ref()
+                .is_some_and(|s| subjects.contains(&s.digest));
+
+            for layer in &manifest.layers {
+                if referrer && layer.is_sbom() {
+                    return self.blob(&layer.digest).map(Some);
+                }
+                if attestation && layer.is_sbom_statement() {
+                    let statement: Statement =
+                        Self::json(&self.blob(&layer.digest)?, &layer.digest)?;
+                    return serde_json::to_vec(&statement.predicate)
+                        .map(Some)
+                        .map_err(|e| e.to_string());
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Deserialize)]
+struct ImageIndex {
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    subject: Option<Descriptor>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: Option<String>,
+    digest: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+impl Descriptor {
+    fn annotation(&self, key: &str) -> Option<&str> {
+        self.annotations.get(key).map(String::as_str)
+    }
+
+    fn media_type(&self) -> &str {
+        self.media_type.as_deref().unwrap_or_default()
+    }
+
+    fn is_index(&self) -> bool {
+        INDEX_MEDIA_TYPES.contains(&self.media_type())
+    }
+
+    fn is_sbom(&self) -> bool {
+        SBOM_MEDIA_TYPES.contains(&self.media_type())
+    }
+
+    fn is_sbom_statement(&self) -> bool {
+        self.annotation(PREDICATE_TYPE_ANNOTATION)
+            .is_some_and(|t| SBOM_PREDICATE_TYPES.iter().any(|p| t.starts_with(p)))
+    }
+
+    /// Whether this is a BuildKit attestation manifest for one of the given images.
+    fn attests(&self, subjects: &HashSet<String>) -> bool {
+        self.annotation(REFERENCE_TYPE_ANNOTATION) == Some(ATTESTATION_MANIFEST)
+            && self
+                .annotation(REFERENCE_DIGEST_ANNOTATION)
+                .is_some_and(|d| subjects.contains(d))
+    }
+
+    /// Whether this is the image of the given component.
+    ///
+    /// A pinned component only matches the manifest of its digest. `docker save` names images
+    /// fully and keeps only the tag as reference name, whereas other tools may use either a
+    /// tag or a full reference as reference name. A bare tag does not tell repositories
+    /// apart, so it is only trusted when the layout holds a `single_image`.
+    fn names(&self, component: &ComponentId, single_image: bool) -> bool {
+        let same_component =
+            |reference: &str| ComponentId::from_str(reference).is_ok_and(|id| &id == component);
+
+        if let Some(digest) = component.digest() {
+            return digest.to_string() == self.digest;
+        }
+        if let Some(name) = self.annotation(IMAGE_NAME_ANNOTATION) {
+            return same_component(name);
+        }
+        self.annotation(REF_NAME_ANNOTATION).is_some_and(|name| {
+            same_component(name) || (single_image && component.tag() == Some(name))
+        })
+    }
+
+    fn is_named(&self) -> bool {
+        self.annotation(IMAGE_NAME_ANNOTATION).is_some()
+            || self.annotation(REF_NAME_ANNOTATION).is_some()
+    }
+}
+
+#[derive(Deserialize)]
+struct Statement {
+    predicate: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::id::DigestAlgorithm;
+    use crate::domain::component::sbom::SbomFormat;
+    use crate::infrastructure::store::filesystem::FileSystemSbomStore;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const CYCLONEDX: &str = r#"{"bomFormat": "CycloneDX", "specVersion": "1.6"}"#;
+    const SPDX: &str = "SPDXVersion: SPDX-2.3\nPackageName: zlib\nSPDXID: SPDXRef-zlib\n";
+
+    /// Empty directory `name` within the temporary directory of a test.
+    fn subdir(tmp: &TempDir, name: &str) -> PathBuf {
+        let dir = tmp.path().join(name);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn generator(tmp: &TempDir, sources: Vec<PathBuf>) -> OfflineSbomGenerator {
+        let store = FileSystemSbomStore::new(subdir(tmp, "store")).unwrap();
+        OfflineSbomGenerator::new(sources, Arc::new(store))
+    }
+
+    fn component(reference: &str) -> ComponentId {
+        ComponentId::from_str(reference).unwrap()
+    }
+
+    fn write(path: &Path, content: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Write a blob into an OCI layout, returning its descriptor.
+    fn blob(root: &Path, media_type: &str, content: &[u8]) -> serde_json::Value {
+        let digest = Digest::compute(DigestAlgorithm::Sha256, content);
+        write(&root.join("blobs/sha256").join(digest.hex()), content);
+        json!({ "mediaType": media_type, "digest": digest.to_string(), "size": content.len() })
+    }
+
+    fn json_blob(root: &Path, media_type: &str, value: &serde_json::Value) -> serde_json::Value {
+        blob(root, media_type, value.to_string().as_bytes())
+    }
+
+    fn annotated(
+        mut descriptor: serde_json::Value,
+        annotations: serde_json::Value,
+    ) -> serde_json::Value {
+        descriptor["annotations"] = annotations;
+        descriptor
+    }
+
+    fn image_manifest(root: &Path) -> serde_json::Value {
+        let config = blob(root, "application/vnd.oci.image.config.v1+json", b"{}");
+        json_blob(
+            root,
+            "application/vnd.oci.image.manifest.v1+json",
+            &json!({ "schemaVersion": 2, "config": config, "layers": [] }),
+        )
+    }
+
+    fn write_layout(root: &Path, manifests: &[serde_json::Value]) {
+        write(
+            &root.join(OCI_LAYOUT_FILE),
+            br#"{"imageLayoutVersion": "1.0.0"}"#,
+        );
+        let index = json!({ "schemaVersion": 2, "manifests": manifests });
+        write(&root.join(OCI_INDEX_FILE), index.to_string().as_bytes());
+    }
+
+    /// OCI layout where the SBOM is a referrer of the image manifest.
+    fn referrer_layout(root: &Path) {
+        let image = image_manifest(root);
+        let sbom = blob(root, "application/spdx+json", SPDX.as_bytes());
+        let config = blob(root, "application/vnd.oci.empty.v1+json", b"{}");
+        let referrer = json_blob(
+            root,
+            "application/vnd.oci.image.manifest.v1+json",
+            &json!({ "schemaVersion": 2, "config": config, "layers": [sbom], "subject": image }),
+        );
+        write_layout(
+            root,
+            &[
+                annotated(image, json!({ REF_NAME_ANNOTATION: "1.27" })),
+                referrer,
+            ],
+        );
+    }
+
+    /// `docker save` layout of a multi-platform image with a BuildKit SBOM attestation.
+    fn attestation_layout(root: &Path) {
+        let platform = image_manifest(root);
+        let statement = json!({
+            "_type": "https://in-toto.io/Statement/v0.1",
+            "predicateType": "https://cyclonedx.org/bom",
+            "predicate": serde_json::from_str::<serde_json::Value>(CYCLONEDX).unwrap(),
+        });
+        let statement = annotated(
+            json_blob(root, "application/vnd.in-toto+json", &statement),
+            json!({ PREDICATE_TYPE_ANNOTATION: "https://cyclonedx.org/bom" }),
+        );
+        let config = blob(root, "application/vnd.oci.image.config.v1+json", b"{}");
+        let attestation = json_blob(
+            root,
+            "application/vnd.oci.image.manifest.v1+json",
+            &json!({ "schemaVersion": 2, "config": config, "layers": [statement] }),
+        );
+        let attestation = annotated(
+            attestation,
+            json!({
+                REFERENCE_TYPE_ANNOTATION: ATTESTATION_MANIFEST,
+                REFERENCE_DIGEST_ANNOTATION: platform["digest"],
+            }),
+        );
+        let image_index = json_blob(
+            root,
+            INDEX_MEDIA_TYPES[0],
+            &json!({ "schemaVersion": 2, "manifests": [platform, attestation] }),
+        );
+        write_layout(
+            root,
+            &[annotated(
+                image_index,
+                json!({
+                    IMAGE_NAME_ANNOTATION: "docker.io/library/redis:7.2",
+                    REF_NAME_ANNOTATION: "7.2",
+                }),
+            )],
+        );
+    }
+
+    fn tarball(layout: &Path, path: &Path) {
+        let mut builder = tar::Builder::new(fs::File::create(path).unwrap());
+        builder.append_dir_all(".", layout).unwrap();
+        builder.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn directory_tree_should_prefer_digest_then_tag() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = subdir(&tmp, "tree");
+        let hex = "a".repeat(64);
+        write(
+            &root.join("docker.io/library/nginx/1.27.spdx"),
+            SPDX.as_bytes(),
+        );
+        write(
+            &root.join(format!("docker.io/library/nginx/sha256-{hex}.cdx.json")),
+            CYCLONEDX.as_bytes(),
+        );
+        let generator = generator(&tmp, vec![root]);
+
+        let sbom = generator.generate(&component("nginx:1.27")).await.unwrap();
+        assert_eq!(sbom.spec().unwrap().format(), SbomFormat::SpdxTagValue);
+
+        let pinned = component(&format!("nginx:1.27@sha256:{hex}"));
+        let sbom = generator.generate(&pinned).await.unwrap();
+        assert_eq!(sbom.spec().unwrap().format(), SbomFormat::CycloneDxJson);
+    }
+
+    #[tokio::test]
+    async fn oci_layout_should_resolve_referrers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = subdir(&tmp, "oci");
+        referrer_layout(&root);
+        let generator = generator(&tmp, vec![root]);
+
+        let sbom = generator.generate(&component("nginx:1.27")).await.unwrap();
+        assert_eq!(
+            sbom.digest().unwrap(),
+            &Digest::compute(DigestAlgorithm::Sha256, SPDX.as_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn oci_layout_should_not_confuse_images() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = subdir(&tmp, "oci");
+        referrer_layout(&root);
+        let generator = generator(&tmp, vec![root.clone()]);
+
+        // A pinned component only matches its own manifest, whatever its tag.
+        let other = Digest::compute(DigestAlgorithm::Sha256, b"other image");
+        let pinned = component(&format!("nginx:1.27@{other}"));
+        assert!(generator.generate(&pinned).await.is_err());
+
+        // Once the layout holds another image, a bare tag no longer identifies the image.
+        let index: serde_json::Value =
+            serde_json::from_slice(&fs::read(root.join(OCI_INDEX_FILE)).unwrap()).unwrap();
+        let mut manifests = index["manifests"].as_array().unwrap().clone();
+        let config = blob(
+            &root,
+            "application/vnd.oci.image.config.v1+json",
+            b"{\"os\": \"linux\"}",
+        );
+        let redis = json_blob(
+            &root,
+            "application/vnd.oci.image.manifest.v1+json",
+            &json!({ "schemaVersion": 2, "config": config, "layers": [] }),
+        );
+        manifests.push(annotated(redis, json!({ REF_NAME_ANNOTATION: "1.27" })));
+        write_layout(&root, &manifests);
+
+        let err = generator
+            .generate(&component("nginx:1.27"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, SbomGeneratorError::NotFound(_, reason) if reason.starts_with("no pre-built SBOM")),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn docker_save_tarball_should_resolve_attestations() {
+        let tmp = tempfile::tempdir().unwrap();
+        let layout = subdir(&tmp, "docker_save_layout");
+        attestation_layout(&layout);
+        let archive = subdir(&tmp, "docker_save").join("redis.tar");
+        tarball(&layout, &archive);
+        let generator = generator(&tmp, vec![archive]);
+
+        let sbom = generator.generate(&component("redis:7.2")).await.unwrap();
+        assert_eq!(sbom.spec().unwrap().format(), SbomFormat::CycloneDxJson);
+    }
+
+    #[tokio::test]
+    async fn missing_sbom_should_fail_generation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = subdir(&tmp, "missing");
+        referrer_layout(&root);
+        let generator = generator(&tmp, vec![subdir(&tmp, "missing_tree"), root.clone()]);
+
+        for reference in ["nginx:1.28", "redis:7.2"] {
+            let err = generator.generate(&component(reference)).await.unwrap_err();
+            assert!(
+                matches!(&err, SbomGeneratorError::NotFound(_, reason) if reason.starts_with("no pre-built SBOM")),
+                "{err:?}"
+            );
+        }
+
+        // Blobs are content-addressed: one that was altered is rejected.
+        let sbom = Digest::compute(DigestAlgorithm::Sha256, SPDX.as_bytes());
+        write(&root.join("blobs/sha256").join(sbom.hex()), b"tampered");
+        let err = generator
+            .generate(&component("nginx:1.27"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, SbomGeneratorError::GenerationFailed(_, reason) if reason.contains("does not match")),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::process::Stdio;
+
+use tokio::process::Command;
+
+use crate::application::service::sbom_generator::SbomGeneratorError;
+use crate::domain::component::id::ComponentId;
+
+/// Longest tail of the standard error of a failed tool kept in [`SbomGeneratorError`].
+const MAX_STDERR_LEN: usize = 4096;
+
+/// Check that a command line tool can be run, with `tool --version`.
+///
+/// # Errors
+///
+/// Returns [`SbomGeneratorError::ToolUnavailable`] if the tool is not installed or not executable.
+pub fn check_available(tool: &str) -> Result<(), SbomGeneratorError> {
+    let status = std::process::Command::new(tool)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+
+    match status {
+        Ok(s) if s.success() => Ok(()),
+        Ok(_) => Err(SbomGeneratorError::ToolUnavailable(
+            tool.to_string(),
+            format!("{tool} --version returned non-zero exit code"),
+        )),
+        Err(e) => Err(SbomGeneratorError::ToolUnavailable(
+            tool.to_string(),
+            format!("{tool} not found or not executable: {e}"),
+        )),
+    }
+}
+
+/// Run a tool generating the SBOM of `component` on its standard output.
+///
+/// The tool is killed if the returned future is dropped, so that timeouts and cancellations
+/// do not leave processes behind.
+///
+/// # Errors
+///
+/// Returns [`SbomGeneratorError::GenerationFailed`] if the tool cannot be started, exits
+/// with a failure, in which case the end of its standard error is included, or prints nothing.
+pub async fn run(
+    tool: &str,
+    args: &[&str],
+    component: &ComponentId,
+) -> Result<Vec<u8>, SbomGeneratorError> {
+    let failed =
+        |reason: String| SbomGeneratorError::GenerationFailed(component.to_string(), reason);
+
+    let output = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| failed(format!("{tool} could not be started: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(failed(format!(
+            "{tool} exited with {}: {}",
+            output.status,
+            tail(stderr.trim())
+        )));
+    }
+    if output.stdout.is_empty() {
+        return Err(failed(format!("{tool} produced an empty SBOM")));
+    }
+    Ok(output.stdout)
+}
+
+fn tail(text: &str) -> &str {
+    let start = text.len().saturating_sub(MAX_STDERR_LEN);
+    let start = (start..text.len())
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(text.len());
+    &text[start..]
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::time::{Duration, Instant};
+
+    fn component() -> ComponentId {
+        ComponentId::from_str("nginx:1.27").unwrap()
+    }
+
+    async fn sh(script: &str) -> Result<Vec<u8>, SbomGeneratorError> {
+        run("sh", &["-c", script], &component()).await
+    }
+
+    #[tokio::test]
+    async fn run_should_return_standard_output() {
+        assert_eq!(sh("printf '{}'").await.unwrap(), b"{}");
+    }
+
+    #[tokio::test]
+    async fn failures_should_carry_standard_error() {
+        let err = sh("echo 'pulling image' >&2; echo 'MANIFEST_UNKNOWN' >&2; exit 3")
+            .await
+            .unwrap_err();
+        match err {
+            SbomGeneratorError::GenerationFailed(c, reason) => {
+                assert_eq!(c, component().to_string());
+                assert!(reason.contains("exit status: 3"), "{reason}");
+                assert!(
+                    reason.ends_with("pulling image\nMANIFEST_UNKNOWN"),
+                    "{reason}"
+                );
+            }
+            other => panic!("Expected GenerationFailed, got {other:?}"),
+        }
+
+        let err = sh("true").await.unwrap_err();
+        assert!(matches!(err, SbomGeneratorError::GenerationFailed(_, r) if r.contains("empty")));
+
+        let err = run("venom-missing-tool", &[], &component())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SbomGeneratorError::GenerationFailed(..)));
+    }
+
+    #[tokio::test]
+    async fn long_standard_error_should_be_truncated_to_its_end() {
+        let err = sh("head -c 10000 /dev/zero | tr '\\0' x >&2; echo end >&2; exit 1")
+            .await
+            .unwrap_err();
+        let SbomGeneratorError::GenerationFailed(_, reason) = err else {
+            panic!("Expected GenerationFailed");
+        };
+        assert!(reason.len() < MAX_STDERR_LEN + 100);
+        assert!(reason.ends_with("end"));
+    }
+
+    #[tokio::test]
+    async fn dropped_run_should_kill_the_tool() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("killed");
+        let script = format!("sleep 1; touch {}", marker.display());
+
+        let started = Instant::now();
+        let result = tokio::time::timeout(Duration::from_millis(100), sh(&script)).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!marker.exists());
+    }
+}
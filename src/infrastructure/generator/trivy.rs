@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::application::service::sbom_generator::{SbomGenerator, SbomGeneratorError};
+use crate::application::service::sbom_store::SbomStore;
+use crate::domain::component::{id::ComponentId, sbom::Sbom};
+use crate::infrastructure::generator::{self, process};
+
+/// Generates CycloneDX JSON SBOMs with the `trivy` CLI and hands them to an [`SbomStore`].
+pub struct TrivySbomGenerator {
+    program: String,
+    store: Arc<dyn SbomStore>,
+}
+
+impl TrivySbomGenerator {
+    /// Create a new `TrivySbomGenerator`, validating that the `trivy` CLI tool is available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomGeneratorError::ToolUnavailable`] if the `trivy` tool is not installed or not executable.
+    pub fn new(store: Arc<dyn SbomStore>) -> Result<Self, SbomGeneratorError> {
+        Self::with_program("trivy", store)
+    }
+
+    /// Create a new `TrivySbomGenerator` running the given `trivy` executable instead of the
+    /// one found in `PATH`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomGeneratorError::ToolUnavailable`] if `program` is missing or not executable.
+    pub fn with_program(
+        program: impl Into<String>,
+        store: Arc<dyn SbomStore>,
+    ) -> Result<Self, SbomGeneratorError> {
+        let program = program.into();
+        process::check_available(&program)?;
+        Ok(Self { program, store })
+    }
+}
+
+#[async_trait]
+impl SbomGenerator for TrivySbomGenerator {
+    async fn generate(&self, component: &ComponentId) -> Result<Sbom, SbomGeneratorError> {
+        let target = component.to_string();
+
+        info!("Generating sbom for {target} with trivy");
+
+        let args = [
+            "image",
+            "--format",
+            "cyclonedx",
+            "--image-src",
+            "remote", // pull from the registry, as syft does
+            "--quiet",
+            &target,
+        ];
+        let sbom = process::run(&self.program, &args, component).await?;
+        generator::store(&self.store, component, sbom).await
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::application::service::sbom_store::SbomStoreError;
+    use crate::domain::component::sbom::SbomFormat;
+    use crate::infrastructure::store::filesystem::FileSystemSbomStore;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    /// Stands in for `trivy`: checks the invocation, then fails or prints a document
+    /// depending on the image reference.
+    const FAKE_TRIVY: &str = r#"#!/bin/sh
+if [ "$1" = "--version" ]; then
+    echo "Version: 0.0.0-fake"
+    exit 0
+fi
+[ "$1" = "image" ] && [ "$2" = "--format" ] && [ "$3" = "cyclonedx" ] || exit 2
+for target; do :; done
+case "$target" in
+    *missing*) echo "unable to find the specified image" >&2; exit 1 ;;
+    *empty*) exit 0 ;;
+esac
+printf '{"bomFormat": "CycloneDX", "specVersion": "1.6"}'
+"#;
+
+    fn install_fake_trivy(dir: &Path) -> String {
+        let program = dir.join("trivy");
+        fs::write(&program, FAKE_TRIVY).unwrap();
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+        program.to_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn generate_should_store_trivy_output_and_map_failures() {
+        let tmp = tempfile::tempdir().unwrap();
+        let program = install_fake_trivy(tmp.path());
+        let store = Arc::new(FileSystemSbomStore::new(tmp.path().join("store")).unwrap());
+        let generator = TrivySbomGenerator::with_program(program, store.clone()).unwrap();
+
+        let nginx = ComponentId::from_str("nginx:1.27").unwrap();
+        let sbom = generator.generate(&nginx).await.unwrap();
+        assert_eq!(sbom.spec().unwrap().format(), SbomFormat::CycloneDxJson);
+        assert_eq!(store.get(&nginx), Ok::<_, SbomStoreError>(Some(sbom)));
+
+        // Reruns replace the stored SBOM instead of failing.
+        assert!(generator.generate(&nginx).await.is_ok());
+
+        for reference in ["missing/app:1.0", "empty/app:1.0"] {
+            let component = ComponentId::from_str(reference).unwrap();
+            let err = generator.generate(&component).await.unwrap_err();
+            assert!(
+                matches!(&err, SbomGeneratorError::GenerationFailed(c, _) if *c == component.to_string()),
+                "{err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_program_should_be_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let program = tmp.path().join("trivy").to_str().unwrap().to_string();
+        let store = Arc::new(FileSystemSbomStore::new(tmp.path().join("store")).unwrap());
+
+        let err = TrivySbomGenerator::with_program(program.clone(), store)
+            .err()
+            .unwrap();
+        assert!(
+            matches!(&err, SbomGeneratorError::ToolUnavailable(tool, _) if *tool == program),
+            "{err:?}"
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use crate::application::aggregate::component::event::{
+    ComponentDeprecatedEvent, ComponentRegisteredEvent, SbomAssignedEvent,
+    SbomGenerationFailedEvent, SbomGenerationRequestedEvent,
+};
+use crate::application::service::collection_membership::ComponentEventStream;
+use crate::application::shared::event::Event;
+use crate::application::shared::event::listener::EventListener;
+use crate::domain::component::event::ComponentEvent;
+use crate::domain::component::id::ComponentId;
+
+/// In-memory journal of component events, fed from the `EventBus`.
+#[derive(Default)]
+pub struct InMemoryComponentJournal {
+    streams: RwLock<HashMap<ComponentId, Vec<ComponentEvent>>>,
+}
+
+impl InMemoryComponentJournal {
+    pub fn append(&self, event: ComponentEvent) {
+        let mut streams = self
+            .streams
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        streams
+            .entry(event.component_id().clone())
+            .or_default()
+            .push(event);
+    }
+}
+
+impl ComponentEventStream for InMemoryComponentJournal {
+    fn events(&self, id: &ComponentId) -> Vec<ComponentEvent> {
+        self.streams
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl EventListener<ComponentRegisteredEvent> for InMemoryComponentJournal {
+    async fn on_event(&self, event: &ComponentRegisteredEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.append(payload.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<ComponentDeprecatedEvent> for InMemoryComponentJournal {
+    async fn on_event(&self, event: &ComponentDeprecatedEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.append(payload.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<SbomAssignedEvent> for InMemoryComponentJournal {
+    async fn on_event(&self, event: &SbomAssignedEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.append(payload.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<SbomGenerationFailedEvent> for InMemoryComponentJournal {
+    async fn on_event(&self, event: &SbomGenerationFailedEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.append(payload.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<SbomGenerationRequestedEvent> for InMemoryComponentJournal {
+    async fn on_event(&self, event: &SbomGenerationRequestedEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.append(payload.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::Component;
+    use crate::domain::component::sbom::Sbom;
+    use crate::domain::shared::aggregate::EventSourcedAggregate;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn deprecations_and_sbom_assignments_should_be_journaled() {
+        let journal = InMemoryComponentJournal::default();
+        let id = ComponentId::from_str("registry.test/namespace/a:v0").unwrap();
+        let registered = Component::register(id.clone());
+        let assigned = ComponentEvent::SbomAssigned {
+            component_id: id.clone(),
+            sbom: Sbom::from_url_str("https://example.com/sbom.json")
+                .unwrap()
+                .sealed(b"{}"),
+        };
+        let deprecated = ComponentEvent::ComponentDeprecated {
+            component_id: id.clone(),
+        };
+
+        journal
+            .on_event(&ComponentRegisteredEvent::new(registered.clone()))
+            .await;
+        journal
+            .on_event(&SbomAssignedEvent::new(assigned.clone()))
+            .await;
+        journal
+            .on_event(&ComponentDeprecatedEvent::new(deprecated.clone()))
+            .await;
+
+        assert_eq!(journal.events(&id), vec![registered, assigned, deprecated]);
+        assert!(
+            Component::rehydrate(&journal.events(&id))
+                .unwrap()
+                .is_deprecated()
+        );
+    }
+}
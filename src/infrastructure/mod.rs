@@ -1,2 +1,20 @@
 pub mod bus;
+pub mod feed;
+pub mod fetcher;
 pub mod generator;
+pub mod journal;
+pub mod parser;
+pub mod projection;
+pub mod store;
+
+/// Run `f` on the blocking thread pool.
+///
+/// Adapters hand their blocking I/O, such as filesystem access, to this helper so that it never
+/// holds up the async workers. Fails with the reason `f` did not complete, e.g. a panic.
+pub(crate) async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, String> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,418 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::application::service::sbom_parser::{SbomParser, SbomParserError};
+use crate::domain::component::{
+    inventory::{Checksum, Dependency, HashAlgorithm, Package, PackageInventory},
+    sbom::Sbom,
+};
+use crate::infrastructure::parser::{known_dependencies, package_url, parse_local};
+
+const FORMAT: &str = "CycloneDX";
+const SUPPORTED_SPEC_VERSIONS: [&str; 3] = ["1.4", "1.5", "1.6"];
+
+/// Property under which Syft lists the CPEs of a component besides the main `cpe` field.
+const SYFT_CPE_PROPERTY: &str = "syft:cpe23";
+
+/// Reads CycloneDX 1.4 to 1.6 JSON documents stored on the local filesystem.
+///
+/// Documents are checked against the digest of the [`Sbom`] before being parsed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CycloneDxParser;
+
+impl CycloneDxParser {
+    /// Parse a CycloneDX JSON document. `source` only names the document in errors.
+    ///
+    /// Nested components are flattened into the inventory, and the component described by
+    /// the BOM metadata becomes its subject. Dependencies on anything but components, such as
+    /// services, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomParserError::Malformed`] if the document is not valid CycloneDX JSON,
+    /// [`SbomParserError::UnsupportedFormat`] or [`SbomParserError::UnsupportedSpecVersion`]
+    /// if it is not a supported CycloneDX version, or [`SbomParserError::InvalidInventory`]
+    /// if references are duplicated.
+    pub fn parse_document(source: &str, json: &str) -> Result<PackageInventory, SbomParserError> {
+        let bom: Bom = serde_json::from_str(json)
+            .map_err(|e| SbomParserError::Malformed(source.to_string(), e.to_string()))?;
+
+        if bom.bom_format != FORMAT {
+            return Err(SbomParserError::UnsupportedFormat(
+                source.to_string(),
+                FORMAT.to_string(),
+            ));
+        }
+        if !SUPPORTED_SPEC_VERSIONS.contains(&bom.spec_version.as_str()) {
+            return Err(SbomParserError::UnsupportedSpecVersion(
+                source.to_string(),
+                FORMAT.to_string(),
+                bom.spec_version,
+            ));
+        }
+
+        // The described component may itself nest components, which belong to the inventory.
+        let mut described = bom
+            .metadata
+            .and_then(|m| m.component)
+            .map(BomComponent::into_packages)
+            .unwrap_or_default()
+            .into_iter();
+        let subject = described.next();
+        let packages: Vec<_> = described
+            .chain(
+                bom.components
+                    .into_iter()
+                    .flat_map(BomComponent::into_packages),
+            )
+            .collect();
+        let dependencies = known_dependencies(
+            source,
+            packages.iter().chain(&subject),
+            bom.dependencies.into_iter().flat_map(|node| {
+                node.depends_on
+                    .into_iter()
+                    .map(move |to| Dependency::new(node.reference.clone(), to))
+            }),
+        );
+
+        PackageInventory::new(subject, packages, dependencies)
+            .map_err(|e| SbomParserError::InvalidInventory(source.to_string(), e))
+    }
+}
+
+#[async_trait]
+impl SbomParser for CycloneDxParser {
+    async fn parse(&self, sbom: &Sbom) -> Result<PackageInventory, SbomParserError> {
+        parse_local(sbom, |sbom, content| {
+            Self::parse_document(&sbom.to_string(), content)
+        })
+        .await
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: String,
+    spec_version: String,
+    metadata: Option<BomMetadata>,
+    #[serde(default)]
+    components: Vec<BomComponent>,
+    #[serde(default)]
+    dependencies: Vec<BomDependency>,
+}
+
+#[derive(Deserialize)]
+struct BomMetadata {
+    component: Option<BomComponent>,
+}
+
+#[derive(Deserialize)]
+struct BomComponent {
+    #[serde(rename = "bom-ref")]
+    bom_ref: Option<String>,
+    name: String,
+    version: Option<String>,
+    purl: Option<String>,
+    cpe: Option<String>,
+    #[serde(default)]
+    licenses: Vec<BomLicenseChoice>,
+    #[serde(default)]
+    hashes: Vec<BomHash>,
+    #[serde(default)]
+    properties: Vec<BomProperty>,
+    #[serde(default)]
+    components: Vec<BomComponent>,
+}
+
+impl BomComponent {
+    /// The component followed by its nested components, depth first.
+    fn into_packages(self) -> Vec<Package> {
+        // `bom-ref` is optional: fall back on the most specific identity available.
+        let reference = self
+            .bom_ref
+            .or_else(|| self.purl.clone())
+            .unwrap_or_else(|| match &self.version {
+                Some(version) => format!("{}@{version}", self.name),
+                None => self.name.clone(),
+            });
+
+        let cpes = self.cpe.into_iter().chain(
+            self.properties
+                .into_iter()
+                .filter(|p| p.name == SYFT_CPE_PROPERTY)
+                .filter_map(|p| p.value),
+        );
+        let licenses = self.licenses.into_iter().filter_map(|choice| match choice {
+            BomLicenseChoice::License { license } => license.id.or(license.name),
+            BomLicenseChoice::Expression { expression } => Some(expression),
+        });
+        let hashes = self
+            .hashes
+            .into_iter()
+            .map(|h| Checksum::new(HashAlgorithm::parse(&h.alg), &h.content));
+
+        let mut package = Package::new(reference, self.name)
+            .with_cpes(cpes)
+            .with_licenses(licenses)
+            .with_hashes(hashes);
+        if let Some(version) = self.version {
+            package = package.with_version(version);
+        }
+        if let Some(purl) = self.purl.as_deref().and_then(package_url) {
+            package = package.with_purl(purl);
+        }
+
+        std::iter::once(package)
+            .chain(
+                self.components
+                    .into_iter()
+                    .flat_map(BomComponent::into_packages),
+            )
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BomLicenseChoice {
+    License { license: BomLicense },
+    Expression { expression: String },
+}
+
+#[derive(Deserialize)]
+struct BomLicense {
+    id: Option<String>,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BomHash {
+    alg: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct BomProperty {
+    name: String,
+    value: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BomDependency {
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(default)]
+    depends_on: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::sbom::SbomIntegrityError;
+    use serde_json::json;
+    use std::fs;
+
+    fn document(spec_version: &str) -> serde_json::Value {
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": spec_version,
+            "version": 1,
+            "metadata": {
+                "component": {
+                    "bom-ref": "image",
+                    "type": "container",
+                    "name": "registry.test/app",
+                    "version": "sha256:abc"
+                }
+            },
+            "components": [
+                {
+                    "bom-ref": "pkg:deb/debian/openssl@3.0.11?arch=amd64",
+                    "type": "library",
+                    "name": "openssl",
+                    "version": "3.0.11",
+                    "purl": "pkg:deb/debian/openssl@3.0.11?arch=amd64",
+                    "cpe": "cpe:2.3:a:openssl:openssl:3.0.11:*:*:*:*:*:*:*",
+                    "licenses": [{ "license": { "id": "Apache-2.0" } }],
+                    "hashes": [{ "alg": "SHA-256", "content": "ABC123" }],
+                    "properties": [
+                        { "name": "syft:cpe23", "value": "cpe:2.3:a:openssl_project:openssl:3.0.11:*:*:*:*:*:*:*" },
+                        { "name": "syft:package:type", "value": "deb" }
+                    ]
+                },
+                {
+                    "type": "library",
+                    "name": "zlib",
+                    "version": "1.3",
+                    "licenses": [{ "expression": "Zlib OR MIT" }],
+                    "components": [
+                        { "bom-ref": "minizip", "type": "library", "name": "minizip", "purl": "minizip@1.3" }
+                    ]
+                }
+            ],
+            "dependencies": [
+                { "ref": "image", "dependsOn": ["pkg:deb/debian/openssl@3.0.11?arch=amd64", "zlib@1.3"] },
+                { "ref": "zlib@1.3", "dependsOn": ["minizip"] },
+                { "ref": "minizip" }
+            ]
+        })
+    }
+
+    fn parse(value: &serde_json::Value) -> Result<PackageInventory, SbomParserError> {
+        CycloneDxParser::parse_document("test.json", &value.to_string())
+    }
+
+    #[test]
+    fn supported_versions_should_produce_complete_inventory() {
+        for version in SUPPORTED_SPEC_VERSIONS {
+            let inventory = parse(&document(version)).unwrap();
+
+            assert_eq!(inventory.subject().unwrap().name(), "registry.test/app");
+            assert_eq!(inventory.len(), 3);
+
+            let openssl = inventory
+                .package("pkg:deb/debian/openssl@3.0.11?arch=amd64")
+                .unwrap();
+            assert_eq!(openssl.name(), "openssl");
+            assert_eq!(openssl.version(), Some("3.0.11"));
+            assert_eq!(
+                openssl.purl().map(ToString::to_string).as_deref(),
+                Some("pkg:deb/debian/openssl@3.0.11?arch=amd64")
+            );
+            assert_eq!(openssl.cpes().len(), 2);
+            assert!(openssl.licenses().contains("Apache-2.0"));
+            assert_eq!(
+                openssl.hashes().iter().next().unwrap(),
+                &Checksum::new(HashAlgorithm::Sha256, "abc123")
+            );
+
+            let zlib = inventory.package("zlib@1.3").unwrap();
+            assert!(zlib.licenses().contains("Zlib OR MIT"));
+            assert!(zlib.purl().is_none());
+            // Invalid purls are dropped rather than failing the whole document.
+            assert!(inventory.package("minizip").unwrap().purl().is_none());
+            assert_eq!(
+                inventory.dependencies_of("zlib@1.3").collect::<Vec<_>>(),
+                vec!["minizip"]
+            );
+            assert_eq!(inventory.dependencies_of("image").count(), 2);
+        }
+    }
+
+    #[test]
+    fn minimal_document_should_be_accepted() {
+        let inventory = parse(&json!({ "bomFormat": "CycloneDX", "specVersion": "1.5" })).unwrap();
+        assert!(inventory.is_empty());
+        assert!(inventory.subject().is_none());
+    }
+
+    #[test]
+    fn unsupported_documents_should_be_rejected() {
+        let err = parse(&json!({ "bomFormat": "SPDX", "specVersion": "1.5" })).unwrap_err();
+        assert!(matches!(err, SbomParserError::UnsupportedFormat(..)));
+
+        let err = parse(&document("1.3")).unwrap_err();
+        assert_eq!(
+            err,
+            SbomParserError::UnsupportedSpecVersion(
+                "test.json".to_string(),
+                "CycloneDX".to_string(),
+                "1.3".to_string()
+            )
+        );
+
+        let err = CycloneDxParser::parse_document("test.json", "{ not json").unwrap_err();
+        assert!(matches!(err, SbomParserError::Malformed(..)));
+
+        let err = parse(&json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.6",
+            "components": [{ "type": "library", "version": "1.0" }]
+        }))
+        .unwrap_err();
+        assert!(matches!(err, SbomParserError::Malformed(..)));
+    }
+
+    #[test]
+    fn dangling_dependencies_should_be_ignored() {
+        let inventory = parse(&json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.6",
+            "components": [
+                { "bom-ref": "a", "type": "library", "name": "a" },
+                { "bom-ref": "c", "type": "library", "name": "c" }
+            ],
+            "services": [{ "bom-ref": "api", "name": "api" }],
+            "dependencies": [
+                { "ref": "a", "dependsOn": ["b", "api", "c"] },
+                { "ref": "api", "dependsOn": ["a"] }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(inventory.len(), 2);
+        assert_eq!(
+            inventory.dependencies_of("a").collect::<Vec<_>>(),
+            vec!["c"]
+        );
+        assert_eq!(inventory.dependencies_of("api").count(), 0);
+    }
+
+    #[test]
+    fn nested_metadata_components_should_be_kept() {
+        let inventory = parse(&json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.6",
+            "metadata": {
+                "component": {
+                    "bom-ref": "app",
+                    "type": "application",
+                    "name": "app",
+                    "components": [{ "bom-ref": "plugin", "type": "library", "name": "plugin" }]
+                }
+            },
+            "dependencies": [{ "ref": "app", "dependsOn": ["plugin"] }]
+        }))
+        .unwrap();
+        assert_eq!(inventory.subject().unwrap().reference(), "app");
+        assert_eq!(inventory.package("plugin").unwrap().name(), "plugin");
+        assert_eq!(
+            inventory.dependencies_of("app").collect::<Vec<_>>(),
+            vec!["plugin"]
+        );
+    }
+
+    #[tokio::test]
+    async fn parse_should_read_local_sboms_only() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_path_buf();
+        let content = document("1.6").to_string();
+        fs::write(&path, &content).unwrap();
+
+        let sbom = Sbom::from(path.clone()).sealed(content.as_bytes());
+        let inventory = CycloneDxParser.parse(&sbom).await.unwrap();
+        assert_eq!(inventory.len(), 3);
+
+        fs::write(&path, document("1.5").to_string()).unwrap();
+        let err = CycloneDxParser.parse(&sbom).await.unwrap_err();
+        assert!(matches!(
+            err,
+            SbomParserError::Integrity(SbomIntegrityError::Tampered { .. })
+        ));
+
+        let err = CycloneDxParser
+            .parse(&Sbom::from(path.clone()))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SbomParserError::Integrity(SbomIntegrityError::MissingDigest(_))
+        ));
+
+        let remote = Sbom::from_url_str("https://example.com/sbom.json").unwrap();
+        let err = CycloneDxParser.parse(&remote).await.unwrap_err();
+        assert!(matches!(err, SbomParserError::UnsupportedLocation(_)));
+    }
+}
@@ -0,0 +1,246 @@
+pub mod cyclonedx;
+pub mod spdx;
+
+use std::collections::HashSet;
+use std::fs;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::application::service::sbom_fetcher::SbomFetcher;
+use crate::application::service::sbom_parser::{SbomParser, SbomParserError};
+use crate::domain::component::inventory::{Dependency, Package, PackageInventory};
+use crate::domain::component::purl::PackageUrl;
+use crate::domain::component::sbom::{Sbom, SbomFormat, SbomLocation, SbomSpec};
+use crate::infrastructure::blocking;
+use crate::infrastructure::parser::cyclonedx::CycloneDxParser;
+use crate::infrastructure::parser::spdx::{SpdxJsonParser, SpdxTagValueParser};
+
+/// Recognize the format and specification version of an SBOM document from its contents.
+#[must_use]
+pub fn detect_spec(content: &str) -> Option<SbomSpec> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') {
+        let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+        if json.get("bomFormat").and_then(serde_json::Value::as_str) == Some("CycloneDX") {
+            let version = json.get("specVersion")?.as_str()?;
+            return Some(SbomSpec::new(SbomFormat::CycloneDxJson, version));
+        }
+        let version = json.get("spdxVersion")?.as_str()?.strip_prefix("SPDX-")?;
+        return Some(SbomSpec::new(SbomFormat::SpdxJson, version));
+    }
+
+    trimmed
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))?
+        .strip_prefix("SPDXVersion:")?
+        .trim()
+        .strip_prefix("SPDX-")
+        .map(|version| SbomSpec::new(SbomFormat::SpdxTagValue, version))
+}
+
+/// Parse a purl found in an SBOM.
+///
+/// Tools do not always emit valid purls: an invalid one is dropped with a warning rather than
+/// making the whole document unreadable.
+fn package_url(purl: &str) -> Option<PackageUrl> {
+    purl.parse()
+        .inspect_err(|e| tracing::warn!("Ignoring package URL: {e}"))
+        .ok()
+}
+
+/// Keep the dependency edges between known packages.
+///
+/// Documents may relate packages to elements that are not part of the inventory, such as
+/// CycloneDX services or SPDX files: such edges are dropped with a warning rather than making
+/// the whole document unreadable.
+fn known_dependencies<'a>(
+    source: &str,
+    packages: impl IntoIterator<Item = &'a Package>,
+    dependencies: impl IntoIterator<Item = Dependency>,
+) -> Vec<Dependency> {
+    let known: HashSet<&str> = packages.into_iter().map(Package::reference).collect();
+    dependencies
+        .into_iter()
+        .filter(|d| {
+            let kept = known.contains(d.from()) && known.contains(d.to());
+            if !kept {
+                tracing::warn!(
+                    "Ignoring dependency of '{}' on '{}' in {source}: unknown package",
+                    d.from(),
+                    d.to()
+                );
+            }
+            kept
+        })
+        .collect()
+}
+
+/// Read a local SBOM, checking its contents against the digest recorded on it.
+///
+/// # Errors
+///
+/// Returns [`SbomParserError::UnsupportedLocation`] for remote SBOMs,
+/// [`SbomParserError::Unreadable`] if the file cannot be read as UTF-8 text, or
+/// [`SbomParserError::Integrity`] if the SBOM has no digest or the contents do not match it.
+pub fn read_verified(sbom: &Sbom) -> Result<String, SbomParserError> {
+    let SbomLocation::Local(path) = sbom.location() else {
+        return Err(SbomParserError::UnsupportedLocation(sbom.to_string()));
+    };
+    let content =
+        fs::read(path).map_err(|e| SbomParserError::Unreadable(sbom.to_string(), e.to_string()))?;
+    sbom.verify(&content)?;
+    String::from_utf8(content)
+        .map_err(|e| SbomParserError::Unreadable(sbom.to_string(), e.to_string()))
+}
+
+/// Read a local SBOM with [`read_verified`] and parse its contents.
+async fn parse_local(
+    sbom: &Sbom,
+    parse: fn(&Sbom, &str) -> Result<PackageInventory, SbomParserError>,
+) -> Result<PackageInventory, SbomParserError> {
+    let source = sbom.to_string();
+    let sbom = sbom.clone();
+    blocking(move || parse(&sbom, &read_verified(&sbom)?))
+        .await
+        .map_err(|e| SbomParserError::Unreadable(source, e))?
+}
+
+/// Reads local SBOMs in any supported format.
+///
+/// The format recorded on the [`Sbom`] is trusted when present, and detected from the
+/// document contents otherwise.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SbomDocumentParser;
+
+impl SbomDocumentParser {
+    /// Parse contents already read from the SBOM location and checked against its digest.
+    fn parse_document(sbom: &Sbom, content: &str) -> Result<PackageInventory, SbomParserError> {
+        let format = sbom
+            .spec()
+            .cloned()
+            .or_else(|| detect_spec(content))
+            .map(|spec| spec.format())
+            .ok_or_else(|| {
+                SbomParserError::UnsupportedFormat(
+                    sbom.to_string(),
+                    "CycloneDX or SPDX".to_string(),
+                )
+            })?;
+
+        let source = sbom.to_string();
+        match format {
+            SbomFormat::CycloneDxJson => CycloneDxParser::parse_document(&source, content),
+            SbomFormat::SpdxJson => SpdxJsonParser::parse_document(&source, content),
+            SbomFormat::SpdxTagValue => SpdxTagValueParser::parse_document(&source, content),
+        }
+    }
+}
+
+#[async_trait]
+impl SbomParser for SbomDocumentParser {
+    async fn parse(&self, sbom: &Sbom) -> Result<PackageInventory, SbomParserError> {
+        parse_local(sbom, Self::parse_document).await
+    }
+}
+
+/// Reads SBOMs in any supported format, fetching remote ones first.
+///
+/// Remote documents are read like local ones, including the digest check when the SBOM
+/// carries one.
+pub struct FetchingSbomParser {
+    fetcher: Arc<dyn SbomFetcher>,
+}
+
+impl FetchingSbomParser {
+    pub fn new(fetcher: Arc<dyn SbomFetcher>) -> Self {
+        Self { fetcher }
+    }
+}
+
+#[async_trait]
+impl SbomParser for FetchingSbomParser {
+    async fn parse(&self, sbom: &Sbom) -> Result<PackageInventory, SbomParserError> {
+        if let SbomLocation::Local(_) = sbom.location() {
+            return SbomDocumentParser.parse(sbom).await;
+        }
+
+        let fetched = self.fetcher.fetch(sbom).await?;
+        let content = String::from_utf8(fetched.content)
+            .map_err(|e| SbomParserError::Unreadable(sbom.to_string(), e.to_string()))?;
+        SbomDocumentParser::parse_document(&fetched.sbom, &content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_spec_should_recognize_supported_formats() {
+        let cases = [
+            (
+                r#"{"bomFormat": "CycloneDX", "specVersion": "1.5"}"#,
+                SbomSpec::new(SbomFormat::CycloneDxJson, "1.5"),
+            ),
+            (
+                r#"{"spdxVersion": "SPDX-2.3", "SPDXID": "SPDXRef-DOCUMENT"}"#,
+                SbomSpec::new(SbomFormat::SpdxJson, "2.3"),
+            ),
+            (
+                "# generated\n\nSPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\n",
+                SbomSpec::new(SbomFormat::SpdxTagValue, "2.3"),
+            ),
+        ];
+        for (content, spec) in cases {
+            assert_eq!(detect_spec(content), Some(spec));
+        }
+
+        assert_eq!(detect_spec(r#"{"name": "unknown"}"#), None);
+        assert_eq!(detect_spec("PackageName: zlib"), None);
+    }
+
+    #[tokio::test]
+    async fn parse_should_dispatch_on_detected_format() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let path = file.path().to_path_buf();
+        let content = "SPDXVersion: SPDX-2.3\nPackageName: zlib\nSPDXID: SPDXRef-zlib\n";
+        fs::write(&path, content).unwrap();
+
+        let sbom = Sbom::from(path.clone()).sealed(content.as_bytes());
+        let inventory = SbomDocumentParser.parse(&sbom).await.unwrap();
+        assert_eq!(inventory.package("SPDXRef-zlib").unwrap().name(), "zlib");
+
+        fs::write(&path, "not an sbom").unwrap();
+        let err = SbomDocumentParser
+            .parse(&Sbom::from(path.clone()).sealed(b"not an sbom"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SbomParserError::UnsupportedFormat(..)));
+    }
+
+    #[tokio::test]
+    async fn fetching_parser_should_read_remote_sboms() {
+        use crate::infrastructure::fetcher::caching::CachingSbomFetcher;
+        use std::time::Duration;
+        use url::Url;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vendor.spdx");
+        let content = "SPDXVersion: SPDX-2.3\nPackageName: zlib\nSPDXID: SPDXRef-zlib\n";
+        fs::write(&path, content).unwrap();
+
+        let fetcher =
+            CachingSbomFetcher::new(dir.path().join("cache"), Duration::from_secs(1), 1024)
+                .unwrap();
+        let parser = FetchingSbomParser::new(Arc::new(fetcher));
+
+        let remote = Sbom::from(Url::from_file_path(&path).unwrap());
+        let inventory = parser.parse(&remote).await.unwrap();
+        assert_eq!(inventory.package("SPDXRef-zlib").unwrap().name(), "zlib");
+
+        let err = parser.parse(&remote.sealed(b"{}")).await.unwrap_err();
+        assert!(matches!(err, SbomParserError::Fetch(_)), "{err:?}");
+    }
+}
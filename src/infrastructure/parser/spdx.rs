@@ -0,0 +1,544 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::application::service::sbom_parser::{SbomParser, SbomParserError};
+use crate::domain::component::{
+    inventory::{Checksum, Dependency, HashAlgorithm, Package, PackageInventory},
+    sbom::Sbom,
+};
+use crate::infrastructure::parser::{known_dependencies, package_url, parse_local};
+
+const FORMAT: &str = "SPDX";
+const SUPPORTED_SPEC_VERSIONS: [&str; 1] = ["SPDX-2.3"];
+const DOCUMENT_ID: &str = "SPDXRef-DOCUMENT";
+
+/// Reads SPDX 2.3 JSON documents stored on the local filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpdxJsonParser;
+
+impl SpdxJsonParser {
+    /// Parse an SPDX JSON document. `source` only names the document in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomParserError::Malformed`] if the document is not valid SPDX JSON, and
+    /// otherwise the same errors as [`SpdxTagValueParser::parse_document`].
+    pub fn parse_document(source: &str, json: &str) -> Result<PackageInventory, SbomParserError> {
+        let document: JsonDocument = serde_json::from_str(json)
+            .map_err(|e| SbomParserError::Malformed(source.to_string(), e.to_string()))?;
+        SpdxDocument::from(document).into_inventory(source)
+    }
+}
+
+#[async_trait]
+impl SbomParser for SpdxJsonParser {
+    async fn parse(&self, sbom: &Sbom) -> Result<PackageInventory, SbomParserError> {
+        parse_local(sbom, |sbom, content| {
+            Self::parse_document(&sbom.to_string(), content)
+        })
+        .await
+    }
+}
+
+/// Reads SPDX 2.3 tag-value documents stored on the local filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpdxTagValueParser;
+
+impl SpdxTagValueParser {
+    /// Parse an SPDX tag-value document. `source` only names the document in errors.
+    ///
+    /// File and snippet sections are skipped, as are relationships that do not link two
+    /// packages: `DEPENDS_ON` and `CONTAINS` (and their inverses) become dependency edges, and
+    /// the single package the document `DESCRIBES`, if any, becomes the inventory subject.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomParserError::Malformed`] if a line is not a `Tag: value` pair or a
+    /// `<text>` block is not closed, [`SbomParserError::UnsupportedFormat`] or
+    /// [`SbomParserError::UnsupportedSpecVersion`] if it is not an SPDX 2.3 document, or
+    /// [`SbomParserError::InvalidInventory`] if package identifiers are duplicated.
+    pub fn parse_document(source: &str, text: &str) -> Result<PackageInventory, SbomParserError> {
+        let malformed = |line: usize, reason: &str| {
+            SbomParserError::Malformed(source.to_string(), format!("line {line}: {reason}"))
+        };
+
+        let mut document = SpdxDocument::default();
+        let mut section = Section::Document;
+        let mut lines = text.lines().enumerate().map(|(i, l)| (i + 1, l));
+
+        while let Some((number, line)) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (tag, value) = line
+                .split_once(':')
+                .ok_or_else(|| malformed(number, "expected `Tag: value`"))?;
+            let mut value = value.trim().to_string();
+            if let Some(start) = value.strip_prefix("<text>") {
+                value = start.to_string();
+                while !value.contains("</text>") {
+                    let (_, next) = lines
+                        .next()
+                        .ok_or_else(|| malformed(number, "unterminated <text> block"))?;
+                    value.push('\n');
+                    value.push_str(next);
+                }
+                value.truncate(value.find("</text>").unwrap_or(value.len()));
+            }
+
+            match tag.trim() {
+                "SPDXVersion" => document.version = Some(value),
+                "PackageName" => {
+                    document.packages.push(SpdxPackage {
+                        name: value,
+                        ..SpdxPackage::default()
+                    });
+                    section = Section::Package;
+                }
+                "FileName" | "SnippetSPDXID" | "LicenseID" => section = Section::Other,
+                "Relationship" => {
+                    let parts: Vec<&str> = value.split_whitespace().collect();
+                    let [from, kind, to] = parts[..] else {
+                        return Err(malformed(number, "expected `Relationship: A TYPE B`"));
+                    };
+                    document.relationships.push(SpdxRelationship {
+                        from: from.to_string(),
+                        kind: kind.to_string(),
+                        to: to.to_string(),
+                    });
+                }
+                tag => {
+                    if let (Section::Package, Some(package)) =
+                        (section, document.packages.last_mut())
+                    {
+                        package.set(tag, value);
+                    }
+                }
+            }
+        }
+
+        document.into_inventory(source)
+    }
+}
+
+#[async_trait]
+impl SbomParser for SpdxTagValueParser {
+    async fn parse(&self, sbom: &Sbom) -> Result<PackageInventory, SbomParserError> {
+        parse_local(sbom, |sbom, content| {
+            Self::parse_document(&sbom.to_string(), content)
+        })
+        .await
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Section {
+    Document,
+    Package,
+    Other,
+}
+
+/// Contents shared by both SPDX serializations.
+#[derive(Default)]
+struct SpdxDocument {
+    version: Option<String>,
+    describes: Vec<String>,
+    packages: Vec<SpdxPackage>,
+    relationships: Vec<SpdxRelationship>,
+}
+
+impl SpdxDocument {
+    fn into_inventory(self, source: &str) -> Result<PackageInventory, SbomParserError> {
+        let version = self.version.ok_or_else(|| {
+            SbomParserError::UnsupportedFormat(source.to_string(), FORMAT.to_string())
+        })?;
+        if !SUPPORTED_SPEC_VERSIONS.contains(&version.as_str()) {
+            return Err(SbomParserError::UnsupportedSpecVersion(
+                source.to_string(),
+                FORMAT.to_string(),
+                version,
+            ));
+        }
+
+        let mut described: Vec<String> = self.describes;
+        let mut edges = Vec::new();
+        for SpdxRelationship { from, kind, to } in self.relationships {
+            match kind.as_str() {
+                "DESCRIBES" if from == DOCUMENT_ID => described.push(to),
+                "DESCRIBED_BY" if to == DOCUMENT_ID => described.push(from),
+                "DEPENDS_ON" | "CONTAINS" => edges.push(Dependency::new(from, to)),
+                "DEPENDENCY_OF" | "CONTAINED_BY" => edges.push(Dependency::new(to, from)),
+                _ => {}
+            }
+        }
+        described.sort();
+        described.dedup();
+
+        let (subject, packages): (Vec<_>, Vec<_>) = self
+            .packages
+            .into_iter()
+            .map(SpdxPackage::into_package)
+            .partition(|p| described.len() == 1 && p.reference() == described[0]);
+
+        let edges = known_dependencies(source, packages.iter().chain(&subject), edges);
+
+        PackageInventory::new(subject.into_iter().next(), packages, edges)
+            .map_err(|e| SbomParserError::InvalidInventory(source.to_string(), e))
+    }
+}
+
+#[derive(Default)]
+struct SpdxPackage {
+    id: String,
+    name: String,
+    version: Option<String>,
+    purl: Option<String>,
+    cpes: Vec<String>,
+    licenses: Vec<String>,
+    checksums: Vec<Checksum>,
+}
+
+impl SpdxPackage {
+    /// Record a tag-value field of the package section.
+    fn set(&mut self, tag: &str, value: String) {
+        match tag {
+            "SPDXID" => self.id = value,
+            "PackageVersion" => self.version = Some(value),
+            "PackageLicenseConcluded" | "PackageLicenseDeclared" => self.licenses.push(value),
+            "PackageChecksum" => {
+                if let Some((algorithm, value)) = value.split_once(':') {
+                    self.checksums
+                        .push(Checksum::new(HashAlgorithm::parse(algorithm), value));
+                }
+            }
+            "ExternalRef" => {
+                let parts: Vec<&str> = value.split_whitespace().collect();
+                if let [_category, kind, locator] = parts[..] {
+                    self.add_external_ref(kind, locator);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_external_ref(&mut self, kind: &str, locator: &str) {
+        match kind {
+            "purl" => self.purl = Some(locator.to_string()),
+            "cpe22Type" | "cpe23Type" => self.cpes.push(locator.to_string()),
+            _ => {}
+        }
+    }
+
+    fn into_package(self) -> Package {
+        let licenses = self
+            .licenses
+            .into_iter()
+            .filter(|l| !matches!(l.as_str(), "NOASSERTION" | "NONE" | ""));
+
+        let mut package = Package::new(self.id, self.name)
+            .with_cpes(self.cpes)
+            .with_licenses(licenses)
+            .with_hashes(self.checksums);
+        if let Some(version) = self.version {
+            package = package.with_version(version);
+        }
+        if let Some(purl) = self.purl.as_deref().and_then(package_url) {
+            package = package.with_purl(purl);
+        }
+        package
+    }
+}
+
+struct SpdxRelationship {
+    from: String,
+    kind: String,
+    to: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonDocument {
+    spdx_version: Option<String>,
+    #[serde(default)]
+    document_describes: Vec<String>,
+    #[serde(default)]
+    packages: Vec<JsonPackage>,
+    #[serde(default)]
+    relationships: Vec<JsonRelationship>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonPackage {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: String,
+    version_info: Option<String>,
+    license_concluded: Option<String>,
+    license_declared: Option<String>,
+    #[serde(default)]
+    checksums: Vec<JsonChecksum>,
+    #[serde(default)]
+    external_refs: Vec<JsonExternalRef>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonChecksum {
+    algorithm: String,
+    checksum_value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExternalRef {
+    reference_type: String,
+    reference_locator: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRelationship {
+    spdx_element_id: String,
+    relationship_type: String,
+    related_spdx_element: String,
+}
+
+impl From<JsonDocument> for SpdxDocument {
+    fn from(document: JsonDocument) -> Self {
+        let packages = document
+            .packages
+            .into_iter()
+            .map(|p| {
+                let mut package = SpdxPackage {
+                    id: p.spdx_id,
+                    name: p.name,
+                    version: p.version_info,
+                    licenses: p
+                        .license_concluded
+                        .into_iter()
+                        .chain(p.license_declared)
+                        .collect(),
+                    checksums: p
+                        .checksums
+                        .iter()
+                        .map(|c| {
+                            Checksum::new(HashAlgorithm::parse(&c.algorithm), &c.checksum_value)
+                        })
+                        .collect(),
+                    ..SpdxPackage::default()
+                };
+                for r in p.external_refs {
+                    package.add_external_ref(&r.reference_type, &r.reference_locator);
+                }
+                package
+            })
+            .collect();
+
+        Self {
+            version: document.spdx_version,
+            describes: document.document_describes,
+            packages,
+            relationships: document
+                .relationships
+                .into_iter()
+                .map(|r| SpdxRelationship {
+                    from: r.spdx_element_id,
+                    kind: r.relationship_type,
+                    to: r.related_spdx_element,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PURL: &str = "pkg:deb/debian/openssl@3.0.11?arch=amd64";
+
+    fn json_document(version: &str) -> String {
+        json!({
+            "spdxVersion": version,
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": "registry.test/app",
+            "documentDescribes": ["SPDXRef-image"],
+            "packages": [
+                { "SPDXID": "SPDXRef-image", "name": "registry.test/app", "versionInfo": "v1" },
+                {
+                    "SPDXID": "SPDXRef-openssl",
+                    "name": "openssl",
+                    "versionInfo": "3.0.11",
+                    "licenseConcluded": "NOASSERTION",
+                    "licenseDeclared": "Apache-2.0",
+                    "checksums": [{ "algorithm": "SHA256", "checksumValue": "ABC123" }],
+                    "externalRefs": [
+                        { "referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl", "referenceLocator": PURL },
+                        { "referenceCategory": "SECURITY", "referenceType": "cpe23Type", "referenceLocator": "cpe:2.3:a:openssl:openssl:3.0.11:*:*:*:*:*:*:*" }
+                    ]
+                },
+                { "SPDXID": "SPDXRef-zlib", "name": "zlib", "versionInfo": "1.3" }
+            ],
+            "relationships": [
+                { "spdxElementId": "SPDXRef-image", "relationshipType": "CONTAINS", "relatedSpdxElement": "SPDXRef-openssl" },
+                { "spdxElementId": "SPDXRef-zlib", "relationshipType": "DEPENDENCY_OF", "relatedSpdxElement": "SPDXRef-openssl" },
+                { "spdxElementId": "SPDXRef-openssl", "relationshipType": "DEPENDS_ON", "relatedSpdxElement": "SPDXRef-File-libssl" },
+                { "spdxElementId": "SPDXRef-openssl", "relationshipType": "GENERATED_FROM", "relatedSpdxElement": "SPDXRef-zlib" }
+            ]
+        })
+        .to_string()
+    }
+
+    const TAG_VALUE: &str = "\
+SPDXVersion: SPDX-2.3
+DataLicense: CC0-1.0
+SPDXID: SPDXRef-DOCUMENT
+DocumentName: registry.test/app
+DocumentComment: <text>Generated for
+testing purposes</text>
+
+## Packages
+PackageName: registry.test/app
+SPDXID: SPDXRef-image
+PackageVersion: v1
+
+PackageName: openssl
+SPDXID: SPDXRef-openssl
+PackageVersion: 3.0.11
+PackageChecksum: SHA256: ABC123
+PackageLicenseConcluded: NOASSERTION
+PackageLicenseDeclared: Apache-2.0
+ExternalRef: PACKAGE-MANAGER purl pkg:deb/debian/openssl@3.0.11?arch=amd64
+ExternalRef: SECURITY cpe23Type cpe:2.3:a:openssl:openssl:3.0.11:*:*:*:*:*:*:*
+
+FileName: /usr/lib/libssl.so
+SPDXID: SPDXRef-File-libssl
+FileChecksum: SHA1: 0123
+
+PackageName: zlib
+SPDXID: SPDXRef-zlib
+PackageVersion: 1.3
+
+Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-image
+Relationship: SPDXRef-image CONTAINS SPDXRef-openssl
+Relationship: SPDXRef-zlib DEPENDENCY_OF SPDXRef-openssl
+Relationship: SPDXRef-openssl DEPENDS_ON SPDXRef-File-libssl
+";
+
+    fn assert_inventory(inventory: &PackageInventory) {
+        assert_eq!(inventory.subject().unwrap().reference(), "SPDXRef-image");
+        assert_eq!(inventory.len(), 2);
+
+        let openssl = inventory.package("SPDXRef-openssl").unwrap();
+        assert_eq!(openssl.name(), "openssl");
+        assert_eq!(openssl.version(), Some("3.0.11"));
+        assert_eq!(
+            openssl.purl().map(ToString::to_string).as_deref(),
+            Some(PURL)
+        );
+        assert_eq!(openssl.cpes().len(), 1);
+        assert_eq!(
+            openssl.licenses().iter().collect::<Vec<_>>(),
+            vec!["Apache-2.0"]
+        );
+        assert!(
+            openssl
+                .hashes()
+                .contains(&Checksum::new(HashAlgorithm::Sha256, "abc123"))
+        );
+
+        assert_eq!(
+            inventory
+                .dependencies_of("SPDXRef-image")
+                .collect::<Vec<_>>(),
+            vec!["SPDXRef-openssl"]
+        );
+        assert_eq!(
+            inventory
+                .dependencies_of("SPDXRef-openssl")
+                .collect::<Vec<_>>(),
+            vec!["SPDXRef-zlib"]
+        );
+    }
+
+    #[test]
+    fn json_document_should_produce_inventory() {
+        let inventory =
+            SpdxJsonParser::parse_document("test.spdx.json", &json_document("SPDX-2.3")).unwrap();
+        assert_inventory(&inventory);
+    }
+
+    #[test]
+    fn tag_value_document_should_produce_inventory() {
+        let inventory = SpdxTagValueParser::parse_document("test.spdx", TAG_VALUE).unwrap();
+        assert_inventory(&inventory);
+    }
+
+    #[test]
+    fn dangling_relationships_should_be_ignored() {
+        let text = "\
+SPDXVersion: SPDX-2.3
+PackageName: a
+SPDXID: SPDXRef-a
+
+PackageName: c
+SPDXID: SPDXRef-c
+
+Relationship: SPDXRef-a DEPENDS_ON SPDXRef-b
+Relationship: SPDXRef-a DEPENDS_ON SPDXRef-c
+Relationship: SPDXRef-b DEPENDS_ON SPDXRef-a
+";
+        let inventory = SpdxTagValueParser::parse_document("test.spdx", text).unwrap();
+        assert_eq!(inventory.len(), 2);
+        assert_eq!(
+            inventory.dependencies_of("SPDXRef-a").collect::<Vec<_>>(),
+            vec!["SPDXRef-c"]
+        );
+        assert_eq!(inventory.dependencies_of("SPDXRef-b").count(), 0);
+    }
+
+    #[test]
+    fn unsupported_versions_should_be_rejected() {
+        let err = SpdxJsonParser::parse_document("test.spdx.json", &json_document("SPDX-2.2"))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SbomParserError::UnsupportedSpecVersion(
+                "test.spdx.json".to_string(),
+                "SPDX".to_string(),
+                "SPDX-2.2".to_string()
+            )
+        );
+
+        let err = SpdxTagValueParser::parse_document("test.spdx", "PackageName: zlib").unwrap_err();
+        assert!(matches!(err, SbomParserError::UnsupportedFormat(..)));
+    }
+
+    #[test]
+    fn malformed_tag_value_should_report_line() {
+        let err = SpdxTagValueParser::parse_document(
+            "test.spdx",
+            "SPDXVersion: SPDX-2.3\nnot a tag value pair",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            SbomParserError::Malformed(
+                "test.spdx".to_string(),
+                "line 2: expected `Tag: value`".to_string()
+            )
+        );
+
+        let err = SpdxTagValueParser::parse_document(
+            "test.spdx",
+            "SPDXVersion: SPDX-2.3\nDocumentComment: <text>never closed",
+        )
+        .unwrap_err();
+        assert!(matches!(err, SbomParserError::Malformed(..)));
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use crate::application::aggregate::vulnerability::event::ManagedVulnerabilityRegisteredEvent;
+use crate::application::service::managed_vulnerability_index::ManagedVulnerabilityIndex;
+use crate::application::shared::event::Event;
+use crate::application::shared::event::listener::EventListener;
+use crate::domain::component::id::ComponentId;
+use crate::domain::vulnerability::event::ManagedVulnerabilityEvent;
+use crate::domain::vulnerability::id::{ManagedVulnerabilityId, VulnerabilityId};
+
+/// In-memory index of managed vulnerabilities, fed from the `EventBus`.
+#[derive(Default)]
+pub struct InMemoryManagedVulnerabilityIndex {
+    ids: RwLock<HashSet<ManagedVulnerabilityId>>,
+}
+
+impl InMemoryManagedVulnerabilityIndex {
+    pub fn insert(&self, id: ManagedVulnerabilityId) {
+        self.ids
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(id);
+    }
+
+    fn filter(
+        &self,
+        predicate: impl Fn(&ManagedVulnerabilityId) -> bool,
+    ) -> Vec<ManagedVulnerabilityId> {
+        let mut ids: Vec<_> = self
+            .ids
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .filter(|id| predicate(id))
+            .cloned()
+            .collect();
+        ids.sort_by_cached_key(ToString::to_string);
+        ids
+    }
+}
+
+impl ManagedVulnerabilityIndex for InMemoryManagedVulnerabilityIndex {
+    fn by_component(&self, id: &ComponentId) -> Vec<ManagedVulnerabilityId> {
+        self.filter(|managed| managed.component_id() == id)
+    }
+
+    fn by_vulnerability(&self, id: &VulnerabilityId) -> Vec<ManagedVulnerabilityId> {
+        self.filter(|managed| managed.vulnerability_id().same_vulnerability(id))
+    }
+}
+
+#[async_trait]
+impl EventListener<ManagedVulnerabilityRegisteredEvent> for InMemoryManagedVulnerabilityIndex {
+    async fn on_event(&self, event: &ManagedVulnerabilityRegisteredEvent) {
+        if let Some(ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered { id }) =
+            event.as_payload::<ManagedVulnerabilityEvent>()
+        {
+            self.insert(id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn managed_id(image: &str, cve: &str) -> ManagedVulnerabilityId {
+        ManagedVulnerabilityId::new(
+            ComponentId::from_str(image).unwrap(),
+            VulnerabilityId::new(cve).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn registered_vulnerabilities_should_be_found_by_component_and_vulnerability() {
+        let index = InMemoryManagedVulnerabilityIndex::default();
+        let a1 = managed_id("registry.test/app/a:v1", "CVE-2024-0001");
+        let a2 = managed_id("registry.test/app/a:v1", "CVE-2024-0002");
+        let b1 = managed_id("registry.test/app/b:v1", "CVE-2024-0001");
+        for id in [&a1, &a2, &b1] {
+            index
+                .on_event(&ManagedVulnerabilityRegisteredEvent::new(
+                    ManagedVulnerabilityEvent::ManagedVulnerabilityRegistered { id: id.clone() },
+                ))
+                .await;
+        }
+
+        assert_eq!(index.by_component(a1.component_id()), vec![a1.clone(), a2]);
+        assert_eq!(index.by_vulnerability(a1.vulnerability_id()), vec![a1, b1]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use crate::application::aggregate::component::event::{
+    ComponentDeprecatedEvent, SbomAssignedEvent, SbomGenerationFailedEvent,
+};
+use crate::application::service::sbom_generation_failures::{
+    SbomGenerationFailure, SbomGenerationFailureIndex,
+};
+use crate::application::shared::event::Event;
+use crate::application::shared::event::listener::EventListener;
+use crate::domain::component::event::ComponentEvent;
+use crate::domain::component::id::ComponentId;
+
+/// In-memory index of failed SBOM generations, fed from the `EventBus`.
+#[derive(Default)]
+pub struct InMemorySbomGenerationFailureIndex {
+    failures: RwLock<HashMap<ComponentId, SbomGenerationFailure>>,
+}
+
+impl InMemorySbomGenerationFailureIndex {
+    pub fn insert(&self, failure: SbomGenerationFailure) {
+        self.failures
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(failure.component_id.clone(), failure);
+    }
+
+    pub fn remove(&self, id: &ComponentId) {
+        self.failures
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(id);
+    }
+}
+
+impl SbomGenerationFailureIndex for InMemorySbomGenerationFailureIndex {
+    fn failures(&self) -> Vec<SbomGenerationFailure> {
+        let mut failures: Vec<_> = self
+            .failures
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect();
+        failures.sort_by_cached_key(|f| f.component_id.to_string());
+        failures
+    }
+}
+
+#[async_trait]
+impl EventListener<SbomGenerationFailedEvent> for InMemorySbomGenerationFailureIndex {
+    async fn on_event(&self, event: &SbomGenerationFailedEvent) {
+        if let Some(ComponentEvent::SbomGenerationFailed {
+            component_id,
+            reason,
+            attempts,
+        }) = event.as_payload::<ComponentEvent>()
+        {
+            self.insert(SbomGenerationFailure {
+                component_id: component_id.clone(),
+                reason: reason.clone(),
+                attempts: *attempts,
+                date: *event.date(),
+            });
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<SbomAssignedEvent> for InMemorySbomGenerationFailureIndex {
+    async fn on_event(&self, event: &SbomAssignedEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.remove(payload.component_id());
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener<ComponentDeprecatedEvent> for InMemorySbomGenerationFailureIndex {
+    async fn on_event(&self, event: &ComponentDeprecatedEvent) {
+        if let Some(payload) = event.as_payload::<ComponentEvent>() {
+            self.remove(payload.component_id());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::component::sbom::Sbom;
+    use std::str::FromStr;
+
+    fn id(image: &str) -> ComponentId {
+        ComponentId::from_str(image).unwrap()
+    }
+
+    async fn fail(index: &InMemorySbomGenerationFailureIndex, id: &ComponentId, reason: &str) {
+        index
+            .on_event(&SbomGenerationFailedEvent::new(
+                ComponentEvent::SbomGenerationFailed {
+                    component_id: id.clone(),
+                    reason: reason.to_string(),
+                    attempts: 3,
+                },
+            ))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn failures_should_be_listed_until_resolved() {
+        let index = InMemorySbomGenerationFailureIndex::default();
+        let (nginx, redis, httpd) = (id("nginx:1.27"), id("redis:7.2"), id("httpd:2.4"));
+        fail(&index, &redis, "timed out").await;
+        fail(&index, &nginx, "manifest unknown").await;
+        fail(&index, &httpd, "manifest unknown").await;
+        fail(&index, &redis, "connection reset").await;
+
+        let failures = index.failures();
+        let listed: Vec<_> = failures
+            .iter()
+            .map(|f| (f.component_id.clone(), f.reason.as_str()))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                (httpd.clone(), "manifest unknown"),
+                (nginx.clone(), "manifest unknown"),
+                (redis.clone(), "connection reset"),
+            ]
+        );
+
+        index
+            .on_event(&SbomAssignedEvent::new(ComponentEvent::SbomAssigned {
+                component_id: nginx,
+                sbom: Sbom::from_url_str("https://example.com/sbom.json")
+                    .unwrap()
+                    .sealed(b"{}"),
+            }))
+            .await;
+        index
+            .on_event(&ComponentDeprecatedEvent::new(
+                ComponentEvent::ComponentDeprecated {
+                    component_id: httpd,
+                },
+            ))
+            .await;
+
+        let remaining: Vec<_> = index
+            .failures()
+            .into_iter()
+            .map(|f| f.component_id)
+            .collect();
+        assert_eq!(remaining, vec![redis]);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod in_memory_managed_vulnerability;
+pub mod in_memory_sbom_generation_failure;
@@ -0,0 +1,260 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::application::service::sbom_store::{SbomStore, SbomStoreError};
+use crate::domain::component::{
+    id::{ComponentId, Digest, DigestAlgorithm},
+    sbom::Sbom,
+};
+use crate::infrastructure::parser::detect_spec;
+
+const BLOBS_DIR: &str = "blobs";
+const INDEX_DIR: &str = "index";
+const TMP_DIR: &str = "tmp";
+
+/// Content-addressed SBOM store on the local filesystem.
+///
+/// Layout under the root directory:
+/// - `blobs/<algorithm>/<hex>`: documents, named after their digest and written once;
+/// - `index/<hex>`: one entry per component, named after the digest of its reference and
+///   holding `<blob digest> <component reference>`;
+/// - `tmp/`: files being written, renamed into place once complete so that readers never
+///   observe a partial document or entry.
+pub struct FileSystemSbomStore {
+    root: PathBuf,
+}
+
+impl FileSystemSbomStore {
+    /// Open the store rooted at the given directory, creating its layout if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SbomStoreError::Unavailable`] if the directories cannot be created.
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, SbomStoreError> {
+        let store = Self { root: root.into() };
+        for dir in [BLOBS_DIR, INDEX_DIR, TMP_DIR] {
+            let path = store.root.join(dir);
+            fs::create_dir_all(&path).map_err(|e| unavailable(&path, &e))?;
+        }
+        Ok(store)
+    }
+
+    fn blob_path(&self, digest: &Digest) -> PathBuf {
+        self.root
+            .join(BLOBS_DIR)
+            .join(digest.algorithm().to_string())
+            .join(digest.hex())
+    }
+
+    fn index_path(&self, component: &ComponentId) -> PathBuf {
+        let key = Digest::compute(DigestAlgorithm::Sha256, component.to_string().as_bytes());
+        self.root.join(INDEX_DIR).join(key.hex())
+    }
+
+    fn write_atomically(&self, path: &Path, content: &[u8]) -> Result<(), SbomStoreError> {
+        write_atomically(&self.root.join(TMP_DIR), path, content).map_err(|e| unavailable(path, &e))
+    }
+
+    /// Resolve an index entry into the component it names and the stored document.
+    fn read_entry(&self, path: &Path) -> Result<(ComponentId, Sbom), SbomStoreError> {
+        let corrupt = |reason: String| SbomStoreError::CorruptIndex(display(path), reason);
+
+        let entry = fs::read_to_string(path).map_err(|e| unavailable(path, &e))?;
+        let (digest, component) = entry
+            .trim_end()
+            .split_once(' ')
+            .ok_or_else(|| corrupt("expected `<digest> <component>`".to_string()))?;
+        let digest = Digest::from_str(digest).map_err(|e| corrupt(e.to_string()))?;
+        let component = ComponentId::from_str(component).map_err(|e| corrupt(e.to_string()))?;
+
+        let blob = self.blob_path(&digest);
+        let content = fs::read(&blob)
+            .map_err(|e| corrupt(format!("blob {} cannot be read: {e}", display(&blob))))?;
+        Ok((component, sbom(blob, digest, &content)))
+    }
+}
+
+impl SbomStore for FileSystemSbomStore {
+    fn put(&self, component: &ComponentId, content: &[u8]) -> Result<Sbom, SbomStoreError> {
+        let digest = Digest::compute(DigestAlgorithm::Sha256, content);
+        let blob = self.blob_path(&digest);
+        // Blobs are named after their contents: an existing one needs no rewrite.
+        if !blob.exists() {
+            self.write_atomically(&blob, content)?;
+        }
+
+        let entry = format!("{digest} {component}\n");
+        self.write_atomically(&self.index_path(component), entry.as_bytes())?;
+        Ok(sbom(blob, digest, content))
+    }
+
+    fn get(&self, component: &ComponentId) -> Result<Option<Sbom>, SbomStoreError> {
+        let path = self.index_path(component);
+        if !path.exists() {
+            return Ok(None);
+        }
+        self.read_entry(&path).map(|(_, sbom)| Some(sbom))
+    }
+
+    fn list(&self) -> Result<Vec<(ComponentId, Sbom)>, SbomStoreError> {
+        let index = self.root.join(INDEX_DIR);
+        let mut entries = fs::read_dir(&index)
+            .map_err(|e| unavailable(&index, &e))?
+            .map(|entry| {
+                let entry = entry.map_err(|e| unavailable(&index, &e))?;
+                self.read_entry(&entry.path())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_cached_key(|(component, _)| component.to_string());
+        Ok(entries)
+    }
+}
+
+fn sbom(blob: PathBuf, digest: Digest, content: &[u8]) -> Sbom {
+    let spec = std::str::from_utf8(content).ok().and_then(detect_spec);
+    let sbom = Sbom::from(blob).with_digest(digest);
+    match spec {
+        Some(spec) => sbom.with_spec(spec),
+        None => sbom,
+    }
+}
+
+/// Write `content` to `path` through a file of `tmp_dir` renamed into place, so that readers
+/// never observe a partial file.
+///
+/// `tmp_dir` must be on the same filesystem as `path`. Missing parents of `path` are created.
+pub(crate) fn write_atomically(tmp_dir: &Path, path: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = tmp_dir.join(Uuid::new_v4().to_string());
+    fs::File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp, path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn unavailable(path: &Path, e: &io::Error) -> SbomStoreError {
+    SbomStoreError::Unavailable(display(path), e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::service::sbom_parser::SbomParser;
+    use crate::domain::component::sbom::{SbomFormat, SbomLocation};
+    use crate::infrastructure::parser::SbomDocumentParser;
+    use tempfile::TempDir;
+
+    const CYCLONEDX: &str = r#"{"bomFormat": "CycloneDX", "specVersion": "1.6"}"#;
+    const SPDX: &str = "SPDXVersion: SPDX-2.3\nPackageName: zlib\nSPDXID: SPDXRef-zlib\n";
+
+    /// Store rooted in a fresh temporary directory, removed when the returned guard drops.
+    fn store() -> (TempDir, FileSystemSbomStore) {
+        let root = tempfile::tempdir().unwrap();
+        let store = FileSystemSbomStore::new(root.path()).unwrap();
+        (root, store)
+    }
+
+    fn component(reference: &str) -> ComponentId {
+        ComponentId::from_str(reference).unwrap()
+    }
+
+    fn local_path(sbom: &Sbom) -> &Path {
+        match sbom.location() {
+            SbomLocation::Local(path) => path,
+            SbomLocation::Remote(url) => panic!("Expected a local SBOM, got {url}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn put_should_store_readable_sealed_documents() {
+        let (_root, store) = store();
+        let nginx = component("nginx:1.27");
+
+        let sbom = store.put(&nginx, SPDX.as_bytes()).unwrap();
+        assert_eq!(
+            sbom.digest().unwrap(),
+            &Digest::compute(DigestAlgorithm::Sha256, SPDX.as_bytes())
+        );
+        assert_eq!(sbom.spec().unwrap().format(), SbomFormat::SpdxTagValue);
+        assert!(local_path(&sbom).ends_with(sbom.digest().unwrap().hex()));
+
+        let inventory = SbomDocumentParser.parse(&sbom).await.unwrap();
+        assert_eq!(inventory.package("SPDXRef-zlib").unwrap().name(), "zlib");
+        assert_eq!(store.get(&nginx).unwrap(), Some(sbom));
+        assert_eq!(store.get(&component("nginx:1.28")).unwrap(), None);
+    }
+
+    #[test]
+    fn identical_documents_should_be_stored_once() {
+        let (_root, store) = store();
+        let digest_ref = format!("nginx@sha256:{}", "a".repeat(64));
+
+        let tagged = store
+            .put(&component("nginx:1.27"), CYCLONEDX.as_bytes())
+            .unwrap();
+        let pinned = store
+            .put(&component(&digest_ref), CYCLONEDX.as_bytes())
+            .unwrap();
+        assert_eq!(tagged, pinned);
+
+        let blobs = fs::read_dir(store.root.join(BLOBS_DIR).join("sha256")).unwrap();
+        assert_eq!(blobs.count(), 1);
+        assert_eq!(fs::read_dir(store.root.join(TMP_DIR)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn rerun_should_replace_index_entry() {
+        let (_root, store) = store();
+        let nginx = component("nginx:1.27");
+
+        store.put(&nginx, SPDX.as_bytes()).unwrap();
+        let sbom = store.put(&nginx, CYCLONEDX.as_bytes()).unwrap();
+
+        assert_eq!(store.get(&nginx).unwrap(), Some(sbom));
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn list_should_return_every_component_in_order() {
+        let (_root, store) = store();
+        let (redis, nginx) = (component("redis:7"), component("nginx:1.27"));
+
+        let redis_sbom = store.put(&redis, CYCLONEDX.as_bytes()).unwrap();
+        let nginx_sbom = store.put(&nginx, SPDX.as_bytes()).unwrap();
+
+        assert_eq!(
+            store.list().unwrap(),
+            vec![(nginx, nginx_sbom), (redis, redis_sbom)]
+        );
+    }
+
+    #[test]
+    fn corrupt_index_entry_should_be_reported() {
+        let (_root, store) = store();
+        let nginx = component("nginx:1.27");
+        fs::write(store.index_path(&nginx), "not-a-digest nginx:1.27\n").unwrap();
+
+        let err = store.get(&nginx).unwrap_err();
+        assert!(matches!(err, SbomStoreError::CorruptIndex(..)));
+        assert!(matches!(
+            store.list().unwrap_err(),
+            SbomStoreError::CorruptIndex(..)
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::sync::{PoisonError, RwLock};
+
+use crate::application::service::vulnerability_store::VulnerabilityStore;
+use crate::domain::vulnerability::entity::Vulnerability;
+use crate::domain::vulnerability::id::VulnerabilityId;
+
+/// In-memory vulnerability store, indexed by primary identifier.
+#[derive(Default)]
+pub struct InMemoryVulnerabilityStore {
+    vulnerabilities: RwLock<BTreeMap<VulnerabilityId, Vulnerability>>,
+}
+
+impl VulnerabilityStore for InMemoryVulnerabilityStore {
+    fn find(&self, id: &VulnerabilityId) -> Option<Vulnerability> {
+        let vulnerabilities = self
+            .vulnerabilities
+            .read()
+            .unwrap_or_else(PoisonError::into_inner);
+        vulnerabilities
+            .get(id)
+            .or_else(|| {
+                vulnerabilities
+                    .iter()
+                    .find(|(stored, _)| stored.same_vulnerability(id))
+                    .map(|(_, vulnerability)| vulnerability)
+            })
+            .cloned()
+    }
+
+    fn put(&self, vulnerability: Vulnerability) -> Vec<Vulnerability> {
+        let mut vulnerabilities = self
+            .vulnerabilities
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let replaced: Vec<VulnerabilityId> = vulnerabilities
+            .keys()
+            .filter(|stored| stored.same_vulnerability(vulnerability.id()))
+            .cloned()
+            .collect();
+        let replaced = replaced
+            .iter()
+            .filter_map(|stored| vulnerabilities.remove(stored))
+            .collect();
+        vulnerabilities.insert(vulnerability.id().clone(), vulnerability);
+        replaced
+    }
+
+    fn list(&self) -> Vec<Vulnerability> {
+        self.vulnerabilities
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn id(primary: &str, aliases: &[&str]) -> VulnerabilityId {
+        VulnerabilityId::from_str(primary)
+            .unwrap()
+            .with_aliases(aliases.iter().map(|alias| alias.parse().unwrap()))
+    }
+
+    #[test]
+    fn vulnerabilities_should_be_found_and_replaced_through_any_identifier() {
+        let store = InMemoryVulnerabilityStore::default();
+        let ghsa = Vulnerability::new(id("GHSA-xvch-5gv4-984h", &[])).with_summary("first");
+        assert_eq!(store.put(ghsa.clone()), vec![]);
+
+        // The advisory later gets a CVE, which becomes its primary identifier.
+        let cve = Vulnerability::new(id("GHSA-xvch-5gv4-984h", &["CVE-2024-1234"]));
+        assert_eq!(cve.id().as_str(), "CVE-2024-1234");
+        assert_eq!(store.find(cve.id()), Some(ghsa.clone()));
+        assert_eq!(store.put(cve.clone()), vec![ghsa]);
+
+        assert_eq!(
+            store.find(&id("GHSA-xvch-5gv4-984h", &[])),
+            Some(cve.clone())
+        );
+        assert_eq!(store.find(&id("CVE-2024-1234", &[])), Some(cve.clone()));
+        assert_eq!(store.find(&id("CVE-2024-9999", &[])), None);
+        assert_eq!(store.list(), vec![cve]);
+    }
+
+    #[test]
+    fn vulnerabilities_linked_by_a_record_should_be_replaced_together() {
+        let store = InMemoryVulnerabilityStore::default();
+        let ghsa = Vulnerability::new(id("GHSA-xvch-5gv4-984h", &[]));
+        let pysec = Vulnerability::new(id("PYSEC-2024-12", &[]));
+        store.put(ghsa.clone());
+        store.put(pysec.clone());
+
+        let linked = Vulnerability::new(id("GHSA-xvch-5gv4-984h", &["PYSEC-2024-12"]));
+        assert_eq!(store.put(linked.clone()), vec![ghsa, pysec]);
+        assert_eq!(store.list(), vec![linked]);
+    }
+}
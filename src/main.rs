@@ -4,20 +4,45 @@ use venom::config::VenomConfig;
 
 use actix::prelude::*;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
+use venom::application::saga::classification_outdated::ClassificationOutdatedSaga;
 use venom::application::saga::sbom_generation::SbomGenerationSaga;
 use venom::infrastructure::bus::in_memory_event::InMemoryEventBus;
-use venom::infrastructure::generator::syft::SyftSbomGenerator;
+use venom::infrastructure::feed::osv::OsvFeed;
+use venom::infrastructure::generator;
+use venom::infrastructure::journal::in_memory_component::InMemoryComponentJournal;
+use venom::infrastructure::projection::in_memory_managed_vulnerability::InMemoryManagedVulnerabilityIndex;
+use venom::infrastructure::projection::in_memory_sbom_generation_failure::InMemorySbomGenerationFailureIndex;
+use venom::infrastructure::store::filesystem::FileSystemSbomStore;
+use venom::infrastructure::store::in_memory_vulnerability::InMemoryVulnerabilityStore;
 use venom::{
     application::{
-        aggregate::component::{
-            cmd::{ComponentCommand, ComponentCommandKind},
-            supervisor::ComponentSupervisor,
+        aggregate::{
+            collection::{
+                cmd::{CollectionCommand, CollectionCommandKind},
+                supervisor::CollectionSupervisor,
+            },
+            component::{
+                cmd::{ComponentCommand, ComponentCommandKind},
+                event::{
+                    ComponentDeprecatedEvent, ComponentRegisteredEvent,
+                    ExecutionContextReplacedEvent, SbomAssignedEvent, SbomGenerationFailedEvent,
+                    SbomGenerationRequestedEvent,
+                },
+                supervisor::ComponentSupervisor,
+            },
+            vulnerability::{
+                event::{ManagedVulnerabilityRegisteredEvent, VulnerabilityUpdatedEvent},
+                supervisor::ManagedVulnerabilitySupervisor,
+            },
         },
-        shared::command::CommandBus,
+        service::sbom_generation_failures::SbomGenerationFailures,
+        service::vulnerability_ingestion::VulnerabilityIngestion,
+        shared::{command::CommandBus, retry::RetryPolicy},
     },
-    domain::component::id::ComponentId,
+    domain::{collection::id::CollectionId, component::id::ComponentId},
 };
 
 #[actix::main]
@@ -43,14 +68,69 @@ async fn main() {
     let cmd_bus = Arc::new(Mutex::new(CommandBus::default()));
     let event_bus = Arc::new(InMemoryEventBus::default());
 
+    let journal = Arc::new(InMemoryComponentJournal::default());
+    let _ = event_bus.subscribe::<ComponentRegisteredEvent, _>(journal.clone());
+    let _ = event_bus.subscribe::<SbomGenerationFailedEvent, _>(journal.clone());
+    let _ = event_bus.subscribe::<SbomGenerationRequestedEvent, _>(journal.clone());
+    let _ = event_bus.subscribe::<SbomAssignedEvent, _>(journal.clone());
+    let _ = event_bus.subscribe::<ComponentDeprecatedEvent, _>(journal.clone());
+
     let supervisor = ComponentSupervisor::new(event_bus.clone()).start();
-    let generator = Box::new(SyftSbomGenerator::new(&config.sboms_path).unwrap());
-    let sbom_saga = SbomGenerationSaga::new(cmd_bus.clone(), generator).start();
-    let _ = event_bus.subscribe(Arc::new(sbom_saga));
+    let collections = CollectionSupervisor::new(event_bus.clone(), journal).start();
+    let sbom_store = Arc::new(FileSystemSbomStore::new(&config.sboms_path).unwrap());
+    let generator = generator::from_config(&config, sbom_store).unwrap();
+    let sbom_saga = Arc::new(
+        SbomGenerationSaga::new(
+            cmd_bus.clone(),
+            generator,
+            Duration::from_secs(config.sbom_generation.timeout_secs.get()),
+            config.sbom_generation.max_concurrent,
+            RetryPolicy::new(
+                config.sbom_generation.max_attempts,
+                Duration::from_secs(config.sbom_generation.initial_backoff_secs),
+                Duration::from_secs(config.sbom_generation.max_backoff_secs),
+            ),
+        )
+        .start(),
+    );
+    let _ = event_bus.subscribe::<ComponentRegisteredEvent, _>(sbom_saga.clone());
+    let _ = event_bus.subscribe::<SbomGenerationRequestedEvent, _>(sbom_saga.clone());
+    let _ = event_bus.subscribe::<ComponentDeprecatedEvent, _>(sbom_saga);
+
+    let sbom_failures = Arc::new(InMemorySbomGenerationFailureIndex::default());
+    let _ = event_bus.subscribe::<SbomGenerationFailedEvent, _>(sbom_failures.clone());
+    let _ = event_bus.subscribe::<SbomAssignedEvent, _>(sbom_failures.clone());
+    let _ = event_bus.subscribe::<ComponentDeprecatedEvent, _>(sbom_failures.clone());
+    let sbom_failures = SbomGenerationFailures::new(cmd_bus.clone(), sbom_failures);
+
+    let managed_vulnerabilities = Arc::new(InMemoryManagedVulnerabilityIndex::default());
+    let _ = event_bus
+        .subscribe::<ManagedVulnerabilityRegisteredEvent, _>(managed_vulnerabilities.clone());
+    let vulnerabilities = ManagedVulnerabilitySupervisor::new(event_bus.clone()).start();
+    let outdated_saga =
+        Arc::new(ClassificationOutdatedSaga::new(cmd_bus.clone(), managed_vulnerabilities).start());
+    let _ = event_bus.subscribe::<ExecutionContextReplacedEvent, _>(outdated_saga.clone());
+    let _ = event_bus.subscribe::<VulnerabilityUpdatedEvent, _>(outdated_saga);
 
     // Only mutable for registering
-    let mut cmd_bus = cmd_bus.lock().unwrap();
-    cmd_bus.register(supervisor);
+    cmd_bus.lock().unwrap().register(supervisor);
+    cmd_bus.lock().unwrap().register(collections);
+    cmd_bus.lock().unwrap().register(vulnerabilities);
+
+    let ingestion = VulnerabilityIngestion::new(
+        Arc::new(InMemoryVulnerabilityStore::default()),
+        event_bus.clone(),
+    );
+    for source in &config.osv_sources {
+        match ingestion.ingest(&OsvFeed::new(source)) {
+            Ok(report) => {
+                for rejected in report.rejected {
+                    info!("❌ Rejected vulnerability record: {rejected}");
+                }
+            }
+            Err(e) => info!("❌ Failed to ingest {source}: {e}"),
+        }
+    }
 
     let components = vec![
         "docker.io/library/nginx:1.21",
@@ -65,18 +145,40 @@ async fn main() {
         "docker.io/library/ubuntu:24.04",
     ];
 
-    for ref_id in components {
+    for ref_id in &components {
         let id = ComponentId::from_str(ref_id).unwrap();
         let cmd = Box::new(ComponentCommand {
             id,
             kind: ComponentCommandKind::Register,
         });
 
-        let result = cmd_bus.dispatch(cmd);
+        let result = cmd_bus.lock().unwrap().dispatch(cmd);
         match result {
             Ok(()) => info!("✅ Dispatched {ref_id}"),
             Err(e) => info!("❌ Failed dispatch for {ref_id}: {e}"),
         }
     }
+
+    // Let the journal catch up with the registrations before using them as members.
+    actix::clock::sleep(std::time::Duration::from_secs(1)).await;
+    let cmd = Box::new(CollectionCommand {
+        id: CollectionId::new("official-images").unwrap(),
+        kind: CollectionCommandKind::Create(
+            components
+                .iter()
+                .map(|ref_id| ComponentId::from_str(ref_id).unwrap())
+                .collect(),
+        ),
+    });
+    if let Err(e) = cmd_bus.lock().unwrap().dispatch(cmd) {
+        info!("❌ Failed dispatch for collection: {e}");
+    }
     actix::clock::sleep(std::time::Duration::from_secs(25)).await;
+
+    for failure in sbom_failures.list() {
+        info!(
+            "❌ SBOM generation failed for {} after {} attempt(s): {}",
+            failure.component_id, failure.attempts, failure.reason
+        );
+    }
 }